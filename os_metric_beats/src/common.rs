@@ -1,9 +1,13 @@
 pub use std::{
-    collections::HashMap, env, fs, fs::File, future::Future, io::BufReader, io::Write, sync::Arc,
-    thread::sleep as std_sleep,
+    collections::{BTreeMap, HashMap, VecDeque}, env, fs, fs::File, future::Future, io::BufReader, io::Write, sync::Arc,
+    thread::sleep as std_sleep, time::Instant,
 };
 
-pub use tokio::{time::sleep, time::Duration};
+pub use tokio::{
+    select,
+    sync::mpsc,
+    time::{interval, sleep, Duration},
+};
 
 pub use log::{error, info, warn};
 
@@ -13,23 +17,32 @@ pub use flexi_logger::{Age, Cleanup, Criterion, FileSpec, Logger, Naming, Record
 
 pub use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-pub use serde_json::{from_reader, Value};
+pub use serde_json::{from_reader, json, Value};
 
 pub use elasticsearch::{
+    auth::Credentials,
     cat::CatIndicesParts,
     cluster::ClusterHealthParts,
+    http::headers::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE, RETRY_AFTER},
     http::response::Response,
+    http::Method,
     http::transport::{SingleNodeConnectionPool, Transport, TransportBuilder},
     http::Url,
-    indices::IndicesDeleteParts,
-    nodes::NodesStatsParts,
-    Elasticsearch, IndexParts,
+    ilm::{IlmGetLifecycleParts, IlmPutLifecycleParts},
+    indices::{
+        IndicesDeleteParts, IndicesGetIndexTemplateParts, IndicesGetMappingParts,
+        IndicesPutIndexTemplateParts,
+    },
+    nodes::{NodesInfoParts, NodesStatsParts},
+    CreateParts, DeleteParts, Elasticsearch, GetParts, IndexParts,
 };
 
-pub use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+pub use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 
 pub use anyhow::{anyhow, Result};
 
+pub use base64::{prelude::BASE64_STANDARD, Engine};
+
 pub use derive_new::new;
 pub use getset::Getters;
 
@@ -37,11 +50,11 @@ pub use futures::future::join_all;
 
 pub use async_trait::async_trait;
 
-pub use once_cell::sync::Lazy as once_lazy;
+pub use once_cell::sync::{Lazy as once_lazy, OnceCell};
 
 pub use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
-pub use sysinfo::{ComponentExt, CpuExt, DiskExt, NetworkExt, NetworksExt, System, SystemExt, ProcessExt};
+pub use sysinfo::{ComponentExt, CpuExt, DiskExt, NetworkExt, NetworksExt, PidExt, System, SystemExt, ProcessExt};
 
 pub use local_ip_address::local_ip;
 
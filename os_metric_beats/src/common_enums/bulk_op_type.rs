@@ -0,0 +1,19 @@
+use crate::common::*;
+
+#[doc = "_bulk 요청의 action 종류 - data stream 은 create 만 허용한다."]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BulkOpType {
+    #[default]
+    Index,
+    Create,
+}
+
+impl BulkOpType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BulkOpType::Index => "index",
+            BulkOpType::Create => "create",
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::common::*;
+
+#[doc = "에이전트가 색인하는 문서 종류 - 문서 종류별 pipeline/색인 대상 설정의 key 로 사용된다."]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DocType {
+    /* 시스템/파일시스템/프로세스/네트워크/Elasticsearch 프로세스 지표 - 한 문서에 함께 담기므로 system, filesystem, process 도 같은 종류로 읽는다. */
+    #[serde(alias = "system", alias = "filesystem", alias = "process")]
+    Metric,
+    /* search/indexing slow log 집계 - 로그에서 만든 이벤트 문서이므로 events 로도 지정할 수 있다. */
+    #[serde(alias = "events")]
+    Slowlog,
+}
+
+impl DocType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocType::Metric => "metric",
+            DocType::Slowlog => "slowlog",
+        }
+    }
+
+    #[doc = "같은 호스트/수집 시각 안에서 문서를 구분하는 key - 문서 _id 를 만들 때 사용한다."]
+    /// 시스템 지표는 호스트당 수집 주기마다 한 건이라 key 가 필요 없고,
+    /// slow log 집계는 (slow log 종류, 인덱스) 별로 한 건씩 만들어진다.
+    pub fn entity_key(&self, document: &Value) -> String {
+        match self {
+            DocType::Metric => String::new(),
+            DocType::Slowlog => format!(
+                "{}/{}",
+                document["slowlog_type"].as_str().unwrap_or_default(),
+                document["slowlog_index"].as_str().unwrap_or_default()
+            ),
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::common::*;
+
+#[doc = "출력 대상 클러스터의 배포판 - auto 면 루트 엔드포인트(GET /)의 version.distribution 으로 판별한다."]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EsFlavour {
+    #[default]
+    Auto,
+    Elasticsearch,
+    OpenSearch,
+}
+
+impl EsFlavour {
+    #[doc = "GET / 응답으로 배포판을 판별해주는 함수 - OpenSearch 만 version.distribution 에 'opensearch' 를 내려준다."]
+    pub fn from_server_info(server_info: &Value) -> Self {
+        match server_info["version"]["distribution"].as_str() {
+            Some(distribution) if distribution.eq_ignore_ascii_case("opensearch") => EsFlavour::OpenSearch,
+            _ => EsFlavour::Elasticsearch,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::common::*;
+
+#[doc = "InfluxDB line protocol 전송 방식"]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum InfluxProtocol {
+    /* InfluxDB 2.x 호환 /api/v2/write */
+    #[default]
+    Http,
+    /* UDP listener - 응답이 없으므로 재시도하지 않는다. */
+    Udp,
+}
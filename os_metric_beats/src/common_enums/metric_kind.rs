@@ -0,0 +1,20 @@
+use crate::common::*;
+
+#[doc = "외부 exporter 로 내보내는 지표의 종류"]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricKind {
+    /* 수집 시점의 값 (사용률, 개수 등) */
+    Gauge,
+    /* 단조 증가 값 - 수집기는 직전 수집 이후의 증가분(delta)이나 커널 누적값(MetricSample::cumulative)을 넘겨준다. */
+    Counter,
+}
+
+impl MetricKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Gauge => "gauge",
+            MetricKind::Counter => "counter",
+        }
+    }
+}
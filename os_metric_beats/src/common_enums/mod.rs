@@ -1 +1,10 @@
+pub mod bulk_op_type;
+pub mod doc_type;
+pub mod es_flavour;
+pub mod influx_protocol;
+pub mod metric_kind;
+pub mod otlp_protocol;
+pub mod otlp_temporality;
+pub mod retry_class;
 pub mod tcp_state;
+pub mod tls_verification_mode;
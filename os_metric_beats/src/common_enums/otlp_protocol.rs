@@ -0,0 +1,20 @@
+use crate::common::*;
+
+#[doc = "OTLP/HTTP 요청 본문 형식 - OTEL_EXPORTER_OTLP_PROTOCOL 과 같은 값을 사용한다."]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum OtlpProtocol {
+    #[default]
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+    #[serde(rename = "http/json")]
+    HttpJson,
+}
+
+impl OtlpProtocol {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            OtlpProtocol::HttpProtobuf => "application/x-protobuf",
+            OtlpProtocol::HttpJson => "application/json",
+        }
+    }
+}
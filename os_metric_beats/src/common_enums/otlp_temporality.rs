@@ -0,0 +1,12 @@
+use crate::common::*;
+
+#[doc = "OTLP Sum(counter) 의 aggregation temporality"]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OtlpTemporality {
+    /* 수집기가 주는 직전 수집 이후의 증가분을 그대로 보낸다. */
+    #[default]
+    Delta,
+    /* 에이전트 시작 이후의 누적값으로 바꿔서 보낸다 (Prometheus 계열 backend 용) */
+    Cumulative,
+}
@@ -0,0 +1,58 @@
+use crate::common::*;
+
+use crate::model::elastic::es_response_error::*;
+
+#[doc = "Elasticsearch 요청 실패를 재시도 가능 여부로 분류한 결과"]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RetryClass {
+    Retryable { retry_after: Option<Duration> },
+    Permanent,
+}
+
+impl RetryClass {
+    #[doc = "HTTP 상태코드 기준 분류 - 과부하/게이트웨이 오류만 재시도하고, 나머지(ex. 400 mapping 오류)는 다시 보내도 실패한다."]
+    pub fn from_status(status: u16, retry_after: Option<Duration>) -> Self {
+        match status {
+            429 | 502 | 503 | 504 => RetryClass::Retryable { retry_after },
+            _ => RetryClass::Permanent,
+        }
+    }
+
+    #[doc = "요청 에러 분류 - 응답을 받지 못한 전송 오류(연결 실패, timeout, 전송 중 연결 끊김)만 재시도 대상이다."]
+    /// 그 외 에러(ex. 직렬화/압축 실패, 응답 해석 실패)는 다시 보내도 실패하므로 Permanent 로 보고 dead-letter 로 보낸다.
+    pub fn from_error(err: &anyhow::Error) -> Self {
+        /* 재시도/노드 실패 context 가 덧붙여져 있어도 원인 중 응답 에러를 찾는다. */
+        if let Some(response_error) = err.chain().find_map(|cause| cause.downcast_ref::<EsResponseError>()) {
+            return Self::from_status(response_error.status, response_error.retry_after);
+        }
+
+        if err.chain().any(Self::is_transport_error) {
+            RetryClass::Retryable { retry_after: None }
+        } else {
+            RetryClass::Permanent
+        }
+    }
+
+    #[doc = "에러 원인 중 하나가 전송 계층 오류인지 확인해주는 함수"]
+    fn is_transport_error(cause: &(dyn std::error::Error + 'static)) -> bool {
+        if let Some(reqwest_error) = cause.downcast_ref::<reqwest::Error>() {
+            return reqwest_error.is_connect()
+                || reqwest_error.is_timeout()
+                || reqwest_error.is_request()
+                || reqwest_error.is_body();
+        }
+
+        if let Some(es_error) = cause.downcast_ref::<elasticsearch::Error>() {
+            /* 상태코드가 있는 에러는 응답을 받은 것이므로 전송 오류가 아니다. */
+            return es_error.is_timeout() || es_error.status_code().is_none() && Self::has_transport_source(es_error);
+        }
+
+        /* TCP 연결, TLS handshake 같은 소켓 I/O 실패 (ex. ca_fingerprint 확인) */
+        cause.downcast_ref::<std::io::Error>().is_some()
+    }
+
+    #[doc = "elasticsearch::Error 가 감싸고 있는 원인이 reqwest 전송 오류인지 확인해주는 함수"]
+    fn has_transport_source(es_error: &elasticsearch::Error) -> bool {
+        std::error::Error::source(es_error).is_some_and(Self::is_transport_error)
+    }
+}
@@ -0,0 +1,12 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 서버 인증서 검증 방식"]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TlsVerificationMode {
+    /* 신뢰하는 CA 서명 + 호스트명까지 검증 */
+    #[default]
+    Full,
+    /* 검증하지 않음 - 장애 진단용으로만 사용 */
+    None,
+}
@@ -2,28 +2,36 @@ use crate::common::*;
 
 use crate::repository::es_repository::*;
 
-use crate::traits::{metirc_service::*, request_service::*};
+use crate::traits::{metirc_service::*, node_info_service::*, request_service::*};
 
+use crate::model::elastic::es_node_identity::*;
+use crate::model::exporter::{metric_detail::*, metric_snapshot::*};
 use crate::model::metric_info::*;
 use crate::model::network_packet::network_packet_info::*;
 use crate::model::network::network_socket_info::*;
 use crate::model::network::network_usage::*;
 use crate::model::system_config::*;
-use crate::model::memory::os_mem_res::*;
+use crate::model::memory::{mmap_info::*, os_mem_res::*};
+use crate::model::process::thread_pool_info::*;
+use crate::model::jvm::gc_log_stats::*;
+use crate::model::slowlog::{slowlog_metric_info::*, slowlog_stats::*};
 
 use crate::utils_module::io_utils::*;
 use crate::utils_module::time_utils::*;
 
 use crate::env_configuration::env_config::*;
 
-pub struct MainHandler<M: MetricService, R: RequestService> {
+pub struct MainHandler<M: MetricService, R: RequestService, N: NodeInfoService> {
     metric_service: M,
     request_service: R,
+    node_info_service: N,
     private_ip: String,
+    /* 직전에 exporter 로 내보낸 수집 결과의 수집 시각 (epoch millis) */
+    last_collected_at_ms: Option<i64>,
 }
 
-impl<M: MetricService, R: RequestService> MainHandler<M, R> {
-    pub fn new(metric_service: M, request_service: R) -> Self {
+impl<M: MetricService, R: RequestService, N: NodeInfoService> MainHandler<M, R, N> {
+    pub fn new(metric_service: M, request_service: R, node_info_service: N) -> Self {
         let private_ip: String = match local_ip() {
             Ok(ip) => ip.to_string(),
             Err(_err) => match read_toml_from_file::<SystemConfig>(&SYSTEM_INFO) {
@@ -38,7 +46,9 @@ impl<M: MetricService, R: RequestService> MainHandler<M, R> {
         Self {
             metric_service,
             request_service,
+            node_info_service,
             private_ip,
+            last_collected_at_ms: None,
         }
     }
 
@@ -48,11 +58,15 @@ impl<M: MetricService, R: RequestService> MainHandler<M, R> {
         let cur_utc_time_str: String =
             get_str_from_naivedatetime(cur_utc_time, "%Y-%m-%dT%H:%M:%SZ")?;
 
-        let es_conn: Arc<EsRepositoryPub> = get_elastic_conn();
+        let es_conns: Vec<Arc<EsRepositoryPub>> = get_elastic_conns();
         
+        /* 프로세스 목록은 수집 주기마다 한 번만 갱신하고 아래 지표들이 같은 목록을 사용한다. */
+        self.metric_service.refresh_processes();
+
         /* 각 metric 값 호출 */
         let system_cpu_usage: f32 = self.metric_service.get_cpu_usage();
         let system_disk_usage: f32 = self.metric_service.get_disk_usage();
+        let disk_mounts: BTreeMap<String, f64> = self.metric_service.get_disk_usage_by_mount();
         let system_memory_usage: f32 = self.metric_service.get_memory_usage();
         let system_network_usage: NetworkUsage = self.metric_service.get_network_usage()?;
         let process_count: usize = self.metric_service.get_process_count();
@@ -64,14 +78,42 @@ impl<M: MetricService, R: RequestService> MainHandler<M, R> {
         let process_mem_total: OsMemRes = self.metric_service.get_process_mem_usage()?;
         let process_use_mem: u64 = process_mem_total.working_set_size;
         let process_virtual_mem: u64 = process_mem_total.virtual_size;
+
+        /* Elasticsearch 프로세스의 mmap 개수 (vm.max_map_count 대비) 수집 */
+        let mmap_info: MmapInfo = self.metric_service.get_process_mmap_info()?;
+
+        /* Elasticsearch 스레드를 thread pool 별로 묶은 CPU 사용 지표 수집 */
+        let thread_pool_info: ThreadPoolInfo = self.metric_service.get_thread_pool_info()?;
+
+        /* gc.log 기반 GC pause 지표 수집 */
+        let gc_log_stats: GcLogStats = self.metric_service.get_gc_log_stats()?;
+
+        /* slow log 를 인덱스 별로 집계한 지표 수집 */
+        let slowlog_stats: Vec<SlowLogStats> = self.metric_service.get_slowlog_stats()?;
+
+        /* 로컬 Elasticsearch 노드 정보 (노드 id/이름, 클러스터, 역할, 속성) */
+        let es_node: Option<EsNodeIdentity> = self.node_info_service.get_node_identity().await;
         
-        let log_index_name: &String = es_conn.index_pattern();
+        /* 실제 색인 대상은 출력(클러스터) 별 설정에 따라 정해진다. */
+        let index_date: String = get_str_from_naivedatetime(cur_utc_time, "%Y%m%d")?;
 
-        let index_name: String = format!(
-            "{}{}",
-            log_index_name,
-            get_str_from_naivedatetime(cur_utc_time, "%Y%m%d")?
-        );
+        let slowlog_infos: Vec<SlowLogMetricInfo> = slowlog_stats
+            .into_iter()
+            .map(|stats| {
+                SlowLogMetricInfoBuilder::default()
+                    .timestamp(cur_utc_time_str.clone())
+                    .host(self.private_ip.clone())
+                    .slowlog_type(stats.slowlog_type)
+                    .slowlog_index(stats.index_name)
+                    .slow_count(stats.slow_count)
+                    .took_max_ms(stats.took_max_ms)
+                    .took_avg_ms(stats.took_avg_ms)
+                    .top_shard(stats.top_shard)
+                    .worst_source(stats.worst_source)
+                    .es_node(es_node.clone())
+                    .build()
+            })
+            .collect::<Result<Vec<SlowLogMetricInfo>, _>>()?;
 
         let metric_info = MetricInfoBuilder::default()
             .timestamp(cur_utc_time_str)
@@ -94,12 +136,53 @@ impl<M: MetricService, R: RequestService> MainHandler<M, R> {
             .tcp_close_wait(network_socket_info.tcp_close_wait)
             .process_use_mem(process_use_mem)
             .process_virtual_mem(process_virtual_mem)
+            .vm_max_map_count(mmap_info.max_map_count)
+            .es_process_mmaps(mmap_info.processes)
+            .es_thread_pools(thread_pool_info.thread_pools)
+            .es_thread_states(thread_pool_info.thread_states)
+            .gc_pause_count(gc_log_stats.pause_count)
+            .gc_pause_total_ms(gc_log_stats.pause_total_ms)
+            .gc_pause_max_ms(gc_log_stats.pause_max_ms)
+            .gc_pause_p99_ms(gc_log_stats.pause_p99_ms)
+            .gc_heap_before_mb(gc_log_stats.heap_before_mb)
+            .gc_heap_after_mb(gc_log_stats.heap_after_mb)
+            .gc_to_space_exhausted(gc_log_stats.to_space_exhausted)
+            .gc_full_gc(gc_log_stats.full_gc)
+            .es_node(es_node)
+            .es_output_nodes(es_conns.iter().flat_map(|es_conn| es_conn.node_stats()).collect::<Vec<_>>())
+            .es_output_transport(es_conns.iter().map(|es_conn| es_conn.transport_stats()).collect::<Vec<_>>())
             .build()?;
-        
+
+        let metric_detail: MetricDetail = MetricDetail {
+            network_interfaces: system_network_usage.interfaces,
+            network_packet_interfaces: network_packet_info.interfaces,
+            disk_mounts,
+            es_process_memory: process_mem_total.processes,
+        };
+
+        /* Elasticsearch 전송 실패와 관계없이 다른 모니터링 시스템에는 최신 값을 내보낸다. */
+        let collected_at_ms: i64 = cur_utc_time.and_utc().timestamp_millis();
+        let snapshot: MetricSnapshot = MetricSnapshot::from_collected(
+            collected_at_ms,
+            self.last_collected_at_ms.replace(collected_at_ms),
+            &metric_info,
+            &metric_detail,
+            &slowlog_infos,
+        );
+        if let Err(err) = self.request_service.request_metric_to_exporters(snapshot).await {
+            error!("[ERROR][MainHandler->task_set] {:?}", err);
+        }
+
         self.request_service
-            .request_metric_to_elastic(index_name, metric_info)
+            .request_metric_to_elastic(index_date.clone(), metric_info)
             .await?;
 
+        if !slowlog_infos.is_empty() {
+            self.request_service
+                .request_slowlog_to_elastic(index_date, slowlog_infos)
+                .await?;
+        }
+
         info!("System metrics collection completed successfully.");
 
         Ok(())
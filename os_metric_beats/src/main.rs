@@ -21,10 +21,14 @@ pub mod handler;
 use handler::main_handler::*;
 
 pub mod repository;
+use repository::es_repository::*;
 
 pub mod service;
+use service::index_setup_service_impl::*;
 use service::linux_metric_service_impl::*;
+use service::node_info_service_impl::*;
 use service::request_service_impl::*;
+use service::retention_service_impl::*;
 use service::windows_metirc_service_impl::*;
 
 pub mod model;
@@ -38,6 +42,7 @@ pub mod env_configuration;
 use env_configuration::env_config::*;
 
 pub mod traits;
+use traits::{index_setup_service::*, retention_service::*};
 
 pub mod common_enums;
 
@@ -56,6 +61,34 @@ async fn main() {
         }
     };
 
+    let es_conns: Vec<Arc<EsRepositoryPub>> = get_elastic_conns();
+
+    /* 색인 전에 출력 별로 index template 을 동시에 설치 - 실패한 출력은 수집을 먼저 시작하고 백그라운드에서 재시도한다. */
+    join_all(es_conns.iter().map(|es_conn| async move {
+        let index_setup_service: IndexSetupServiceImpl = IndexSetupServiceImpl::new(es_conn.clone());
+        if let Err(err) = index_setup_service.setup_index_templates().await {
+            error!("[ERROR][main] [{}] {:?}", es_conn.output_name(), err);
+            tokio::spawn(run_index_setup(index_setup_service));
+        }
+    }))
+    .await;
+
+    for es_conn in es_conns {
+        /* 클러스터에 새로 추가된 노드를 주기적으로 connection pool 에 반영 */
+        if *es_conn.node_pool_config().sniff_enabled() {
+            tokio::spawn(run_node_sniff(es_conn.clone()));
+        }
+
+        /* ILM 이 없는 클러스터를 위한 날짜별 인덱스 정리 작업 */
+        if let Some(retention_config) = es_conn.retention_config().clone() {
+            let interval_secs: u64 = *retention_config.interval_secs();
+            tokio::spawn(run_retention_cleanup(
+                RetentionServiceImpl::new(es_conn, retention_config),
+                Duration::from_secs(interval_secs),
+            ));
+        }
+    }
+
     /* Operation System 별로 구분 -> Windows/Linux */
     let os_ver: String = system_config.os_ver.to_lowercase();
 
@@ -69,16 +102,54 @@ async fn main() {
     }
 }
 
+#[doc = "index template 설치가 성공할 때까지 주기적으로 재시도해주는 함수"]
+async fn run_index_setup<I: IndexSetupService>(index_setup_service: I) {
+    loop {
+        sleep(Duration::from_secs(30)).await;
+
+        match index_setup_service.setup_index_templates().await {
+            Ok(_) => break,
+            Err(err) => error!("[ERROR][run_index_setup] {:?}", err),
+        }
+    }
+}
+
+#[doc = "Elasticsearch 노드 목록을 주기적으로 sniff 해주는 함수"]
+async fn run_node_sniff(es_conn: Arc<EsRepositoryPub>) {
+    let sniff_interval: Duration = Duration::from_secs(*es_conn.node_pool_config().sniff_interval_secs());
+
+    loop {
+        if let Err(err) = es_conn.sniff_nodes().await {
+            error!("[ERROR][run_node_sniff] {:?}", err);
+        }
+
+        sleep(sniff_interval).await;
+    }
+}
+
+#[doc = "보관 기간이 지난 인덱스 정리 작업을 주기적으로 실행해주는 함수"]
+async fn run_retention_cleanup<S: RetentionService>(retention_service: S, cleanup_interval: Duration) {
+    loop {
+        if let Err(err) = retention_service.cleanup_expired_indices().await {
+            error!("[ERROR][run_retention_cleanup] {:?}", err);
+        }
+
+        sleep(cleanup_interval).await;
+    }
+}
+
 #[doc = "Windows OS 전용 모드"]
 async fn run_windows_mode() {
     info!("Running in Windows mode");
 
     let os_metirc_service: WindowsMetricServiceImpl = WindowsMetricServiceImpl::new();
     let request_service: RequestServiceImpl = RequestServiceImpl::new();
+    let node_info_service: NodeInfoServiceImpl = NodeInfoServiceImpl::new();
     let mut main_handler: MainHandler<
         WindowsMetricServiceImpl,
         RequestServiceImpl,
-    > = MainHandler::new(os_metirc_service, request_service);
+        NodeInfoServiceImpl,
+    > = MainHandler::new(os_metirc_service, request_service, node_info_service);
 
     loop {
         match main_handler.task_set().await {
@@ -100,10 +171,12 @@ async fn run_linux_mode() {
 
     let os_metirc_service: LinuxMetricServiceImpl = LinuxMetricServiceImpl::new();
     let request_service: RequestServiceImpl = RequestServiceImpl::new();
+    let node_info_service: NodeInfoServiceImpl = NodeInfoServiceImpl::new();
     let mut main_handler: MainHandler<
         LinuxMetricServiceImpl,
         RequestServiceImpl,
-    > = MainHandler::new(os_metirc_service, request_service);
+        NodeInfoServiceImpl,
+    > = MainHandler::new(os_metirc_service, request_service, node_info_service);
     
     loop {
         match main_handler.task_set().await {
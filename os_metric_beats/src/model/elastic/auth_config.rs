@@ -0,0 +1,49 @@
+use crate::common::*;
+
+use crate::model::elastic::secret_value::*;
+
+#[doc = "Elasticsearch 인증 방식 - Authorization 헤더로 전송된다."]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuthConfig {
+    Basic {
+        username: String,
+        password: SecretValue,
+    },
+    /* "id:api_key" 형식 또는 ES 가 발급한 base64 encoded 값 */
+    ApiKey {
+        api_key: SecretValue,
+    },
+    /* service account token */
+    Bearer {
+        token: SecretValue,
+    },
+}
+
+impl AuthConfig {
+    #[doc = "Authorization 헤더 값을 만들어주는 함수"]
+    pub fn to_header_value(&self) -> Result<HeaderValue, anyhow::Error> {
+        let header: String = match self {
+            AuthConfig::Basic { username, password } => {
+                let credentials: String = format!("{}:{}", username, password.resolve()?);
+                format!("Basic {}", BASE64_STANDARD.encode(credentials))
+            }
+            AuthConfig::ApiKey { api_key } => {
+                let api_key: String = api_key.resolve()?;
+                let encoded: String = if api_key.contains(':') {
+                    BASE64_STANDARD.encode(api_key)
+                } else {
+                    api_key
+                };
+                format!("ApiKey {}", encoded)
+            }
+            AuthConfig::Bearer { token } => format!("Bearer {}", token.resolve()?),
+        };
+
+        let mut header_value: HeaderValue = HeaderValue::from_str(header.trim())
+            .map_err(|_| anyhow!("[ERROR][AuthConfig->to_header_value] Credential contains invalid header characters"))?;
+        header_value.set_sensitive(true);
+
+        Ok(header_value)
+    }
+}
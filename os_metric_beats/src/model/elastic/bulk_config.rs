@@ -0,0 +1,24 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct BulkConfig {
+    pub max_docs: usize,
+    pub max_bytes: usize,
+    pub max_age_secs: u64,
+    pub max_item_retries: u32,
+    pub queue_capacity: usize,
+}
+
+impl Default for BulkConfig {
+    fn default() -> Self {
+        BulkConfig {
+            max_docs: 500,
+            max_bytes: 5 * 1024 * 1024,
+            max_age_secs: 10,
+            max_item_retries: 3,
+            queue_capacity: 10_000,
+        }
+    }
+}
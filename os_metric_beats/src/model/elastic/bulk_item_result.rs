@@ -0,0 +1,133 @@
+use crate::common::*;
+
+use crate::common_enums::retry_class::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct BulkItemResult {
+    pub status: u16,
+    pub error_type: Option<String>,
+    pub error_reason: Option<String>,
+}
+
+impl BulkItemResult {
+    #[doc = "_bulk API 응답에서 요청 순서대로 각 문서의 처리 결과를 꺼내주는 함수"]
+    /// # Arguments
+    /// * `response_body` - _bulk API 응답 body
+    ///
+    /// # Returns
+    /// * Result<Vec<BulkItemResult>, anyhow::Error>
+    pub fn from_bulk_response(response_body: &Value) -> Result<Vec<Self>, anyhow::Error> {
+        let items: &Vec<Value> = response_body
+            .get("items")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("[ERROR][BulkItemResult->from_bulk_response] 'items' does not exist in bulk response"))?;
+
+        items
+            .iter()
+            .map(|item| {
+                /* { "index": { "status": 201, "error": { "type": ..., "reason": ... } } } */
+                let result: &Value = item
+                    .as_object()
+                    .and_then(|item| item.values().next())
+                    .ok_or_else(|| anyhow!("[ERROR][BulkItemResult->from_bulk_response] invalid bulk item: {}", item))?;
+
+                let status: u16 = result.get("status").and_then(Value::as_u64).unwrap_or(0) as u16;
+                let error: Option<&Value> = result.get("error");
+
+                Ok(BulkItemResult::new(
+                    status,
+                    error
+                        .and_then(|e| e.get("type"))
+                        .and_then(Value::as_str)
+                        .map(String::from),
+                    error
+                        .and_then(|e| e.get("reason"))
+                        .and_then(Value::as_str)
+                        .map(String::from),
+                ))
+            })
+            .collect()
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    #[doc = "같은 _id 의 문서가 이미 색인되어 create 가 거절된 경우 - 이전 시도가 실제로는 반영된 것이므로 성공으로 본다."]
+    pub fn is_duplicate(&self) -> bool {
+        self.status == 409 && self.error_type.as_deref() == Some("version_conflict_engine_exception")
+    }
+
+    #[doc = "재시도 하면 성공할 수 있는 실패인지 여부 (429, 502/503/504)"]
+    pub fn is_retryable(&self) -> bool {
+        matches!(RetryClass::from_status(self.status, None), RetryClass::Retryable { .. })
+    }
+
+    #[doc = "다시 보내야 하는 첫 문서의 위치를 구해주는 함수 - 응답에 결과가 빠진 문서도 다시 보낸다."]
+    /// # Arguments
+    /// * `item_results` - _bulk 응답의 문서별 결과
+    /// * `doc_count`    - 요청에 담은 문서 수
+    ///
+    /// # Returns
+    /// * Option<usize> - 모든 문서가 처리(성공 혹은 영구 거부)되었다면 None
+    pub fn first_retry_index(item_results: &[Self], doc_count: usize) -> Option<usize> {
+        (0..doc_count).find(|idx| item_results.get(*idx).is_none_or(|item_result| item_result.is_retryable()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn partial_failure_response() -> Value {
+        json!({
+            "took": 3,
+            "errors": true,
+            "items": [
+                { "index": { "_index": "metric", "status": 201 } },
+                { "index": { "_index": "metric", "status": 400, "error": { "type": "mapper_parsing_exception", "reason": "failed to parse field [cpu]" } } },
+                { "create": { "_index": "metric", "status": 409, "error": { "type": "version_conflict_engine_exception", "reason": "document already exists" } } },
+                { "index": { "_index": "metric", "status": 429, "error": { "type": "es_rejected_execution_exception", "reason": "rejected execution" } } }
+            ]
+        })
+    }
+
+    #[test]
+    fn parses_partial_bulk_failures_in_request_order() {
+        let item_results: Vec<BulkItemResult> = BulkItemResult::from_bulk_response(&partial_failure_response()).unwrap();
+
+        assert_eq!(item_results.len(), 4);
+
+        assert!(item_results[0].is_success());
+        assert_eq!(item_results[0].error_type, None);
+
+        assert!(!item_results[1].is_success());
+        assert!(!item_results[1].is_retryable());
+        assert_eq!(item_results[1].error_type.as_deref(), Some("mapper_parsing_exception"));
+        assert_eq!(item_results[1].error_reason.as_deref(), Some("failed to parse field [cpu]"));
+
+        assert!(item_results[2].is_duplicate());
+        assert!(!item_results[2].is_retryable());
+
+        assert!(item_results[3].is_retryable());
+    }
+
+    #[test]
+    fn rejects_response_without_items() {
+        assert!(BulkItemResult::from_bulk_response(&json!({ "took": 1, "errors": false })).is_err());
+        assert!(BulkItemResult::from_bulk_response(&json!({ "items": [1] })).is_err());
+    }
+
+    #[test]
+    fn first_retry_index_leaves_retryable_and_missing_items() {
+        let item_results: Vec<BulkItemResult> = BulkItemResult::from_bulk_response(&partial_failure_response()).unwrap();
+
+        /* 성공/영구 거부/중복 뒤의 429 부터 다시 보낸다. */
+        assert_eq!(BulkItemResult::first_retry_index(&item_results, 4), Some(3));
+        assert_eq!(BulkItemResult::first_retry_index(&item_results[..3], 3), None);
+
+        /* 응답에 결과가 없는 문서부터 다시 보낸다. */
+        assert_eq!(BulkItemResult::first_retry_index(&item_results[..3], 5), Some(3));
+        assert_eq!(BulkItemResult::first_retry_index(&[], 2), Some(0));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::common::*;
+
+use crate::model::elastic::{bulk_config::*, es_document::*};
+
+#[doc = "_bulk 로 보내기 전까지 문서를 모아두는 메모리 큐"]
+/// 문서마다 (문서, 직렬화 크기, 큐에 들어온 시각) 을 보관한다.
+#[derive(Debug)]
+pub struct BulkQueue {
+    docs: VecDeque<(EsDocument, usize, Instant)>,
+    bytes: usize,
+    capacity: usize,
+}
+
+impl BulkQueue {
+    #[doc = "최대 capacity 개의 문서를 보관하는 큐를 만들어주는 함수"]
+    pub fn new(capacity: usize) -> Self {
+        BulkQueue {
+            docs: VecDeque::new(),
+            bytes: 0,
+            capacity,
+        }
+    }
+
+    #[doc = "큐의 맨 뒤에 문서를 추가해주는 함수 - 큐가 가득 차 있으면 문서를 버리고 에러를 반환한다."]
+    pub fn push_back(&mut self, doc: EsDocument) -> Result<(), anyhow::Error> {
+        if self.docs.len() >= self.capacity {
+            return Err(anyhow!(
+                "[ERROR][BulkQueue->push_back] Queue is full ({} documents), dropping document: index={}",
+                self.capacity,
+                doc.index_name
+            ));
+        }
+
+        let doc_bytes: usize = doc.to_bulk_lines()?.len();
+
+        self.bytes += doc_bytes;
+        self.docs.push_back((doc, doc_bytes, Instant::now()));
+
+        Ok(())
+    }
+
+    #[doc = "재시도할 문서들을 원래 순서를 유지한 채로 큐의 맨 앞에 돌려놓는 함수"]
+    /// 이미 큐에서 꺼냈던 문서이므로 capacity 를 넘더라도 버리지 않는다.
+    pub fn push_front_all(&mut self, docs: Vec<EsDocument>) -> Result<(), anyhow::Error> {
+        let requeued_at: Instant = Instant::now();
+
+        for doc in docs.into_iter().rev() {
+            let doc_bytes: usize = doc.to_bulk_lines()?.len();
+
+            self.bytes += doc_bytes;
+            self.docs.push_front((doc, doc_bytes, requeued_at));
+        }
+
+        Ok(())
+    }
+
+    #[doc = "문서 수, 바이트, 대기 시간 중 하나라도 기준을 넘으면 flush 대상"]
+    pub fn should_flush(&self, bulk_config: &BulkConfig) -> bool {
+        let Some((_, _, oldest_at)) = self.docs.front() else {
+            return false;
+        };
+
+        self.docs.len() >= *bulk_config.max_docs()
+            || self.bytes >= *bulk_config.max_bytes()
+            || oldest_at.elapsed() >= Duration::from_secs(*bulk_config.max_age_secs())
+    }
+
+    #[doc = "한 번의 _bulk 요청으로 보낼 만큼 큐의 앞쪽에서 문서를 꺼내주는 함수"]
+    pub fn drain_batch(&mut self, bulk_config: &BulkConfig) -> Vec<EsDocument> {
+        let mut batch: Vec<EsDocument> = Vec::new();
+        let mut batch_bytes: usize = 0;
+
+        while let Some((_, doc_bytes, _)) = self.docs.front() {
+            let exceeds_bytes: bool =
+                !batch.is_empty() && batch_bytes + doc_bytes > *bulk_config.max_bytes();
+
+            if batch.len() >= *bulk_config.max_docs() || exceeds_bytes {
+                break;
+            }
+
+            if let Some((doc, doc_bytes, _)) = self.docs.pop_front() {
+                batch_bytes += doc_bytes;
+                self.bytes -= doc_bytes;
+                batch.push(doc);
+            }
+        }
+
+        batch
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.docs.len() >= self.capacity
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.docs.is_empty()
+    }
+}
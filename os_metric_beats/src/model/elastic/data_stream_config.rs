@@ -0,0 +1,28 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct DataStreamConfig {
+    pub namespace: String,
+}
+
+impl Default for DataStreamConfig {
+    fn default() -> Self {
+        DataStreamConfig {
+            namespace: String::from("default"),
+        }
+    }
+}
+
+impl DataStreamConfig {
+    #[doc = "시스템 지표 문서를 색인할 data stream 이름 - metrics-os-<namespace>"]
+    pub fn metric_data_stream(&self) -> String {
+        format!("metrics-os-{}", self.namespace)
+    }
+
+    #[doc = "slow log 집계 문서를 색인할 data stream 이름 - metrics-os.slowlog-<namespace>"]
+    pub fn slowlog_data_stream(&self) -> String {
+        format!("metrics-os.slowlog-{}", self.namespace)
+    }
+}
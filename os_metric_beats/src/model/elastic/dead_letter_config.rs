@@ -0,0 +1,18 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct DeadLetterConfig {
+    pub index: String,
+    pub fallback_path: String,
+}
+
+impl Default for DeadLetterConfig {
+    fn default() -> Self {
+        DeadLetterConfig {
+            index: String::from("os-metric-beats-dead-letter"),
+            fallback_path: String::from("dead_letter.ndjson"),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::common::*;
+
+use crate::model::elastic::{bulk_item_result::*, es_document::*, es_response_error::*};
+
+use crate::utils_module::time_utils::*;
+
+#[doc = "Elasticsearch 가 영구적으로 거부한 문서를 원인과 함께 보관하는 기록"]
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DeadLetterRecord {
+    pub timestamp: String,
+    pub agent_version: String,
+    pub target_index: String,
+    pub op_type: String,
+    pub status: u16,
+    pub error_type: Option<String>,
+    pub error_reason: Option<String>,
+    /* 원본 문서를 객체로 두면 dead-letter 인덱스에서도 같은 mapping 충돌이 나므로 JSON 문자열로 보관 */
+    pub document: String,
+}
+
+impl DeadLetterRecord {
+    #[doc = "거부된 문서와 _bulk 응답의 문서별 결과로 기록을 만들어주는 함수"]
+    pub fn from_rejected(doc: &EsDocument, item_result: &BulkItemResult) -> Self {
+        DeadLetterRecord {
+            timestamp: get_currnet_utc_str(),
+            agent_version: String::from(env!("CARGO_PKG_VERSION")),
+            target_index: doc.index_name.clone(),
+            op_type: String::from(doc.op_type.as_str()),
+            status: item_result.status,
+            error_type: item_result.error_type.clone(),
+            error_reason: item_result.error_reason.clone(),
+            document: doc.source.to_string(),
+        }
+    }
+
+    #[doc = "_bulk 요청 자체가 거부된 경우 요청 에러의 상태코드/사유로 기록을 만들어주는 함수"]
+    /// 응답을 받지 못한 에러(ex. 연결 실패)라면 status 는 0 이고 에러 메시지를 사유로 남긴다.
+    pub fn from_request_error(doc: &EsDocument, err: &anyhow::Error) -> Self {
+        let response_error: Option<&EsResponseError> = err.chain().find_map(|cause| cause.downcast_ref::<EsResponseError>());
+
+        DeadLetterRecord {
+            timestamp: get_currnet_utc_str(),
+            agent_version: String::from(env!("CARGO_PKG_VERSION")),
+            target_index: doc.index_name.clone(),
+            op_type: String::from(doc.op_type.as_str()),
+            status: response_error.map(|e| e.status).unwrap_or(0),
+            error_type: response_error.and_then(|e| e.error_type.clone()),
+            error_reason: response_error
+                .and_then(|e| e.error_reason.clone())
+                .or_else(|| Some(err.to_string())),
+            document: doc.source.to_string(),
+        }
+    }
+
+    #[doc = "spool 에서 역직렬화하지 못한 record 를 원문 그대로 기록으로 만들어주는 함수"]
+    pub fn from_undecodable(payload: String, reason: String) -> Self {
+        DeadLetterRecord {
+            timestamp: get_currnet_utc_str(),
+            agent_version: String::from(env!("CARGO_PKG_VERSION")),
+            target_index: String::new(),
+            op_type: String::new(),
+            status: 0,
+            error_type: Some(String::from("spool_decode_error")),
+            error_reason: Some(reason),
+            document: payload,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use crate::common::*;
+
+#[doc = "문서 종류별 색인 대상과 ingest pipeline - 값이 없으면 기본 규칙(index_pattern/data stream)을 따른다."]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct DocumentRouteConfig {
+    /* 날짜 접미사 없이 그대로 사용할 인덱스/alias/data stream 이름 */
+    pub index: Option<String>,
+    pub pipeline: Option<String>,
+}
+
+impl DocumentRouteConfig {
+    #[doc = "다른 설정에 값이 있는 항목만 덮어쓴 결과를 돌려주는 함수"]
+    pub fn overridden_by(&self, other: &DocumentRouteConfig) -> DocumentRouteConfig {
+        DocumentRouteConfig {
+            index: other.index.clone().or_else(|| self.index.clone()),
+            pipeline: other.pipeline.clone().or_else(|| self.pipeline.clone()),
+        }
+    }
+}
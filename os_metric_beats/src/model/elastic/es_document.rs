@@ -0,0 +1,40 @@
+use crate::common::*;
+
+use crate::common_enums::bulk_op_type::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct EsDocument {
+    pub index_name: String,
+    #[serde(default)]
+    pub op_type: BulkOpType,
+    pub source: Value,
+    #[new(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[new(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pipeline: Option<String>,
+    #[new(default)]
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl EsDocument {
+    #[doc = "_bulk API 의 action/source 두 줄(NDJSON)로 변환해주는 함수"]
+    pub fn to_bulk_lines(&self) -> Result<String, anyhow::Error> {
+        let mut action: Value = json!({});
+        action[self.op_type.as_str()] = json!({ "_index": self.index_name });
+        if let Some(id) = &self.id {
+            action[self.op_type.as_str()]["_id"] = json!(id);
+        }
+        if let Some(pipeline) = &self.pipeline {
+            action[self.op_type.as_str()]["pipeline"] = json!(pipeline);
+        }
+
+        Ok(format!(
+            "{}\n{}\n",
+            serde_json::to_string(&action)?,
+            serde_json::to_string(&self.source)?
+        ))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::common::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct EsNodeIdentity {
+    pub id: String,
+    pub name: String,
+    pub cluster_name: String,
+    pub roles: Vec<String>,
+    pub attributes: HashMap<String, String>,
+}
+
+impl EsNodeIdentity {
+    #[doc = "다른 문서에 es_node 필드로 붙을 때 사용하는 mapping - 노드 속성은 모두 keyword"]
+    pub fn index_mappings() -> Value {
+        json!({
+            "properties": {
+                "id": { "type": "keyword" },
+                "name": { "type": "keyword" },
+                "cluster_name": { "type": "keyword" },
+                "roles": { "type": "keyword" },
+                "attributes": { "type": "object" }
+            }
+        })
+    }
+
+    #[doc = "`_nodes/_local` 응답에서 노드 정보를 꺼내주는 함수"]
+    /// # Arguments
+    /// * `nodes_info` - `_nodes/_local` API 응답 body
+    ///
+    /// # Returns
+    /// * Option<EsNodeIdentity>
+    pub fn from_nodes_info(nodes_info: &Value) -> Option<Self> {
+        let cluster_name: String = nodes_info.get("cluster_name")?.as_str()?.to_string();
+        let (node_id, node) = nodes_info.get("nodes")?.as_object()?.iter().next()?;
+
+        let roles: Vec<String> = node
+            .get("roles")
+            .and_then(Value::as_array)
+            .map(|roles| {
+                roles
+                    .iter()
+                    .filter_map(|role| role.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let attributes: HashMap<String, String> = node
+            .get("attributes")
+            .and_then(Value::as_object)
+            .map(|attributes| {
+                attributes
+                    .iter()
+                    .filter_map(|(key, value)| value.as_str().map(|v| (key.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self::new(
+            node_id.clone(),
+            node.get("name")?.as_str()?.to_string(),
+            cluster_name,
+            roles,
+            attributes,
+        ))
+    }
+}
@@ -0,0 +1,29 @@
+use crate::common::*;
+
+#[doc = "전송 대상 Elasticsearch 노드 별 상태 - 에이전트 자체 모니터링 용도"]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct EsNodeStats {
+    pub output: String,
+    pub node: String,
+    pub alive: bool,
+    pub request_count: u64,
+    pub failure_count: u64,
+    pub latency_avg_ms: f64,
+    pub latency_last_ms: f64,
+}
+
+impl EsNodeStats {
+    pub fn index_mappings() -> Value {
+        json!({
+            "properties": {
+                "output": { "type": "keyword" },
+                "node": { "type": "keyword" },
+                "alive": { "type": "boolean" },
+                "request_count": { "type": "long" },
+                "failure_count": { "type": "long" },
+                "latency_avg_ms": { "type": "double" },
+                "latency_last_ms": { "type": "double" }
+            }
+        })
+    }
+}
@@ -0,0 +1,70 @@
+use crate::common::*;
+
+use crate::utils_module::http_utils::*;
+
+#[doc = "Elasticsearch 가 성공이 아닌 상태코드로 응답한 경우의 에러 - 재시도 여부 판단에 사용된다."]
+#[derive(Debug, Clone)]
+pub struct EsResponseError {
+    pub api: &'static str,
+    pub status: u16,
+    pub retry_after: Option<Duration>,
+    /* 응답 body 의 error.type / error.reason - dead-letter 기록에 사용된다. */
+    pub error_type: Option<String>,
+    pub error_reason: Option<String>,
+}
+
+impl EsResponseError {
+    pub fn from_response(api: &'static str, response: &Response) -> Self {
+        EsResponseError {
+            api,
+            status: response.status_code().as_u16(),
+            retry_after: response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after),
+            error_type: None,
+            error_reason: None,
+        }
+    }
+
+    #[doc = "_bulk 전용 HTTP client 의 응답으로 에러를 만들어주는 함수"]
+    pub fn from_http_response(api: &'static str, response: &reqwest::Response) -> Self {
+        EsResponseError {
+            api,
+            status: response.status().as_u16(),
+            retry_after: response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(parse_retry_after),
+            error_type: None,
+            error_reason: None,
+        }
+    }
+
+    #[doc = "에러 응답 body 에서 error.type / error.reason 을 꺼내 채워주는 함수 (error 가 문자열인 경우 reason 으로 사용)"]
+    pub fn with_error_body(mut self, error_body: Option<&Value>) -> Self {
+        match error_body.and_then(|body| body.get("error")) {
+            Some(Value::String(reason)) => self.error_reason = Some(reason.clone()),
+            Some(error) => {
+                self.error_type = error.get("type").and_then(Value::as_str).map(String::from);
+                self.error_reason = error.get("reason").and_then(Value::as_str).map(String::from);
+            }
+            None => (),
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for EsResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[Elasticsearch Error][{}()] Failed to send request: Status Code: {}",
+            self.api, self.status
+        )
+    }
+}
+
+impl std::error::Error for EsResponseError {}
@@ -0,0 +1,23 @@
+use crate::common::*;
+
+#[doc = "_bulk 요청 body 의 압축 전/후 누적 크기 - 에이전트 자체 모니터링 용도"]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct EsTransportStats {
+    pub output: String,
+    pub bulk_request_count: u64,
+    pub uncompressed_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+impl EsTransportStats {
+    pub fn index_mappings() -> Value {
+        json!({
+            "properties": {
+                "output": { "type": "keyword" },
+                "bulk_request_count": { "type": "long" },
+                "uncompressed_bytes": { "type": "long" },
+                "compressed_bytes": { "type": "long" }
+            }
+        })
+    }
+}
@@ -0,0 +1,168 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct IlmConfig {
+    pub policy_name: String,
+    /* hot phase - rollover 기준 (data stream 모드에서만 적용) */
+    pub rollover_max_primary_shard_size: Option<String>,
+    pub rollover_max_age: Option<String>,
+    /* warm phase */
+    pub warm_min_age: Option<String>,
+    pub warm_shrink_shards: Option<u32>,
+    pub warm_forcemerge_segments: Option<u32>,
+    /* delete phase - ex) "30d" */
+    pub delete_min_age: Option<String>,
+}
+
+impl IlmConfig {
+    #[doc = "_ilm/policy API 로 보낼 본문을 만들어주는 함수 - 재기동 시 변경 여부 비교를 위해 _meta 에 checksum 을 남긴다."]
+    /// # Arguments
+    /// * `rollover_enabled` - rollover 를 사용할 수 있는 대상(data stream)인지 여부
+    ///
+    /// # Returns
+    /// * Value
+    pub fn to_policy_body(&self, rollover_enabled: bool) -> Value {
+        let mut phases: serde_json::Map<String, Value> = serde_json::Map::new();
+
+        let mut rollover: serde_json::Map<String, Value> = serde_json::Map::new();
+        if let Some(max_size) = &self.rollover_max_primary_shard_size {
+            rollover.insert(String::from("max_primary_shard_size"), json!(max_size));
+        }
+        if let Some(max_age) = &self.rollover_max_age {
+            rollover.insert(String::from("max_age"), json!(max_age));
+        }
+
+        if rollover_enabled && !rollover.is_empty() {
+            phases.insert(
+                String::from("hot"),
+                json!({ "min_age": "0ms", "actions": { "rollover": rollover } }),
+            );
+        } else if !rollover.is_empty() {
+            warn!("[WARN][IlmConfig->to_policy_body] Rollover requires data stream mode. Rollover settings are ignored");
+        }
+
+        let mut warm_actions: serde_json::Map<String, Value> = serde_json::Map::new();
+        if let Some(shards) = self.warm_shrink_shards {
+            warm_actions.insert(String::from("shrink"), json!({ "number_of_shards": shards }));
+        }
+        if let Some(segments) = self.warm_forcemerge_segments {
+            warm_actions.insert(String::from("forcemerge"), json!({ "max_num_segments": segments }));
+        }
+
+        if self.warm_min_age.is_some() || !warm_actions.is_empty() {
+            phases.insert(
+                String::from("warm"),
+                json!({
+                    "min_age": self.warm_min_age.clone().unwrap_or(String::from("0ms")),
+                    "actions": warm_actions
+                }),
+            );
+        }
+
+        if let Some(delete_min_age) = &self.delete_min_age {
+            phases.insert(
+                String::from("delete"),
+                json!({ "min_age": delete_min_age, "actions": { "delete": {} } }),
+            );
+        }
+
+        let mut body: Value = json!({ "policy": { "phases": phases } });
+
+        let checksum: String = format!("{:08x}", crc32fast::hash(body.to_string().as_bytes()));
+        body["policy"]["_meta"] = json!({
+            "managed_by": "os_metric_beats",
+            "checksum": checksum
+        });
+
+        body
+    }
+
+    #[doc = "OpenSearch _plugins/_ism/policies API 로 보낼 본문을 만들어주는 함수"]
+    /// ILM 의 phase 를 같은 이름의 ISM state 로 옮기고, 다음 phase 의 min_age 를 전이 조건으로 사용한다.
+    /// ISM policy 에는 _meta 가 없으므로 변경 여부 비교용 checksum 은 description 에 남긴다.
+    ///
+    /// # Arguments
+    /// * `rollover_enabled` - rollover 를 사용할 수 있는 대상(data stream)인지 여부
+    /// * `index_patterns` - policy 를 자동으로 붙일 인덱스 패턴 (ism_template)
+    /// * `priority` - ism_template 우선순위
+    ///
+    /// # Returns
+    /// * Value
+    pub fn to_ism_policy_body(&self, rollover_enabled: bool, index_patterns: &[String], priority: u32) -> Value {
+        let mut hot_actions: Vec<Value> = Vec::new();
+
+        let mut rollover: serde_json::Map<String, Value> = serde_json::Map::new();
+        if let Some(max_size) = &self.rollover_max_primary_shard_size {
+            rollover.insert(String::from("min_primary_shard_size"), json!(max_size));
+        }
+        if let Some(max_age) = &self.rollover_max_age {
+            rollover.insert(String::from("min_index_age"), json!(max_age));
+        }
+
+        if rollover_enabled && !rollover.is_empty() {
+            hot_actions.push(json!({ "rollover": rollover }));
+        } else if !rollover.is_empty() {
+            warn!("[WARN][IlmConfig->to_ism_policy_body] Rollover requires data stream mode. Rollover settings are ignored");
+        }
+
+        let mut warm_actions: Vec<Value> = Vec::new();
+        if let Some(shards) = self.warm_shrink_shards {
+            warm_actions.push(json!({ "shrink": { "num_new_shards": shards } }));
+        }
+        if let Some(segments) = self.warm_forcemerge_segments {
+            warm_actions.push(json!({ "force_merge": { "max_num_segments": segments } }));
+        }
+
+        /* (state 이름, 진입 조건 min_index_age, actions) - hot 은 항상 시작 state */
+        let mut states: Vec<(&str, Option<String>, Vec<Value>)> = vec![("hot", None, hot_actions)];
+
+        if self.warm_min_age.is_some() || !warm_actions.is_empty() {
+            states.push((
+                "warm",
+                Some(self.warm_min_age.clone().unwrap_or(String::from("0ms"))),
+                warm_actions,
+            ));
+        }
+
+        if let Some(delete_min_age) = &self.delete_min_age {
+            states.push(("delete", Some(delete_min_age.clone()), vec![json!({ "delete": {} })]));
+        }
+
+        let ism_states: Vec<Value> = states
+            .iter()
+            .enumerate()
+            .map(|(idx, (name, _, actions))| {
+                let transitions: Vec<Value> = states
+                    .get(idx + 1)
+                    .map(|(next_name, min_age, _)| {
+                        vec![json!({
+                            "state_name": next_name,
+                            "conditions": { "min_index_age": min_age }
+                        })]
+                    })
+                    .unwrap_or_default();
+
+                json!({ "name": name, "actions": actions, "transitions": transitions })
+            })
+            .collect();
+
+        let mut body: Value = json!({
+            "policy": {
+                "default_state": "hot",
+                "states": ism_states,
+                "ism_template": [{ "index_patterns": index_patterns, "priority": priority }]
+            }
+        });
+
+        let checksum: String = format!("{:08x}", crc32fast::hash(body.to_string().as_bytes()));
+        body["policy"]["description"] = json!(ism_description(&checksum));
+
+        body
+    }
+}
+
+#[doc = "ISM policy description - 에이전트가 관리하는 policy 임을 표시하고 checksum 을 남긴다."]
+pub fn ism_description(checksum: &str) -> String {
+    format!("Managed by os_metric_beats (checksum: {})", checksum)
+}
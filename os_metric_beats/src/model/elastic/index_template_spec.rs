@@ -0,0 +1,55 @@
+use crate::common::*;
+
+use crate::model::elastic::template_config::*;
+
+#[doc = "에이전트가 설치할 composable index template 한 개의 정의"]
+#[derive(Debug, Clone, Getters, new)]
+#[getset(get = "pub")]
+pub struct IndexTemplateSpec {
+    pub name: String,
+    pub index_patterns: Vec<String>,
+    pub mappings: Value,
+    pub priority: u32,
+    pub data_stream: bool,
+}
+
+impl IndexTemplateSpec {
+    #[doc = "_index_template API 로 보낼 본문을 만들어주는 함수 - 재기동 시 변경 여부 비교를 위해 _meta 에 checksum 을 남긴다."]
+    /// # Arguments
+    /// * `template_config` - shard/replica 설정
+    /// * `ilm_policy` - 템플릿으로 생성되는 인덱스에 붙일 lifecycle policy 이름
+    ///
+    /// # Returns
+    /// * Value
+    pub fn to_template_body(&self, template_config: &TemplateConfig, ilm_policy: Option<&str>) -> Value {
+        let mut body: Value = json!({
+            "index_patterns": self.index_patterns,
+            "priority": self.priority,
+            "template": {
+                "settings": {
+                    "index": {
+                        "number_of_shards": template_config.number_of_shards(),
+                        "number_of_replicas": template_config.number_of_replicas()
+                    }
+                },
+                "mappings": self.mappings
+            }
+        });
+
+        if let Some(ilm_policy) = ilm_policy {
+            body["template"]["settings"]["index"]["lifecycle"] = json!({ "name": ilm_policy });
+        }
+
+        if self.data_stream {
+            body["data_stream"] = json!({});
+        }
+
+        let checksum: String = format!("{:08x}", crc32fast::hash(body.to_string().as_bytes()));
+        body["_meta"] = json!({
+            "managed_by": "os_metric_beats",
+            "checksum": checksum
+        });
+
+        body
+    }
+}
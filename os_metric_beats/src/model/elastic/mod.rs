@@ -0,0 +1,26 @@
+pub mod auth_config;
+pub mod bulk_config;
+pub mod bulk_item_result;
+pub mod bulk_queue;
+pub mod data_stream_config;
+pub mod dead_letter_config;
+pub mod dead_letter_record;
+pub mod document_route_config;
+pub mod es_document;
+pub mod es_node_identity;
+pub mod es_node_stats;
+pub mod es_response_error;
+pub mod es_transport_stats;
+pub mod ilm_config;
+pub mod index_template_spec;
+pub mod node_pool_config;
+pub mod retention_audit_record;
+pub mod retention_config;
+pub mod retention_lock;
+pub mod retry_config;
+pub mod routing_rule;
+pub mod secret_value;
+pub mod spool_config;
+pub mod template_config;
+pub mod tls_config;
+pub mod transport_config;
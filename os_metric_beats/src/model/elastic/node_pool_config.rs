@@ -0,0 +1,22 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct NodePoolConfig {
+    pub sniff_enabled: bool,
+    pub sniff_interval_secs: u64,
+    pub dead_backoff_base_secs: u64,
+    pub dead_backoff_max_secs: u64,
+}
+
+impl Default for NodePoolConfig {
+    fn default() -> Self {
+        NodePoolConfig {
+            sniff_enabled: false,
+            sniff_interval_secs: 300,
+            dead_backoff_base_secs: 2,
+            dead_backoff_max_secs: 300,
+        }
+    }
+}
@@ -0,0 +1,12 @@
+use crate::common::*;
+
+#[doc = "retention 정리 작업에서 인덱스 별로 남기는 감사 기록 (NDJSON 한 줄)"]
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct RetentionAuditRecord {
+    pub timestamp: String,
+    pub owner: String,
+    pub index: String,
+    pub index_date: String,
+    pub action: String,
+    pub error: Option<String>,
+}
@@ -0,0 +1,33 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct RetentionConfig {
+    pub retention_days: u32,
+    #[serde(default)]
+    pub dry_run: bool,
+    #[serde(default = "default_interval_secs")]
+    pub interval_secs: u64,
+    #[serde(default = "default_lock_index")]
+    pub lock_index: String,
+    #[serde(default = "default_lock_ttl_secs")]
+    pub lock_ttl_secs: u64,
+    #[serde(default = "default_audit_log_path")]
+    pub audit_log_path: String,
+}
+
+fn default_interval_secs() -> u64 {
+    3600
+}
+
+fn default_lock_index() -> String {
+    String::from(".os_metric_beats-lock")
+}
+
+fn default_lock_ttl_secs() -> u64 {
+    600
+}
+
+fn default_audit_log_path() -> String {
+    String::from("retention_audit.log")
+}
@@ -0,0 +1,15 @@
+use crate::common::*;
+
+#[doc = "여러 에이전트 중 하나만 인덱스를 삭제하도록 Elasticsearch 에 저장하는 lock 문서"]
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct RetentionLock {
+    pub owner: String,
+    pub acquired_at: String,
+    pub expires_at_epoch: i64,
+}
+
+impl RetentionLock {
+    pub fn is_expired(&self, now_epoch: i64) -> bool {
+        self.expires_at_epoch <= now_epoch
+    }
+}
@@ -0,0 +1,48 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct RetryConfig {
+    pub max_retries: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /* 한 batch 가 재시도 대기에 쓸 수 있는 총 시간 - 넘으면 spool/큐 재시도로 넘긴다. */
+    pub retry_budget_secs: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_retries: 5,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 30_000,
+            retry_budget_secs: 120,
+        }
+    }
+}
+
+impl RetryConfig {
+    #[doc = "n 번째 재시도 전에 기다릴 시간을 구해주는 함수 - 지수 backoff 에 jitter 를 섞고, Retry-After 가 더 길면 그 값을 따른다."]
+    /// # Arguments
+    /// * `attempt` - 1 부터 시작하는 재시도 횟수
+    /// * `retry_after` - 서버가 Retry-After 헤더로 알려준 대기시간
+    ///
+    /// # Returns
+    /// * Duration
+    pub fn backoff_delay(&self, attempt: u32, retry_after: Option<Duration>) -> Duration {
+        let exp_backoff_ms: u64 = self
+            .initial_backoff_ms
+            .saturating_mul(1u64 << attempt.saturating_sub(1).min(16))
+            .min(self.max_backoff_ms);
+
+        /* 여러 에이전트가 동시에 재시도하지 않도록 [backoff/2, backoff] 구간에서 무작위로 고른다. */
+        let jittered_ms: u64 = rand::thread_rng().gen_range(exp_backoff_ms / 2..=exp_backoff_ms);
+        let delay: Duration = Duration::from_millis(jittered_ms);
+
+        match retry_after {
+            Some(retry_after) if retry_after > delay => retry_after,
+            _ => delay,
+        }
+    }
+}
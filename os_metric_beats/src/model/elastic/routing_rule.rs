@@ -0,0 +1,36 @@
+use crate::common::*;
+
+use crate::common_enums::doc_type::*;
+use crate::model::elastic::document_route_config::*;
+
+#[doc = "호스트 태그 조건에 따라 색인 대상/pipeline 과 보낼 출력(클러스터)을 바꾸는 규칙"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct RoutingRule {
+    /* 모든 태그가 일치해야 적용 (ex. { env = "prod" }) */
+    #[serde(default)]
+    pub when: HashMap<String, String>,
+    /* 비어있으면 모든 문서 종류에 적용 */
+    #[serde(default)]
+    pub doc_types: Vec<DocType>,
+    /* 규칙이 일치한 문서를 받을 출력 이름 목록 - 목록에 없는 출력은 해당 문서 종류를 보내지 않는다. (비어있으면 제한 없음) */
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    #[serde(flatten)]
+    pub route: DocumentRouteConfig,
+}
+
+impl RoutingRule {
+    pub fn matches(&self, doc_type: DocType, host_tags: &HashMap<String, String>) -> bool {
+        (self.doc_types.is_empty() || self.doc_types.contains(&doc_type))
+            && self
+                .when
+                .iter()
+                .all(|(tag, value)| host_tags.get(tag) == Some(value))
+    }
+
+    #[doc = "규칙이 일치한 문서를 해당 출력으로 보내도 되는지 여부"]
+    pub fn selects_output(&self, output_name: &str) -> bool {
+        self.outputs.is_empty() || self.outputs.iter().any(|name| name == output_name)
+    }
+}
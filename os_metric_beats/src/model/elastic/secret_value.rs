@@ -0,0 +1,51 @@
+use crate::common::*;
+
+#[doc = "설정 파일에 평문, 환경변수, 파일 경로 중 하나로 지정할 수 있는 비밀값"]
+/// ```toml
+/// es_pw = "changeme"
+/// es_pw = { env = "ES_PASSWORD" }
+/// es_pw = { file = "/run/secrets/es_password" }
+/// ```
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum SecretValue {
+    Plain(String),
+    Source {
+        env: Option<String>,
+        file: Option<String>,
+    },
+}
+
+/* 설정 객체를 로그로 남기더라도 비밀값은 노출되지 않도록 함 */
+impl std::fmt::Debug for SecretValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SecretValue::Plain(_) => write!(f, "SecretValue(***)"),
+            SecretValue::Source { env, file } => f
+                .debug_struct("SecretValue")
+                .field("env", env)
+                .field("file", file)
+                .finish(),
+        }
+    }
+}
+
+impl SecretValue {
+    #[doc = "실제 비밀값을 읽어주는 함수 - 파일은 끝의 개행문자를 제거한다."]
+    pub fn resolve(&self) -> Result<String, anyhow::Error> {
+        match self {
+            SecretValue::Plain(value) => Ok(value.clone()),
+            SecretValue::Source { env: Some(env_key), file: None } => env::var(env_key).map_err(|e| {
+                anyhow!("[ERROR][SecretValue->resolve] Failed to read env '{}': {}", env_key, e)
+            }),
+            SecretValue::Source { env: None, file: Some(file_path) } => fs::read_to_string(file_path)
+                .map(|value| value.trim_end_matches(['\r', '\n']).to_string())
+                .map_err(|e| {
+                    anyhow!("[ERROR][SecretValue->resolve] Failed to read secret file '{}': {}", file_path, e)
+                }),
+            SecretValue::Source { .. } => Err(anyhow!(
+                "[ERROR][SecretValue->resolve] Exactly one of 'env' or 'file' must be set"
+            )),
+        }
+    }
+}
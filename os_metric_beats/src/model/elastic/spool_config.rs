@@ -0,0 +1,22 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct SpoolConfig {
+    pub path: String,
+    pub max_bytes: u64,
+    pub segment_bytes: u64,
+    pub replay_interval_secs: u64,
+}
+
+impl Default for SpoolConfig {
+    fn default() -> Self {
+        SpoolConfig {
+            path: String::from("spool"),
+            max_bytes: 512 * 1024 * 1024,
+            segment_bytes: 16 * 1024 * 1024,
+            replay_interval_secs: 10,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use crate::common::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct TemplateConfig {
+    pub enabled: bool,
+    pub name_prefix: String,
+    pub number_of_shards: u32,
+    pub number_of_replicas: u32,
+    pub priority: u32,
+}
+
+impl Default for TemplateConfig {
+    fn default() -> Self {
+        TemplateConfig {
+            enabled: true,
+            name_prefix: String::from("os_metric_beats"),
+            number_of_shards: 1,
+            number_of_replicas: 1,
+            priority: 200,
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use crate::common::*;
+
+use crate::common_enums::tls_verification_mode::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct TlsConfig {
+    /* 서버 인증서를 서명한 CA 번들(PEM) 경로 */
+    pub ca_path: Option<String>,
+    /* mTLS 용 클라이언트 인증서/개인키(PEM) 경로 */
+    pub client_cert_path: Option<String>,
+    pub client_key_path: Option<String>,
+    /* ES enrollment 와 같은 방식의 CA 인증서 SHA-256 지문 */
+    pub ca_fingerprint: Option<String>,
+    #[serde(default)]
+    pub verification_mode: TlsVerificationMode,
+}
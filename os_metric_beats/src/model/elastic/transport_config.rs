@@ -0,0 +1,38 @@
+use crate::common::*;
+
+use crate::model::elastic::secret_value::*;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct TransportConfig {
+    pub request_timeout_secs: u64,
+    /* 아래 연결 설정은 _bulk 전용 HTTP client 에 적용된다. */
+    pub connect_timeout_secs: u64,
+    pub pool_idle_timeout_secs: u64,
+    pub pool_max_idle_per_host: usize,
+    /* 0 이면 TCP keep-alive 를 사용하지 않는다. */
+    pub tcp_keepalive_secs: u64,
+    pub compression: bool,
+    pub compression_level: u32,
+    pub proxy_url: Option<String>,
+    pub proxy_username: Option<String>,
+    pub proxy_password: Option<SecretValue>,
+}
+
+impl Default for TransportConfig {
+    fn default() -> Self {
+        TransportConfig {
+            request_timeout_secs: 5,
+            connect_timeout_secs: 3,
+            pool_idle_timeout_secs: 90,
+            pool_max_idle_per_host: 4,
+            tcp_keepalive_secs: 60,
+            compression: false,
+            compression_level: 1,
+            proxy_url: None,
+            proxy_username: None,
+            proxy_password: None,
+        }
+    }
+}
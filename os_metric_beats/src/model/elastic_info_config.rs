@@ -1,10 +1,63 @@
 use crate::common::*;
 
+use crate::common_enums::{doc_type::*, es_flavour::*};
+
+use crate::model::elastic::{
+    auth_config::*, bulk_config::*, data_stream_config::*, dead_letter_config::*,
+    document_route_config::*, ilm_config::*, node_pool_config::*, retention_config::*, retry_config::*,
+    routing_rule::*, secret_value::*, spool_config::*, template_config::*, tls_config::*,
+    transport_config::*,
+};
+
 #[derive(Serialize, Deserialize, Debug, Getters)]
 #[getset(get = "pub")]
 pub struct ElasticInfoConfig {
     pub hosts: Vec<String>,
+    #[serde(default)]
+    pub flavour: EsFlavour,
     pub es_id: Option<String>,
-    pub es_pw: Option<String>,
+    pub es_pw: Option<SecretValue>,
+    pub auth: Option<AuthConfig>,
     pub index_pattern: String,
+    pub slowlog_index_pattern: Option<String>,
+    #[serde(default)]
+    pub documents: HashMap<DocType, DocumentRouteConfig>,
+    #[serde(default)]
+    pub routing_rules: Vec<RoutingRule>,
+    #[serde(default)]
+    pub bulk: BulkConfig,
+    #[serde(default)]
+    pub retry: RetryConfig,
+    pub spool: Option<SpoolConfig>,
+    pub dead_letter: Option<DeadLetterConfig>,
+    pub data_stream: Option<DataStreamConfig>,
+    pub ilm: Option<IlmConfig>,
+    pub retention: Option<RetentionConfig>,
+    pub tls: Option<TlsConfig>,
+    #[serde(default)]
+    pub transport: TransportConfig,
+    #[serde(default)]
+    pub node_pool: NodePoolConfig,
+    #[serde(default)]
+    pub template: TemplateConfig,
+}
+
+impl ElasticInfoConfig {
+    #[doc = "[auth] 설정을 우선하고, 없으면 기존 es_id/es_pw 를 basic 인증으로 사용한다."]
+    pub fn resolve_auth(&self) -> Option<AuthConfig> {
+        if let Some(auth) = &self.auth {
+            if self.es_id.is_some() || self.es_pw.is_some() {
+                warn!("[WARN][ElasticInfoConfig->resolve_auth] Both [auth] and es_id/es_pw are set. es_id/es_pw are ignored");
+            }
+            return Some(auth.clone());
+        }
+
+        match (&self.es_id, &self.es_pw) {
+            (Some(es_id), Some(es_pw)) if !es_id.is_empty() => Some(AuthConfig::Basic {
+                username: es_id.clone(),
+                password: es_pw.clone(),
+            }),
+            _ => None,
+        }
+    }
 }
@@ -0,0 +1,73 @@
+use crate::common::*;
+
+use crate::model::elastic_info_config::*;
+
+use crate::utils_module::io_utils::*;
+
+#[doc = "단일 클러스터 설정 파일을 여러 출력 형식으로 읽었을 때 사용할 출력 이름"]
+pub const DEFAULT_OUTPUT_NAME: &str = "default";
+
+#[doc = "이름 붙은 Elasticsearch 출력 목록 - [outputs.<name>] 마다 하나의 클러스터 설정"]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ElasticOutputsConfig {
+    pub outputs: BTreeMap<String, ElasticInfoConfig>,
+}
+
+impl ElasticOutputsConfig {
+    #[doc = "ELASTIC_SERVER_INFO 파일을 읽어 출력 목록을 만들어주는 함수"]
+    /// [outputs.<name>] 테이블이 없다면 기존 단일 클러스터 형식으로 보고 'default' 출력 하나로 읽는다.
+    ///
+    /// # Arguments
+    /// * `file_path` - toml 설정 파일 경로
+    ///
+    /// # Returns
+    /// * Result<BTreeMap<String, ElasticInfoConfig>, anyhow::Error>
+    pub fn load(file_path: &str) -> Result<BTreeMap<String, ElasticInfoConfig>, anyhow::Error> {
+        let config: toml::Value = read_toml_from_file::<toml::Value>(file_path)?;
+
+        let outputs: BTreeMap<String, ElasticInfoConfig> = if config.get("outputs").is_some() {
+            config.try_into::<ElasticOutputsConfig>()?.outputs
+        } else {
+            BTreeMap::from([(String::from(DEFAULT_OUTPUT_NAME), config.try_into::<ElasticInfoConfig>()?)])
+        };
+
+        if outputs.is_empty() {
+            return Err(anyhow!("[ERROR][ElasticOutputsConfig->load] No Elasticsearch output is configured"));
+        }
+
+        Self::check_unique_paths(&outputs)?;
+
+        Ok(outputs)
+    }
+
+    #[doc = "출력끼리 같은 로컬 파일(spool, dead-letter)을 공유하지 않는지 확인해주는 함수"]
+    fn check_unique_paths(outputs: &BTreeMap<String, ElasticInfoConfig>) -> Result<(), anyhow::Error> {
+        let mut used_paths: HashMap<String, &String> = HashMap::new();
+
+        for (output_name, cluster_config) in outputs {
+            let local_paths = cluster_config
+                .spool()
+                .iter()
+                .map(|spool_config| spool_config.path().clone())
+                .chain(
+                    cluster_config
+                        .dead_letter()
+                        .iter()
+                        .map(|dead_letter_config| dead_letter_config.fallback_path().clone()),
+                );
+
+            for path in local_paths {
+                if let Some(other_output) = used_paths.insert(path.clone(), output_name) {
+                    return Err(anyhow!(
+                        "[ERROR][ElasticOutputsConfig->check_unique_paths] Outputs '{}' and '{}' share the local path '{}'",
+                        other_output,
+                        output_name,
+                        path
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use crate::common::*;
+
+use crate::model::elastic::secret_value::*;
+
+#[doc = "exporter 의 basic 인증 설정"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct BasicAuthConfig {
+    username: String,
+    password: SecretValue,
+}
+
+impl BasicAuthConfig {
+    #[doc = "Authorization 헤더 값(Basic base64(username:password))을 만들어주는 함수"]
+    pub fn to_header_value(&self) -> Result<HeaderValue, anyhow::Error> {
+        let credentials: String = format!("{}:{}", self.username, self.password.resolve()?);
+        Ok(HeaderValue::from_str(&format!("Basic {}", BASE64_STANDARD.encode(credentials)))?)
+    }
+}
@@ -0,0 +1,93 @@
+use crate::common::*;
+
+use crate::common_enums::metric_kind::*;
+use crate::model::exporter::metric_sample::*;
+
+#[doc = "이 횟수만큼 연속으로 수집되지 않은 시계열은 누적값을 버린다 (ex. 날짜가 바뀐 slow log 인덱스)."]
+const STALE_SERIES_CYCLES: u64 = 10;
+
+#[derive(Clone, Debug, Default)]
+struct CounterSeries {
+    /* 증가분 counter 의 누적값 */
+    total: f64,
+    /* 누적값 counter 의 직전 값 - delta temporality 변환에 사용 */
+    last_raw: Option<f64>,
+    last_seen_cycle: u64,
+}
+
+#[doc = "수집기가 넘겨주는 counter 증가분을 시계열 별로 더해서 누적값으로 바꿔주는 저장소"]
+/// 증가분 counter 는 에이전트 시작 시점부터 누적되며, 재시작하면 0 부터 다시 시작한다 (Prometheus 의 counter reset 과 같음).
+/// 커널 누적값 counter (MetricSample::cumulative) 는 더하지 않고 그대로 내보낸다.
+#[derive(Clone, Debug, Default)]
+pub struct CounterTotals {
+    series: HashMap<String, CounterSeries>,
+    cycle: u64,
+}
+
+impl CounterTotals {
+    #[doc = "counter 는 누적값으로, gauge 는 그대로 돌려주는 함수"]
+    pub fn accumulate(&mut self, samples: &[MetricSample]) -> Vec<MetricSample> {
+        self.cycle += 1;
+
+        let samples: Vec<MetricSample> = samples
+            .iter()
+            .map(|sample| {
+                let mut sample: MetricSample = sample.clone();
+                if sample.kind == MetricKind::Counter {
+                    let series: &mut CounterSeries = self.touch(&sample);
+                    if sample.cumulative {
+                        series.last_raw = Some(sample.value);
+                    } else {
+                        series.total += sample.value.max(0.0);
+                        sample.value = series.total;
+                    }
+                }
+                sample
+            })
+            .collect();
+
+        self.evict_stale();
+        samples
+    }
+
+    #[doc = "counter 를 직전 수집 이후의 증가분으로 바꿔주는 함수 - delta temporality 용"]
+    /// 누적값 counter 는 직전 값과의 차이를 내보내고 (값이 줄었다면 counter 가 초기화된 것으로 보고 현재 값),
+    /// 처음 본 시계열은 증가분을 알 수 없으므로 내보내지 않는다.
+    pub fn deltas(&mut self, samples: &[MetricSample]) -> Vec<MetricSample> {
+        self.cycle += 1;
+
+        let samples: Vec<MetricSample> = samples
+            .iter()
+            .filter_map(|sample| {
+                let mut sample: MetricSample = sample.clone();
+                if sample.kind == MetricKind::Counter && sample.cumulative {
+                    let series: &mut CounterSeries = self.touch(&sample);
+                    let last_raw: Option<f64> = series.last_raw.replace(sample.value);
+                    sample.value = match last_raw {
+                        Some(last_raw) if sample.value >= last_raw => sample.value - last_raw,
+                        Some(_) => sample.value,
+                        None => return None,
+                    };
+                }
+                Some(sample)
+            })
+            .collect();
+
+        self.evict_stale();
+        samples
+    }
+
+    #[doc = "시계열의 상태를 가져오고 이번 수집에서 봤다고 표시해주는 함수"]
+    fn touch(&mut self, sample: &MetricSample) -> &mut CounterSeries {
+        let series: &mut CounterSeries = self.series.entry(sample.series_key()).or_default();
+        series.last_seen_cycle = self.cycle;
+        series
+    }
+
+    #[doc = "STALE_SERIES_CYCLES 동안 수집되지 않은 시계열을 버리는 함수 - label 값이 바뀌는 시계열 때문에 메모리가 계속 늘지 않게 한다."]
+    fn evict_stale(&mut self) {
+        let cycle: u64 = self.cycle;
+        self.series
+            .retain(|_, series| cycle - series.last_seen_cycle < STALE_SERIES_CYCLES);
+    }
+}
@@ -0,0 +1,45 @@
+use crate::common::*;
+
+use crate::common_enums::influx_protocol::*;
+use crate::model::elastic::{retry_config::*, secret_value::*};
+
+#[doc = "InfluxDB line protocol 로 수집 결과를 보내는 출력 설정"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct InfluxConfig {
+    protocol: InfluxProtocol,
+    /* http - InfluxDB 주소 (/api/v2/write 는 자동으로 붙는다) */
+    url: String,
+    org: String,
+    bucket: String,
+    token: Option<SecretValue>,
+    compression: bool,
+    timeout_secs: u64,
+    retry: RetryConfig,
+    /* udp - listener 주소와 datagram 하나의 최대 크기 (줄 단위로 나눈다) */
+    udp_addr: String,
+    udp_max_datagram_bytes: usize,
+    /* 모든 줄에 붙일 tag */
+    tags: BTreeMap<String, String>,
+    queue_capacity: usize,
+}
+
+impl Default for InfluxConfig {
+    fn default() -> Self {
+        InfluxConfig {
+            protocol: InfluxProtocol::default(),
+            url: String::from("http://127.0.0.1:8086"),
+            org: String::new(),
+            bucket: String::from("os_metric_beats"),
+            token: None,
+            compression: false,
+            timeout_secs: 10,
+            retry: RetryConfig::default(),
+            udp_addr: String::from("127.0.0.1:8089"),
+            udp_max_datagram_bytes: 1400,
+            tags: BTreeMap::new(),
+            queue_capacity: 100,
+        }
+    }
+}
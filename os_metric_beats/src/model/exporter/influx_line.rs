@@ -0,0 +1,116 @@
+use crate::common::*;
+
+use crate::model::exporter::metric_sample::*;
+
+#[doc = "여러 단어로 된 지표 그룹 - 나머지는 이름의 앞 두 단어(ex. os_cpu, es_gc)를 measurement 로 사용한다."]
+const MULTI_WORD_MEASUREMENTS: [&str; 1] = ["es_thread_pool"];
+
+#[doc = "Telegraf 와 같은 tag 이름을 쓰도록 바꿔주는 시계열 label - 나머지 label 은 이름 그대로 tag 가 된다."]
+const TAG_RENAMES: [(&str, &str); 1] = [("interface", "iface")];
+
+#[doc = "시계열 목록을 InfluxDB line protocol 로 만들어주는 함수"]
+/// 지표 그룹(measurement)과 tag 가 같은 시계열은 한 줄의 field 들로 묶는다.
+/// ex) os_network_received_bytes_total -> measurement os_network, field received_bytes_total
+///
+/// # Arguments
+/// * `samples` - 시계열 목록
+/// * `host` - 모든 줄에 붙일 host tag
+/// * `tags` - 모든 줄에 붙일 tag (시계열 label 이 같은 이름이면 label 이 우선)
+/// * `timestamp_ms` - 수집 시각 (precision=ms)
+///
+/// # Returns
+/// * Vec<String> - 줄 목록
+pub fn render_line_protocol(
+    samples: &[MetricSample],
+    host: &str,
+    tags: &BTreeMap<String, String>,
+    timestamp_ms: i64,
+) -> Vec<String> {
+    let mut lines: Vec<(String, Vec<String>)> = Vec::new();
+
+    for sample in samples {
+        if !sample.value.is_finite() {
+            continue;
+        }
+
+        let (measurement, field) = split_measurement(sample.name);
+
+        let mut line_tags: BTreeMap<String, String> = tags.clone();
+        line_tags.insert(String::from("host"), host.to_string());
+        for (key, value) in &sample.labels {
+            line_tags.insert(tag_key(key).to_string(), value.clone());
+        }
+
+        let mut series: String = escape_key(measurement, true);
+        for (key, value) in line_tags.iter().filter(|(_, value)| !value.is_empty()) {
+            series.push_str(&format!(
+                ",{}={}",
+                escape_key(key, false),
+                escape_key(value, false)
+            ));
+        }
+
+        let field: String = format!("{}={}", escape_key(field, false), sample.value);
+
+        match lines
+            .iter_mut()
+            .find(|(line_series, _)| *line_series == series)
+        {
+            Some((_, fields)) => fields.push(field),
+            None => lines.push((series, vec![field])),
+        }
+    }
+
+    lines
+        .into_iter()
+        .map(|(series, fields)| format!("{} {} {}", series, fields.join(","), timestamp_ms))
+        .collect()
+}
+
+#[doc = "지표 이름을 (measurement, field) 로 나눠주는 함수 - 나눌 부분이 없으면 field 는 value"]
+fn split_measurement(name: &str) -> (&str, &str) {
+    let measurement_len: usize = match MULTI_WORD_MEASUREMENTS
+        .iter()
+        .find(|measurement| name.starts_with(&format!("{}_", measurement)))
+    {
+        Some(measurement) => measurement.len(),
+        None => name
+            .match_indices('_')
+            .nth(1)
+            .map(|(idx, _)| idx)
+            .unwrap_or(name.len()),
+    };
+
+    match name.get(measurement_len + 1..) {
+        Some(field) if !field.is_empty() => (&name[..measurement_len], field),
+        _ => (name, "value"),
+    }
+}
+
+#[doc = "시계열 label 이름을 tag 이름으로 바꿔주는 함수 (ex. interface -> iface)"]
+fn tag_key(label: &str) -> &str {
+    TAG_RENAMES
+        .iter()
+        .find(|(from, _)| *from == label)
+        .map(|(_, to)| *to)
+        .unwrap_or(label)
+}
+
+#[doc = "line protocol 의 measurement/tag/field key 이스케이프 - measurement 는 '=' 를 이스케이프하지 않는다."]
+/// line protocol 은 줄바꿈을 이스케이프할 방법이 없으므로 줄바꿈 문자는 공백으로 바꾼다.
+fn escape_key(value: &str, measurement: bool) -> String {
+    let mut escaped: String = String::with_capacity(value.len());
+    for c in value.chars() {
+        let c: char = match c {
+            '\n' | '\r' => ' ',
+            c => c,
+        };
+        match c {
+            ',' | ' ' | '\\' => escaped.push('\\'),
+            '=' if !measurement => escaped.push('\\'),
+            _ => (),
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,9 @@
+use crate::common::*;
+
+#[doc = "내장 HTTP 서버를 https 로 열기 위한 인증서 설정 (PEM)"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+pub struct ListenTlsConfig {
+    cert_path: String,
+    key_path: String,
+}
@@ -0,0 +1,19 @@
+use crate::common::*;
+
+use crate::model::memory::process_mem_res::*;
+use crate::model::network::iface_counters::*;
+use crate::model::network_packet::iface_counters::IfaceCounters as PacketIfaceCounters;
+
+#[doc = "Elasticsearch 문서에는 넣지 않고 exporter 로만 내보내는 수집 결과"]
+/// Elasticsearch 문서에는 기존처럼 합계/대표 마운트 값만 보낸다.
+#[derive(Clone, Debug, Default)]
+pub struct MetricDetail {
+    /* 인터페이스 별 커널 누적 송/수신 바이트 */
+    pub network_interfaces: BTreeMap<String, IfaceCounters>,
+    /* 인터페이스 별 커널 누적 드롭/에러 패킷 */
+    pub network_packet_interfaces: BTreeMap<String, PacketIfaceCounters>,
+    /* 마운트 지점 별 디스크 사용률 */
+    pub disk_mounts: BTreeMap<String, f64>,
+    /* Elasticsearch 관련 프로세스 별 메모리 사용량 */
+    pub es_process_memory: Vec<ProcessMemRes>,
+}
@@ -0,0 +1,47 @@
+use crate::common::*;
+
+use crate::common_enums::metric_kind::*;
+
+#[doc = "exporter 로 내보내는 시계열 값 하나 - 이름과 label 이 시계열을 구분한다."]
+#[derive(Clone, Debug, PartialEq, new)]
+pub struct MetricSample {
+    pub name: &'static str,
+    pub help: &'static str,
+    pub kind: MetricKind,
+    pub labels: Vec<(&'static str, String)>,
+    pub value: f64,
+    /* counter 의 값이 증가분이 아니라 커널이 관리하는 누적값인지 여부 (ex. 인터페이스 별 송/수신 바이트) */
+    #[new(default)]
+    pub cumulative: bool,
+}
+
+impl MetricSample {
+    pub fn gauge(name: &'static str, help: &'static str, value: f64) -> Self {
+        MetricSample::new(name, help, MetricKind::Gauge, Vec::new(), value)
+    }
+
+    pub fn counter(name: &'static str, help: &'static str, value: f64) -> Self {
+        MetricSample::new(name, help, MetricKind::Counter, Vec::new(), value)
+    }
+
+    #[doc = "수집기가 읽은 누적값을 그대로 내보내는 counter - CounterTotals 가 더하지 않는다."]
+    pub fn cumulative_counter(name: &'static str, help: &'static str, value: f64) -> Self {
+        let mut sample: MetricSample = MetricSample::counter(name, help, value);
+        sample.cumulative = true;
+        sample
+    }
+
+    pub fn with_label(mut self, key: &'static str, value: impl Into<String>) -> Self {
+        self.labels.push((key, value.into()));
+        self
+    }
+
+    #[doc = "이름과 label 로 만든 시계열 key - counter 누적값을 시계열 별로 관리할 때 사용한다."]
+    pub fn series_key(&self) -> String {
+        let mut series_key: String = self.name.to_string();
+        for (key, value) in &self.labels {
+            series_key.push_str(&format!("\u{1f}{}={}", key, value));
+        }
+        series_key
+    }
+}
@@ -0,0 +1,228 @@
+use crate::common::*;
+
+use crate::model::exporter::{metric_detail::*, metric_sample::*};
+use crate::model::metric_info::*;
+use crate::model::network_packet::iface_counters::IfaceCounters as PacketIfaceCounters;
+use crate::model::slowlog::slowlog_metric_info::*;
+
+#[doc = "수집 주기 한 번의 결과를 exporter 가 쓰기 좋은 시계열 목록으로 펼친 것"]
+#[derive(Clone, Debug, new)]
+pub struct MetricSnapshot {
+    pub timestamp_ms: i64,
+    pub host: String,
+    pub samples: Vec<MetricSample>,
+    /* 수집기가 직전에 만든 결과의 수집 시각 - 증가분 counter 의 구간 시작 (첫 수집이면 None) */
+    #[new(default)]
+    pub prev_timestamp_ms: Option<i64>,
+}
+
+const MB: f64 = 1024.0 * 1024.0;
+
+impl MetricSnapshot {
+    #[doc = "Elasticsearch 로 보내는 문서와 같은 수집 결과를 시계열로 변환해주는 함수"]
+    /// 이름은 Prometheus 관례(단위 접미사, counter 는 _total)를 따르고, 단위는 초/바이트로 맞춘다.
+    /// GC/slow log 는 직전 수집 이후의 증가분을 counter 로 표시하고, 인터페이스 별 네트워크/패킷은 커널 누적값을 그대로 counter 로 내보낸다.
+    /// 네트워크는 interface, 디스크는 mount, Elasticsearch 프로세스 지표는 pid/process label 로 나누며,
+    /// 수집기가 나눠진 값을 주지 못하면 합계(증가분) 하나로 내보낸다.
+    ///
+    /// # Arguments
+    /// * `timestamp_ms` - 수집 시각 (epoch millis)
+    /// * `prev_timestamp_ms` - 직전 수집 시각 (epoch millis, 첫 수집이면 None)
+    /// * `metric_info` - 시스템 지표 문서
+    /// * `metric_detail` - exporter 전용 수집 결과 (인터페이스/마운트/프로세스 별 값)
+    /// * `slowlog_infos` - slow log 집계 문서
+    ///
+    /// # Returns
+    /// * MetricSnapshot
+    pub fn from_collected(
+        timestamp_ms: i64,
+        prev_timestamp_ms: Option<i64>,
+        metric_info: &MetricInfo,
+        metric_detail: &MetricDetail,
+        slowlog_infos: &[SlowLogMetricInfo],
+    ) -> Self {
+        let mut samples: Vec<MetricSample> = vec![
+            MetricSample::gauge("os_cpu_usage_percent", "System CPU usage", metric_info.system_cpu_usage as f64),
+            MetricSample::gauge("os_memory_usage_percent", "System memory usage", metric_info.system_memory_usage),
+            MetricSample::gauge("os_processes", "Number of running processes", metric_info.process_count as f64),
+        ];
+
+        if metric_detail.disk_mounts.is_empty() {
+            samples.push(MetricSample::gauge("os_disk_usage_percent", "Disk usage of the mount point", metric_info.system_disk_usage));
+        }
+        for (mount, usage) in &metric_detail.disk_mounts {
+            samples.push(
+                MetricSample::gauge("os_disk_usage_percent", "Disk usage of the mount point", *usage).with_label("mount", mount.clone()),
+            );
+        }
+
+        if metric_detail.network_interfaces.is_empty() {
+            samples.extend(network_byte_samples(metric_info.network_received, metric_info.network_transmitted, MetricSample::counter));
+        }
+        for (iface, counters) in &metric_detail.network_interfaces {
+            samples.extend(
+                network_byte_samples(counters.rx, counters.tx, MetricSample::cumulative_counter)
+                    .map(|sample| sample.with_label("interface", iface.clone())),
+            );
+        }
+
+        if metric_detail.network_packet_interfaces.is_empty() {
+            let totals: PacketIfaceCounters = PacketIfaceCounters::new(
+                metric_info.recv_dropped_packets,
+                metric_info.send_dropped_packets,
+                metric_info.recv_errors_packet,
+                metric_info.send_errors_packet,
+            );
+            samples.extend(network_packet_samples(&totals, MetricSample::counter));
+        }
+        for (iface, counters) in &metric_detail.network_packet_interfaces {
+            samples.extend(
+                network_packet_samples(counters, MetricSample::cumulative_counter)
+                    .map(|sample| sample.with_label("interface", iface.clone())),
+            );
+        }
+
+        samples.extend([
+            MetricSample::gauge("os_tcp_connections", "Number of TCP connections", metric_info.tcp_connections as f64),
+            MetricSample::gauge("os_udp_sockets", "Number of UDP sockets", metric_info.udp_sockets as f64),
+        ]);
+
+        for (state, count) in [
+            ("established", metric_info.tcp_established),
+            ("time_wait", metric_info.tcp_timewait),
+            ("listen", metric_info.tcp_listen),
+            ("close_wait", metric_info.tcp_close_wait),
+        ] {
+            samples.push(
+                MetricSample::gauge("os_tcp_connections_by_state", "Number of TCP connections by state", count as f64)
+                    .with_label("state", state),
+            );
+        }
+
+        if metric_detail.es_process_memory.is_empty() {
+            samples.extend(process_memory_samples(metric_info.process_use_mem, metric_info.process_virtual_mem));
+        }
+        for process_mem in &metric_detail.es_process_memory {
+            samples.extend(
+                process_memory_samples(process_mem.working_set_size, process_mem.virtual_size).map(|sample| {
+                    sample
+                        .with_label("pid", process_mem.pid.to_string())
+                        .with_label("process", process_mem.name.clone())
+                }),
+            );
+        }
+
+        samples.extend([
+            MetricSample::gauge("os_vm_max_map_count", "vm.max_map_count kernel setting", metric_info.vm_max_map_count as f64),
+        ]);
+
+        /* vm.max_map_count 는 프로세스 단위 한도이므로 프로세스 별로 내보낸다. */
+        for process_mmap in &metric_info.es_process_mmaps {
+            samples.extend(
+                [
+                    MetricSample::gauge("es_process_map_count", "Memory mappings of the Elasticsearch process", process_mmap.map_count as f64),
+                    MetricSample::gauge("es_process_map_count_usage_percent", "Memory mappings relative to vm.max_map_count", process_mmap.map_count_usage as f64),
+                ]
+                .map(|sample| {
+                    sample
+                        .with_label("pid", process_mmap.pid.to_string())
+                        .with_label("process", process_mmap.name.clone())
+                }),
+            );
+        }
+
+        /* thread pool 은 Elasticsearch 프로세스의 스레드를 이름으로 묶은 그룹 */
+        let mut thread_pools: Vec<_> = metric_info.es_thread_pools.iter().collect();
+        thread_pools.sort_by(|a, b| a.0.cmp(b.0));
+        for (pool, usage) in thread_pools {
+            samples.extend([
+                MetricSample::gauge("es_thread_pool_threads", "Threads in the Elasticsearch thread pool", usage.thread_count as f64)
+                    .with_label("pool", pool.clone()),
+                MetricSample::counter("es_thread_pool_cpu_seconds_total", "CPU time used by the Elasticsearch thread pool", usage.cpu_time_ms as f64 / 1000.0)
+                    .with_label("pool", pool.clone()),
+                MetricSample::gauge("es_thread_pool_cpu_usage_percent", "CPU usage of the Elasticsearch thread pool", usage.cpu_usage as f64)
+                    .with_label("pool", pool.clone()),
+            ]);
+        }
+
+        let thread_states = &metric_info.es_thread_states;
+        for (state, count) in [
+            ("running", thread_states.running),
+            ("sleeping", thread_states.sleeping),
+            ("disk_sleep", thread_states.disk_sleep),
+            ("stopped", thread_states.stopped),
+            ("zombie", thread_states.zombie),
+            ("other", thread_states.other),
+        ] {
+            samples.push(
+                MetricSample::gauge("es_threads", "Elasticsearch threads by state", count as f64).with_label("state", state),
+            );
+        }
+
+        samples.extend([
+            MetricSample::counter("es_gc_pauses_total", "GC pauses in the Elasticsearch GC log", metric_info.gc_pause_count as f64),
+            MetricSample::counter("es_gc_pause_seconds_total", "Total GC pause time", metric_info.gc_pause_total_ms / 1000.0),
+            MetricSample::gauge("es_gc_pause_max_seconds", "Longest GC pause in the last interval", metric_info.gc_pause_max_ms / 1000.0),
+            MetricSample::gauge("es_gc_pause_p99_seconds", "99th percentile GC pause in the last interval", metric_info.gc_pause_p99_ms / 1000.0),
+            MetricSample::gauge("es_gc_heap_before_bytes", "Heap used before the last GC", metric_info.gc_heap_before_mb * MB),
+            MetricSample::gauge("es_gc_heap_after_bytes", "Heap used after the last GC", metric_info.gc_heap_after_mb * MB),
+            MetricSample::gauge("es_gc_to_space_exhausted", "Whether to-space exhaustion occurred in the last interval", bool_value(metric_info.gc_to_space_exhausted)),
+            MetricSample::gauge("es_gc_full_gc", "Whether a full GC occurred in the last interval", bool_value(metric_info.gc_full_gc)),
+        ]);
+
+        for slowlog_info in slowlog_infos {
+            samples.extend([
+                MetricSample::counter("es_slowlog_entries_total", "Slow log entries", slowlog_info.slow_count as f64),
+                MetricSample::gauge("es_slowlog_took_max_seconds", "Slowest slow log entry in the last interval", slowlog_info.took_max_ms as f64 / 1000.0),
+                MetricSample::gauge("es_slowlog_took_avg_seconds", "Average slow log entry in the last interval", slowlog_info.took_avg_ms / 1000.0),
+            ]
+            .map(|sample| {
+                sample
+                    .with_label("type", slowlog_info.slowlog_type.clone())
+                    .with_label("index", slowlog_info.slowlog_index.clone())
+            }));
+        }
+
+        let mut snapshot: MetricSnapshot = MetricSnapshot::new(timestamp_ms, metric_info.host.clone(), samples);
+        snapshot.prev_timestamp_ms = prev_timestamp_ms;
+        snapshot
+    }
+}
+
+#[doc = "counter 생성 함수 - 증가분이면 MetricSample::counter, 커널 누적값이면 MetricSample::cumulative_counter"]
+type CounterFn = fn(&'static str, &'static str, f64) -> MetricSample;
+
+fn network_byte_samples(received: u64, transmitted: u64, counter: CounterFn) -> [MetricSample; 2] {
+    [
+        counter("os_network_received_bytes_total", "Bytes received on the network interface", received as f64),
+        counter("os_network_transmitted_bytes_total", "Bytes transmitted on the network interface", transmitted as f64),
+    ]
+}
+
+fn process_memory_samples(resident: u64, virtual_size: u64) -> [MetricSample; 2] {
+    [
+        MetricSample::gauge("es_process_resident_memory_bytes", "Elasticsearch process resident memory", resident as f64),
+        MetricSample::gauge("es_process_virtual_memory_bytes", "Elasticsearch process virtual memory", virtual_size as f64),
+    ]
+}
+
+fn network_packet_samples(counters: &PacketIfaceCounters, counter: CounterFn) -> [MetricSample; 4] {
+    [
+        counter("os_network_dropped_packets_total", "Dropped packets on the network interface", counters.rx_dropped as f64)
+            .with_label("direction", "receive"),
+        counter("os_network_dropped_packets_total", "Dropped packets on the network interface", counters.tx_dropped as f64)
+            .with_label("direction", "transmit"),
+        counter("os_network_error_packets_total", "Error packets on the network interface", counters.rx_errors as f64)
+            .with_label("direction", "receive"),
+        counter("os_network_error_packets_total", "Error packets on the network interface", counters.tx_errors as f64)
+            .with_label("direction", "transmit"),
+    ]
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value {
+        1.0
+    } else {
+        0.0
+    }
+}
@@ -0,0 +1,13 @@
+pub mod basic_auth_config;
+pub mod counter_totals;
+pub mod influx_config;
+pub mod influx_line;
+pub mod listen_tls_config;
+pub mod metric_detail;
+pub mod metric_sample;
+pub mod metric_snapshot;
+pub mod otlp_config;
+pub mod otlp_metrics_request;
+pub mod prometheus_config;
+pub mod remote_write_config;
+pub mod remote_write_request;
@@ -0,0 +1,40 @@
+use crate::common::*;
+
+use crate::common_enums::{otlp_protocol::*, otlp_temporality::*};
+use crate::model::elastic::retry_config::*;
+
+#[doc = "OpenTelemetry OTLP/HTTP 로 수집 결과를 보내는 출력 설정"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct OtlpConfig {
+    endpoint: String,
+    protocol: OtlpProtocol,
+    temporality: OtlpTemporality,
+    service_name: String,
+    /* host.name, host.ip, os.type, service.name 외에 resource 에 추가로 붙일 속성 */
+    resource_attributes: BTreeMap<String, String>,
+    /* 인증 토큰 등 요청마다 붙일 헤더 */
+    headers: BTreeMap<String, String>,
+    compression: bool,
+    timeout_secs: u64,
+    queue_capacity: usize,
+    retry: RetryConfig,
+}
+
+impl Default for OtlpConfig {
+    fn default() -> Self {
+        OtlpConfig {
+            endpoint: String::from("http://127.0.0.1:4318/v1/metrics"),
+            protocol: OtlpProtocol::default(),
+            temporality: OtlpTemporality::default(),
+            service_name: String::from("os_metric_beats"),
+            resource_attributes: BTreeMap::new(),
+            headers: BTreeMap::new(),
+            compression: false,
+            timeout_secs: 10,
+            queue_capacity: 100,
+            retry: RetryConfig::default(),
+        }
+    }
+}
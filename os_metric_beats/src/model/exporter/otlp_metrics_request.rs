@@ -0,0 +1,319 @@
+use crate::common::*;
+
+use crate::common_enums::metric_kind::*;
+use crate::model::exporter::metric_sample::*;
+
+/* opentelemetry/proto/collector/metrics/v1, metrics/v1, resource/v1, common/v1 중 Gauge/Sum 전송에 필요한 메시지만 옮긴 것 */
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ExportMetricsServiceRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub resource_metrics: Vec<ResourceMetrics>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ResourceMetrics {
+    #[prost(message, optional, tag = "1")]
+    pub resource: Option<Resource>,
+    #[prost(message, repeated, tag = "2")]
+    pub scope_metrics: Vec<ScopeMetrics>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Resource {
+    #[prost(message, repeated, tag = "1")]
+    pub attributes: Vec<KeyValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ScopeMetrics {
+    #[prost(message, optional, tag = "1")]
+    pub scope: Option<InstrumentationScope>,
+    #[prost(message, repeated, tag = "2")]
+    pub metrics: Vec<Metric>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct InstrumentationScope {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub version: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct KeyValue {
+    #[prost(string, tag = "1")]
+    pub key: String,
+    #[prost(message, optional, tag = "2")]
+    pub value: Option<AnyValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct AnyValue {
+    #[prost(oneof = "AnyValueKind", tags = "1")]
+    pub value: Option<AnyValueKind>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum AnyValueKind {
+    #[prost(string, tag = "1")]
+    StringValue(String),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Metric {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub description: String,
+    #[prost(string, tag = "3")]
+    pub unit: String,
+    #[prost(oneof = "MetricData", tags = "5, 7")]
+    pub data: Option<MetricData>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum MetricData {
+    #[prost(message, tag = "5")]
+    Gauge(Gauge),
+    #[prost(message, tag = "7")]
+    Sum(Sum),
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Gauge {
+    #[prost(message, repeated, tag = "1")]
+    pub data_points: Vec<NumberDataPoint>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Sum {
+    #[prost(message, repeated, tag = "1")]
+    pub data_points: Vec<NumberDataPoint>,
+    #[prost(enumeration = "AggregationTemporality", tag = "2")]
+    pub aggregation_temporality: i32,
+    #[prost(bool, tag = "3")]
+    pub is_monotonic: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum AggregationTemporality {
+    Unspecified = 0,
+    Delta = 1,
+    Cumulative = 2,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct NumberDataPoint {
+    #[prost(message, repeated, tag = "7")]
+    pub attributes: Vec<KeyValue>,
+    #[prost(fixed64, tag = "2")]
+    pub start_time_unix_nano: u64,
+    #[prost(fixed64, tag = "3")]
+    pub time_unix_nano: u64,
+    #[prost(oneof = "NumberValue", tags = "4")]
+    pub value: Option<NumberValue>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum NumberValue {
+    #[prost(double, tag = "4")]
+    AsDouble(f64),
+}
+
+impl KeyValue {
+    pub fn string(key: &str, value: &str) -> Self {
+        KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(AnyValueKind::StringValue(value.to_string())),
+            }),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let value: Value = match self.value.as_ref().and_then(|value| value.value.as_ref()) {
+            Some(AnyValueKind::StringValue(value)) => json!({ "stringValue": value }),
+            None => json!({}),
+        };
+        json!({ "key": self.key, "value": value })
+    }
+}
+
+impl NumberDataPoint {
+    /* OTLP/JSON 규약 - 64bit 정수는 문자열, 필드 이름은 lowerCamelCase */
+    fn to_json(&self) -> Value {
+        let mut data_point: Value = json!({
+            "attributes": self.attributes.iter().map(KeyValue::to_json).collect::<Vec<Value>>(),
+            "timeUnixNano": self.time_unix_nano.to_string(),
+        });
+        if self.start_time_unix_nano > 0 {
+            data_point["startTimeUnixNano"] = json!(self.start_time_unix_nano.to_string());
+        }
+        if let Some(NumberValue::AsDouble(value)) = self.value {
+            data_point["asDouble"] = json!(value);
+        }
+        data_point
+    }
+}
+
+impl ExportMetricsServiceRequest {
+    #[doc = "수집 결과 한 주기를 OTLP 요청으로 만들어주는 함수 - 같은 이름의 시계열은 하나의 Metric 에 data point 로 묶는다."]
+    /// gauge 는 Gauge 로, counter 는 단조 증가 Sum 으로 보내며 이름의 _total 접미사는 붙이지 않는다 (OTel 명명 규칙).
+    ///
+    /// # Arguments
+    /// * `resource_attributes` - host.name, host.ip, os.type, service.name 등
+    /// * `samples` - 시계열 목록 (counter 는 temporality 에 맞게 증가분 또는 누적값)
+    /// * `temporality` - counter 의 aggregation temporality
+    /// * `start_time_ms` - counter 값이 집계된 구간의 시작 (delta: 직전 수집 시각, cumulative: 에이전트 시작 시각)
+    /// * `time_ms` - 수집 시각
+    ///
+    /// # Returns
+    /// * ExportMetricsServiceRequest
+    pub fn from_samples(
+        resource_attributes: Vec<KeyValue>,
+        samples: &[MetricSample],
+        temporality: AggregationTemporality,
+        start_time_ms: i64,
+        time_ms: i64,
+    ) -> Self {
+        let start_time_unix_nano: u64 = millis_to_nanos(start_time_ms);
+        let time_unix_nano: u64 = millis_to_nanos(time_ms);
+        let mut metrics: Vec<Metric> = Vec::new();
+
+        for sample in samples {
+            let data_point: NumberDataPoint = NumberDataPoint {
+                attributes: sample
+                    .labels
+                    .iter()
+                    .map(|(key, value)| KeyValue::string(key, value))
+                    .collect(),
+                start_time_unix_nano: if sample.kind == MetricKind::Counter { start_time_unix_nano } else { 0 },
+                time_unix_nano,
+                value: Some(NumberValue::AsDouble(sample.value)),
+            };
+
+            let name: &str = match sample.kind {
+                MetricKind::Counter => sample.name.strip_suffix("_total").unwrap_or(sample.name),
+                MetricKind::Gauge => sample.name,
+            };
+
+            match metrics.iter_mut().find(|metric| metric.name == name) {
+                Some(metric) => match metric.data.as_mut() {
+                    Some(MetricData::Gauge(gauge)) => gauge.data_points.push(data_point),
+                    Some(MetricData::Sum(sum)) => sum.data_points.push(data_point),
+                    None => (),
+                },
+                None => metrics.push(Metric {
+                    name: name.to_string(),
+                    description: sample.help.to_string(),
+                    unit: unit_of(name).to_string(),
+                    data: Some(match sample.kind {
+                        MetricKind::Gauge => MetricData::Gauge(Gauge {
+                            data_points: vec![data_point],
+                        }),
+                        MetricKind::Counter => MetricData::Sum(Sum {
+                            data_points: vec![data_point],
+                            aggregation_temporality: temporality as i32,
+                            is_monotonic: true,
+                        }),
+                    }),
+                }),
+            }
+        }
+
+        ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: Some(Resource {
+                    attributes: resource_attributes,
+                }),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: Some(InstrumentationScope {
+                        name: String::from("os_metric_beats"),
+                        version: env!("CARGO_PKG_VERSION").to_string(),
+                    }),
+                    metrics,
+                }],
+            }],
+        }
+    }
+
+    #[doc = "OTLP/JSON 형식으로 바꿔주는 함수"]
+    pub fn to_json(&self) -> Value {
+        let resource_metrics: Vec<Value> = self
+            .resource_metrics
+            .iter()
+            .map(|resource_metrics| {
+                let scope_metrics: Vec<Value> = resource_metrics
+                    .scope_metrics
+                    .iter()
+                    .map(|scope_metrics| {
+                        let metrics: Vec<Value> = scope_metrics.metrics.iter().map(metric_to_json).collect();
+                        json!({
+                            "scope": scope_metrics.scope.as_ref().map(|scope| json!({ "name": scope.name, "version": scope.version })),
+                            "metrics": metrics
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "resource": {
+                        "attributes": resource_metrics
+                            .resource
+                            .as_ref()
+                            .map(|resource| resource.attributes.iter().map(KeyValue::to_json).collect::<Vec<Value>>())
+                            .unwrap_or_default()
+                    },
+                    "scopeMetrics": scope_metrics
+                })
+            })
+            .collect();
+
+        json!({ "resourceMetrics": resource_metrics })
+    }
+}
+
+fn metric_to_json(metric: &Metric) -> Value {
+    let mut value: Value = json!({
+        "name": metric.name,
+        "description": metric.description,
+        "unit": metric.unit,
+    });
+
+    match &metric.data {
+        Some(MetricData::Gauge(gauge)) => {
+            value["gauge"] = json!({
+                "dataPoints": gauge.data_points.iter().map(NumberDataPoint::to_json).collect::<Vec<Value>>()
+            });
+        }
+        Some(MetricData::Sum(sum)) => {
+            value["sum"] = json!({
+                "dataPoints": sum.data_points.iter().map(NumberDataPoint::to_json).collect::<Vec<Value>>(),
+                "aggregationTemporality": sum.aggregation_temporality,
+                "isMonotonic": sum.is_monotonic
+            });
+        }
+        None => (),
+    }
+
+    value
+}
+
+#[doc = "이름의 단위 접미사로 UCUM 단위를 정해주는 함수"]
+fn unit_of(name: &str) -> &'static str {
+    if name.ends_with("_bytes") {
+        "By"
+    } else if name.ends_with("_seconds") {
+        "s"
+    } else if name.ends_with("_percent") {
+        "%"
+    } else {
+        "1"
+    }
+}
+
+fn millis_to_nanos(millis: i64) -> u64 {
+    (millis.max(0) as u64).saturating_mul(1_000_000)
+}
@@ -0,0 +1,25 @@
+use crate::common::*;
+
+use crate::model::exporter::{basic_auth_config::*, listen_tls_config::*};
+
+#[doc = "최근 수집 결과를 Prometheus/OpenMetrics text 로 노출하는 내장 HTTP 서버 설정"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct PrometheusConfig {
+    listen_addr: String,
+    path: String,
+    basic_auth: Option<BasicAuthConfig>,
+    tls: Option<ListenTlsConfig>,
+}
+
+impl Default for PrometheusConfig {
+    fn default() -> Self {
+        PrometheusConfig {
+            listen_addr: String::from("0.0.0.0:9479"),
+            path: String::from("/metrics"),
+            basic_auth: None,
+            tls: None,
+        }
+    }
+}
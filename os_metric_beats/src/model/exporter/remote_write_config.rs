@@ -0,0 +1,39 @@
+use crate::common::*;
+
+use crate::model::elastic::{retry_config::*, secret_value::*};
+use crate::model::exporter::basic_auth_config::*;
+
+#[doc = "Prometheus remote_write 프로토콜(snappy 압축 protobuf)로 수집 결과를 보내는 출력 설정 - Mimir, Thanos, VictoriaMetrics 등"]
+#[derive(Serialize, Deserialize, Debug, Clone, Getters)]
+#[getset(get = "pub")]
+#[serde(default)]
+pub struct RemoteWriteConfig {
+    url: String,
+    /* 아래 기준 중 하나라도 넘으면 전송 */
+    batch_max_samples: usize,
+    flush_interval_secs: u64,
+    /* 전송이 밀렸을 때 보관할 수집 결과(주기) 개수 - 넘으면 새 수집 결과를 버린다. */
+    queue_capacity: usize,
+    timeout_secs: u64,
+    /* 모든 시계열에 붙는 label - 같은 이름의 label 이 시계열에 이미 있으면 시계열 쪽 값을 사용 */
+    external_labels: BTreeMap<String, String>,
+    basic_auth: Option<BasicAuthConfig>,
+    bearer_token: Option<SecretValue>,
+    retry: RetryConfig,
+}
+
+impl Default for RemoteWriteConfig {
+    fn default() -> Self {
+        RemoteWriteConfig {
+            url: String::from("http://127.0.0.1:9090/api/v1/write"),
+            batch_max_samples: 2000,
+            flush_interval_secs: 10,
+            queue_capacity: 100,
+            timeout_secs: 10,
+            external_labels: BTreeMap::new(),
+            basic_auth: None,
+            bearer_token: None,
+            retry: RetryConfig::default(),
+        }
+    }
+}
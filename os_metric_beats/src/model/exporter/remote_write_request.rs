@@ -0,0 +1,99 @@
+use crate::common::*;
+
+use crate::model::exporter::metric_sample::*;
+
+/* prometheus/prompb/remote.proto, types.proto 중 remote_write 1.0 에 필요한 메시지만 옮긴 것 */
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct WriteRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub timeseries: Vec<TimeSeries>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TimeSeries {
+    #[prost(message, repeated, tag = "1")]
+    pub labels: Vec<Label>,
+    #[prost(message, repeated, tag = "2")]
+    pub samples: Vec<Sample>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Label {
+    #[prost(string, tag = "1")]
+    pub name: String,
+    #[prost(string, tag = "2")]
+    pub value: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Sample {
+    #[prost(double, tag = "1")]
+    pub value: f64,
+    #[prost(int64, tag = "2")]
+    pub timestamp: i64,
+}
+
+#[doc = "전송 대기 중인 수집 결과 한 주기 - counter 는 이미 누적값으로 바뀐 상태"]
+#[derive(Clone, Debug, new)]
+pub struct PendingSamples {
+    pub timestamp_ms: i64,
+    pub host: String,
+    pub samples: Vec<MetricSample>,
+}
+
+impl WriteRequest {
+    #[doc = "여러 주기의 수집 결과를 시계열 별로 묶어서 remote_write 요청으로 만들어주는 함수"]
+    /// label 은 __name__, 시계열 label, host, external label 순으로 모은 뒤 이름순으로 정렬한다 (remote_write 규약).
+    ///
+    /// # Arguments
+    /// * `pending` - 수집 순서대로 쌓인 전송 대기 결과
+    /// * `external_labels` - 모든 시계열에 붙일 label
+    ///
+    /// # Returns
+    /// * WriteRequest
+    pub fn from_pending(pending: &[PendingSamples], external_labels: &BTreeMap<String, String>) -> Self {
+        let mut series_index: HashMap<String, usize> = HashMap::new();
+        let mut timeseries: Vec<TimeSeries> = Vec::new();
+
+        for pending_samples in pending {
+            for sample in &pending_samples.samples {
+                let mut labels: BTreeMap<String, String> = BTreeMap::new();
+                labels.insert(String::from("__name__"), sample.name.to_string());
+                for (key, value) in &sample.labels {
+                    labels.insert(key.to_string(), value.clone());
+                }
+                labels
+                    .entry(String::from("host"))
+                    .or_insert_with(|| pending_samples.host.clone());
+                for (key, value) in external_labels {
+                    labels.entry(key.clone()).or_insert_with(|| value.clone());
+                }
+
+                let series_key: String = labels
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<String>>()
+                    .join("\u{1f}");
+
+                let idx: usize = *series_index.entry(series_key).or_insert_with(|| {
+                    timeseries.push(TimeSeries {
+                        labels: labels
+                            .into_iter()
+                            .map(|(name, value)| Label { name, value })
+                            .collect(),
+                        samples: Vec::new(),
+                    });
+                    timeseries.len() - 1
+                });
+
+                timeseries[idx].samples.push(Sample {
+                    value: sample.value,
+                    timestamp: pending_samples.timestamp_ms,
+                });
+            }
+        }
+
+        WriteRequest { timeseries }
+    }
+}
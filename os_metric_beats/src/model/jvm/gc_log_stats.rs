@@ -0,0 +1,65 @@
+use crate::common::*;
+
+use crate::model::jvm::gc_pause_event::*;
+
+use crate::utils_module::math_utils::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct GcLogStats {
+    pub pause_count: u64,
+    pub pause_total_ms: f64,
+    pub pause_max_ms: f64,
+    pub pause_p99_ms: f64,
+    pub heap_before_mb: f64,
+    pub heap_after_mb: f64,
+    pub to_space_exhausted: bool,
+    pub full_gc: bool,
+}
+
+impl GcLogStats {
+    #[doc = "수집 구간 동안 읽은 gc.log 라인들을 집계해주는 함수"]
+    /// # Arguments
+    /// * `lines` - 이번 구간에 새로 추가된 gc.log 라인
+    ///
+    /// # Returns
+    /// * GcLogStats
+    pub fn from_lines(lines: &[String]) -> Self {
+        let mut stats: GcLogStats = GcLogStats::default();
+        let mut pauses: Vec<f64> = Vec::new();
+
+        for line in lines {
+            if GcPauseEvent::is_to_space_exhausted(line) {
+                stats.to_space_exhausted = true;
+            }
+
+            /* ZGC 는 pause 라인 대신 collection 요약 라인에 heap 변화량을 남긴다. */
+            if let Some((heap_before_mb, heap_after_mb)) = GcPauseEvent::parse_collection_heap(line) {
+                stats.heap_before_mb = heap_before_mb;
+                stats.heap_after_mb = heap_after_mb;
+                continue;
+            }
+
+            let event: GcPauseEvent = match GcPauseEvent::parse_line(line) {
+                Some(event) => event,
+                None => continue,
+            };
+
+            stats.pause_count += 1;
+            stats.pause_total_ms += event.pause_ms;
+            stats.pause_max_ms = stats.pause_max_ms.max(event.pause_ms);
+            stats.full_gc |= event.full_gc;
+
+            /* heap 은 구간 내 마지막 pause 기준 */
+            if let (Some(heap_before_mb), Some(heap_after_mb)) = (event.heap_before_mb, event.heap_after_mb) {
+                stats.heap_before_mb = heap_before_mb;
+                stats.heap_after_mb = heap_after_mb;
+            }
+
+            pauses.push(event.pause_ms);
+        }
+
+        stats.pause_p99_ms = percentile(&pauses, 99.0);
+
+        stats
+    }
+}
@@ -0,0 +1,154 @@
+use crate::common::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct GcPauseEvent {
+    pub pause_ms: f64,
+    /* ZGC 의 pause 라인에는 heap 변화량이 없다. */
+    pub heap_before_mb: Option<f64>,
+    pub heap_after_mb: Option<f64>,
+    pub full_gc: bool,
+}
+
+impl GcPauseEvent {
+    #[doc = "JVM unified GC log 의 pause 라인을 파싱해주는 함수"]
+    /// 예) G1 `[2025-01-01T00:00:00.000+0000][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 1024M->512M(4096M) 12.345ms`
+    ///     ZGC `[2025-01-01T00:00:00.000+0000][info][gc,phases] GC(3) Pause Mark Start 0.012ms`
+    ///
+    /// # Arguments
+    /// * `line` - gc.log 의 한 라인
+    ///
+    /// # Returns
+    /// * Option<GcPauseEvent> - pause 요약 라인이 아닌 경우에는 None
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let (_, summary) = line.split_once("GC(")?;
+
+        if !summary.contains(" Pause ") {
+            return None;
+        }
+
+        let tokens: Vec<&str> = summary.split_whitespace().collect();
+
+        let pause_ms: f64 = tokens.last()?.strip_suffix("ms")?.parse().ok()?;
+
+        /* `1024M->512M(4096M)` 형태의 heap 변화량 - ZGC 의 pause 라인에는 없다. */
+        let heap_change: Option<(f64, f64)> = match tokens.iter().rev().find(|token| token.contains("->")) {
+            Some(heap_token) => Some(parse_heap_change(heap_token)?),
+            None => None,
+        };
+
+        Some(Self::new(
+            pause_ms,
+            heap_change.map(|(before, _)| before),
+            heap_change.map(|(_, after)| after),
+            summary.contains("Pause Full"),
+        ))
+    }
+
+    #[doc = "ZGC 의 collection 요약 라인에서 heap 변화량(MB)을 꺼내주는 함수"]
+    /// 예) `[2025-01-01T00:00:00.000+0000][info][gc] GC(3) Garbage Collection (Warmup) 204M(10%)->100M(5%)`
+    ///
+    /// # Returns
+    /// * Option<(f64, f64)> - (before, after), collection 요약 라인이 아닌 경우에는 None
+    pub fn parse_collection_heap(line: &str) -> Option<(f64, f64)> {
+        let (_, summary) = line.split_once("GC(")?;
+
+        if !summary.contains(" Collection (") || summary.contains(" Pause ") {
+            return None;
+        }
+
+        let heap_token: &str = summary.split_whitespace().find(|token| token.contains("->"))?;
+        parse_heap_change(heap_token)
+    }
+
+    #[doc = "to-space exhausted (JDK 17 이상: Evacuation Failure) 발생 여부를 확인해주는 함수"]
+    pub fn is_to_space_exhausted(line: &str) -> bool {
+        line.contains("To-space exhausted") || line.contains("Evacuation Failure")
+    }
+}
+
+#[doc = "`1024M->512M(4096M)` 혹은 `204M(10%)->100M(5%)` 형태의 heap 변화량을 (before, after) MB 로 변환해주는 함수"]
+fn parse_heap_change(heap_token: &str) -> Option<(f64, f64)> {
+    let (before, after) = heap_token.split_once("->")?;
+
+    Some((
+        parse_heap_size_mb(before.split('(').next()?)?,
+        parse_heap_size_mb(after.split('(').next()?)?,
+    ))
+}
+
+#[doc = "`512M`, `1G`, `2048K` 형태의 크기 문자열을 MB 단위로 변환해주는 함수"]
+fn parse_heap_size_mb(size: &str) -> Option<f64> {
+    let unit: char = size.chars().last()?;
+    let value: f64 = size[..size.len() - unit.len_utf8()].parse().ok()?;
+
+    match unit {
+        'K' => Some(value / 1024.0),
+        'M' => Some(value),
+        'G' => Some(value * 1024.0),
+        'B' => Some(value / 1024.0 / 1024.0),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_g1_young_pause() {
+        let line: &str = "[2025-01-01T00:00:00.000+0000][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 1024M->512M(4096M) 12.345ms";
+
+        let event: GcPauseEvent = GcPauseEvent::parse_line(line).unwrap();
+
+        assert_eq!(event.pause_ms, 12.345);
+        assert_eq!(event.heap_before_mb, Some(1024.0));
+        assert_eq!(event.heap_after_mb, Some(512.0));
+        assert!(!event.full_gc);
+    }
+
+    #[test]
+    fn parses_g1_full_pause() {
+        let line: &str = "[2025-01-01T00:00:00.000+0000][info][gc] GC(13) Pause Full (G1 Compaction Pause) 3G->1536M(4G) 850.1ms";
+
+        let event: GcPauseEvent = GcPauseEvent::parse_line(line).unwrap();
+
+        assert_eq!(event.pause_ms, 850.1);
+        assert_eq!(event.heap_before_mb, Some(3072.0));
+        assert_eq!(event.heap_after_mb, Some(1536.0));
+        assert!(event.full_gc);
+    }
+
+    #[test]
+    fn parses_zgc_pause_without_heap() {
+        let line: &str = "[2025-01-01T00:00:00.000+0000][info][gc,phases] GC(3) Pause Mark Start 0.012ms";
+
+        let event: GcPauseEvent = GcPauseEvent::parse_line(line).unwrap();
+
+        assert_eq!(event.pause_ms, 0.012);
+        assert_eq!(event.heap_before_mb, None);
+        assert_eq!(event.heap_after_mb, None);
+        assert!(!event.full_gc);
+
+        let generational: &str = "[2025-01-01T00:00:00.000+0000][info][gc,phases] GC(4) Y: Pause Relocate Start 0.008ms";
+        assert_eq!(GcPauseEvent::parse_line(generational).unwrap().pause_ms, 0.008);
+    }
+
+    #[test]
+    fn parses_zgc_collection_heap() {
+        let line: &str = "[2025-01-01T00:00:00.000+0000][info][gc] GC(3) Garbage Collection (Warmup) 204M(10%)->100M(5%)";
+
+        assert_eq!(GcPauseEvent::parse_collection_heap(line), Some((204.0, 100.0)));
+        assert_eq!(GcPauseEvent::parse_line(line).map(|event| event.pause_ms), None);
+    }
+
+    #[test]
+    fn ignores_non_pause_lines() {
+        for line in [
+            "[2025-01-01T00:00:00.000+0000][info][gc,start] GC(12) Pause Young (Normal) (G1 Evacuation Pause)",
+            "[2025-01-01T00:00:00.000+0000][info][gc,phases] GC(12)   Pre Evacuate Collection Set: 0.1ms",
+            "[2025-01-01T00:00:00.000+0000][info][gc,cpu] GC(12) User=0.01s Sys=0.00s Real=0.01s",
+        ] {
+            assert!(GcPauseEvent::parse_line(line).is_none(), "{}", line);
+        }
+    }
+}
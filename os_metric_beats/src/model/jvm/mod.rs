@@ -0,0 +1,2 @@
+pub mod gc_log_stats;
+pub mod gc_pause_event;
@@ -4,4 +4,16 @@ use crate::common::*;
 #[getset(get = "pub")]
 pub struct LinuxConfig {
     pub network_tx_rx_list: Vec<String>,
+    pub gc_log_path: Option<String>,
+    #[serde(default)]
+    pub slowlog_paths: Vec<String>,
+    /* 설정하면 jcmd 스레드 덤프로 잘리지 않은 스레드 이름을 구해 thread pool 별로 묶는다. */
+    pub jcmd_path: Option<String>,
+    #[serde(default = "default_thread_dump_interval_secs")]
+    pub thread_dump_interval_secs: u64,
+}
+
+#[doc = "새 스레드가 생겼을때 스레드 덤프를 다시 뜨는 최소 간격 - 덤프는 JVM safepoint 를 유발한다."]
+fn default_thread_dump_interval_secs() -> u64 {
+    300
 }
@@ -0,0 +1,9 @@
+use crate::common::*;
+
+use crate::model::memory::process_mmap_info::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct MmapInfo {
+    pub max_map_count: u64,
+    pub processes: Vec<ProcessMmapInfo>,
+}
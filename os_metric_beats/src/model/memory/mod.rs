@@ -1 +1,4 @@
-pub mod os_mem_res;
\ No newline at end of file
+pub mod mmap_info;
+pub mod os_mem_res;
+pub mod process_mem_res;
+pub mod process_mmap_info;
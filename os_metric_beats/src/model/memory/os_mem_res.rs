@@ -1,7 +1,13 @@
 use crate::common::*;
 
+use crate::model::memory::process_mem_res::*;
+
 #[derive(Deserialize, Debug, new)]
 pub struct OsMemRes {
     pub working_set_size: u64,
     pub virtual_size: u64,
+    /* 합계를 이루는 프로세스 별 사용량 - exporter 에서 pid/process label 로 나눠 내보낸다. */
+    #[serde(default)]
+    #[new(default)]
+    pub processes: Vec<ProcessMemRes>,
 }
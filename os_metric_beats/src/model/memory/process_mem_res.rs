@@ -0,0 +1,10 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 관련 프로세스 하나의 메모리 사용량"]
+#[derive(Clone, Deserialize, Debug, new)]
+pub struct ProcessMemRes {
+    pub pid: u32,
+    pub name: String,
+    pub working_set_size: u64,
+    pub virtual_size: u64,
+}
@@ -0,0 +1,11 @@
+use crate::common::*;
+
+#[doc = "Elasticsearch 관련 프로세스 하나의 mmap 개수 - vm.max_map_count 는 프로세스 단위 한도이다."]
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct ProcessMmapInfo {
+    pub pid: u32,
+    pub name: String,
+    pub map_count: u64,
+    pub map_count_usage: f32,
+    pub map_count_delta: i64,
+}
@@ -1,5 +1,9 @@
 use crate::common::*;
 
+use crate::model::elastic::{es_node_identity::*, es_node_stats::*, es_transport_stats::*};
+use crate::model::memory::process_mmap_info::*;
+use crate::model::process::{thread_pool_usage::*, thread_state_count::*};
+
 #[derive(Clone, Serialize, Deserialize, Debug, Builder)]
 #[builder(setter(into), default)]
 pub struct MetricInfo {
@@ -23,6 +27,24 @@ pub struct MetricInfo {
     pub tcp_close_wait: i32,
     pub process_use_mem: u64,
     pub process_virtual_mem: u64,
+    pub vm_max_map_count: u64,
+    pub es_process_mmaps: Vec<ProcessMmapInfo>,
+    pub es_thread_pools: HashMap<String, ThreadPoolUsage>,
+    pub es_thread_states: ThreadStateCount,
+    pub gc_pause_count: u64,
+    pub gc_pause_total_ms: f64,
+    pub gc_pause_max_ms: f64,
+    pub gc_pause_p99_ms: f64,
+    pub gc_heap_before_mb: f64,
+    pub gc_heap_after_mb: f64,
+    pub gc_to_space_exhausted: bool,
+    pub gc_full_gc: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub es_node: Option<EsNodeIdentity>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub es_output_nodes: Vec<EsNodeStats>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub es_output_transport: Vec<EsTransportStats>,
 }
 
 impl Default for MetricInfo {
@@ -48,6 +70,89 @@ impl Default for MetricInfo {
             tcp_close_wait: 0,
             process_use_mem: 0,
             process_virtual_mem: 0,
+            vm_max_map_count: 0,
+            es_process_mmaps: Vec::new(),
+            es_thread_pools: HashMap::new(),
+            es_thread_states: ThreadStateCount::default(),
+            gc_pause_count: 0,
+            gc_pause_total_ms: 0.0,
+            gc_pause_max_ms: 0.0,
+            gc_pause_p99_ms: 0.0,
+            gc_heap_before_mb: 0.0,
+            gc_heap_after_mb: 0.0,
+            gc_to_space_exhausted: false,
+            gc_full_gc: false,
+            es_node: None,
+            es_output_nodes: Vec::new(),
+            es_output_transport: Vec::new(),
         }
     }
-}
\ No newline at end of file
+}
+
+impl MetricInfo {
+    #[doc = "시스템 지표 문서의 명시적 mapping - 동적 mapping 으로 실수 필드가 long 이 되는 문제를 막는다."]
+    pub fn index_mappings() -> Value {
+        json!({
+            "dynamic_templates": [
+                { "thread_pool_count": { "path_match": "es_thread_pools.*.thread_count", "mapping": { "type": "long" } } },
+                { "thread_pool_cpu_time": { "path_match": "es_thread_pools.*.cpu_time_ms", "mapping": { "type": "long" } } },
+                { "thread_pool_cpu_usage": { "path_match": "es_thread_pools.*.cpu_usage", "mapping": { "type": "float" } } },
+                { "strings_as_keyword": { "match_mapping_type": "string", "mapping": { "type": "keyword", "ignore_above": 1024 } } }
+            ],
+            "properties": {
+                "timestamp": { "type": "date" },
+                "host": { "type": "keyword" },
+                "system_cpu_usage": { "type": "float" },
+                "system_disk_usage": { "type": "double" },
+                "system_memory_usage": { "type": "double" },
+                "network_received": { "type": "long" },
+                "network_transmitted": { "type": "long" },
+                "process_count": { "type": "long" },
+                "recv_dropped_packets": { "type": "long" },
+                "send_dropped_packets": { "type": "long" },
+                "recv_errors_packet": { "type": "long" },
+                "send_errors_packet": { "type": "long" },
+                "tcp_connections": { "type": "integer" },
+                "udp_sockets": { "type": "integer" },
+                "tcp_established": { "type": "integer" },
+                "tcp_timewait": { "type": "integer" },
+                "tcp_listen": { "type": "integer" },
+                "tcp_close_wait": { "type": "integer" },
+                "process_use_mem": { "type": "long" },
+                "process_virtual_mem": { "type": "long" },
+                "vm_max_map_count": { "type": "long" },
+                "es_process_mmaps": {
+                    "properties": {
+                        "pid": { "type": "long" },
+                        "name": { "type": "keyword" },
+                        "map_count": { "type": "long" },
+                        "map_count_usage": { "type": "float" },
+                        "map_count_delta": { "type": "long" }
+                    }
+                },
+                "es_thread_pools": { "type": "object" },
+                "es_thread_states": {
+                    "properties": {
+                        "running": { "type": "integer" },
+                        "sleeping": { "type": "integer" },
+                        "disk_sleep": { "type": "integer" },
+                        "stopped": { "type": "integer" },
+                        "zombie": { "type": "integer" },
+                        "other": { "type": "integer" }
+                    }
+                },
+                "gc_pause_count": { "type": "long" },
+                "gc_pause_total_ms": { "type": "double" },
+                "gc_pause_max_ms": { "type": "double" },
+                "gc_pause_p99_ms": { "type": "double" },
+                "gc_heap_before_mb": { "type": "double" },
+                "gc_heap_after_mb": { "type": "double" },
+                "gc_to_space_exhausted": { "type": "boolean" },
+                "gc_full_gc": { "type": "boolean" },
+                "es_node": EsNodeIdentity::index_mappings(),
+                "es_output_nodes": EsNodeStats::index_mappings(),
+                "es_output_transport": EsTransportStats::index_mappings()
+            }
+        })
+    }
+}
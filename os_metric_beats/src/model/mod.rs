@@ -1,7 +1,13 @@
+pub mod elastic;
 pub mod elastic_info_config;
+pub mod elastic_outputs_config;
+pub mod exporter;
+pub mod jvm;
 pub mod linux_config;
 pub mod metric_info;
 pub mod network;
 pub mod network_packet;
+pub mod process;
+pub mod slowlog;
 pub mod system_config;
 pub mod memory;
\ No newline at end of file
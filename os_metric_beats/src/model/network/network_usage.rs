@@ -1,5 +1,7 @@
 use crate::common::*;
 
+use crate::model::network::iface_counters::*;
+
 #[derive(Clone, Serialize, Deserialize, Debug, new)]
 pub struct NetworkUsage {
     pub network_received: u64,
@@ -9,4 +11,7 @@ pub struct NetworkUsage {
     pub loop_back_transmitted: u64,
     pub ethernet_received: u64,
     pub ethernet_transmitted: u64,
+    /* 인터페이스 별 커널 누적 송/수신 바이트 (rx/tx) - exporter 전용 */
+    #[new(default)]
+    pub interfaces: BTreeMap<String, IfaceCounters>,
 }
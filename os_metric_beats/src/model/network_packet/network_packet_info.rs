@@ -1,9 +1,14 @@
 use crate::common::*;
 
+use crate::model::network_packet::iface_counters::*;
+
 #[derive(Clone, Serialize, Deserialize, Debug, new)]
 pub struct NetworkPacketInfo {
     pub recv_dropped_packets: u64,
     pub send_dropped_packets: u64,
     pub recv_errors_packet: u64,
     pub send_errors_packet: u64,
+    /* 인터페이스 별 커널 누적 드롭/에러 패킷 - exporter 전용 */
+    #[new(default)]
+    pub interfaces: BTreeMap<String, IfaceCounters>,
 }
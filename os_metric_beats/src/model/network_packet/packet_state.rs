@@ -22,7 +22,7 @@ impl PacketState {
             network_packet_info.recv_dropped_packets,
             network_packet_info.send_dropped_packets,
             network_packet_info.recv_errors_packet,
-            network_packet_info.send_dropped_packets
+            network_packet_info.send_errors_packet
         );
 
         self.ifaces.insert(name, iface_counter);
@@ -0,0 +1,3 @@
+pub mod thread_pool_info;
+pub mod thread_pool_usage;
+pub mod thread_state_count;
@@ -0,0 +1,9 @@
+use crate::common::*;
+
+use crate::model::process::{thread_pool_usage::*, thread_state_count::*};
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct ThreadPoolInfo {
+    pub thread_pools: HashMap<String, ThreadPoolUsage>,
+    pub thread_states: ThreadStateCount,
+}
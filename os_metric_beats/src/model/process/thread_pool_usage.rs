@@ -0,0 +1,8 @@
+use crate::common::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, new)]
+pub struct ThreadPoolUsage {
+    pub thread_count: u32,
+    pub cpu_time_ms: u64,
+    pub cpu_usage: f32,
+}
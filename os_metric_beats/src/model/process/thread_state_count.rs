@@ -0,0 +1,25 @@
+use crate::common::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct ThreadStateCount {
+    pub running: u32,
+    pub sleeping: u32,
+    pub disk_sleep: u32,
+    pub stopped: u32,
+    pub zombie: u32,
+    pub other: u32,
+}
+
+impl ThreadStateCount {
+    #[doc = "/proc/<pid>/task/<tid>/stat 의 state 문자를 기준으로 개수를 증가시켜주는 함수"]
+    pub fn add_state(&mut self, state: char) {
+        match state {
+            'R' => self.running += 1,
+            'S' | 'I' => self.sleeping += 1,
+            'D' => self.disk_sleep += 1,
+            'T' | 't' => self.stopped += 1,
+            'Z' | 'X' => self.zombie += 1,
+            _ => self.other += 1,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod slowlog_entry;
+pub mod slowlog_metric_info;
+pub mod slowlog_stats;
@@ -0,0 +1,227 @@
+use crate::common::*;
+
+#[doc = "샘플로 보관할 쿼리/색인 구문의 최대 길이"]
+const MAX_SOURCE_LEN: usize = 2048;
+
+#[derive(Clone, Serialize, Deserialize, Debug, new)]
+pub struct SlowLogEntry {
+    pub slowlog_type: String,
+    pub index_name: String,
+    pub shard: Option<String>,
+    pub took_millis: u64,
+    pub source: String,
+}
+
+impl SlowLogEntry {
+    #[doc = "slow log 한 라인을 파싱해주는 함수 - JSON(ES 7/8) 및 plain 포맷 지원"]
+    /// # Arguments
+    /// * `line` - slow log 파일의 한 라인
+    ///
+    /// # Returns
+    /// * Option<SlowLogEntry> - slow log 라인이 아닌 경우에는 None
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let line: &str = line.trim();
+
+        if line.starts_with('{') {
+            Self::parse_json_line(line)
+        } else {
+            Self::parse_plain_line(line)
+        }
+    }
+
+    #[doc = "JSON 포맷의 slow log 라인을 파싱해주는 함수"]
+    /// ES 8: `elasticsearch.slowlog.*` 필드, ES 7: `type`, `took_millis`, `source` 필드를 사용한다.
+    fn parse_json_line(line: &str) -> Option<Self> {
+        let json: Value = serde_json::from_str(line).ok()?;
+
+        let get_field = |keys: &[&str]| -> Option<Value> {
+            keys.iter().find_map(|key| json.get(*key).cloned())
+        };
+
+        let logger: String = get_field(&["event.dataset", "type", "log.logger"])?
+            .as_str()?
+            .to_string();
+        let slowlog_type: String = classify_slowlog_type(&logger)?;
+
+        let took_millis: u64 = match get_field(&["elasticsearch.slowlog.took_millis", "took_millis"])? {
+            Value::Number(took) => took.as_u64()?,
+            Value::String(took) => took.parse().ok()?,
+            _ => return None,
+        };
+
+        let message: String = get_field(&["elasticsearch.slowlog.message", "message"])
+            .and_then(|message| message.as_str().map(String::from))
+            .unwrap_or_default();
+        let (index_name, shard) = parse_index_and_shard(&message)?;
+
+        let source: String = get_field(&["elasticsearch.slowlog.source", "source"])
+            .and_then(|source| source.as_str().map(String::from))
+            .unwrap_or_default();
+
+        Some(Self::new(
+            slowlog_type,
+            index_name,
+            shard,
+            took_millis,
+            truncate_source(&source),
+        ))
+    }
+
+    #[doc = "plain 포맷의 slow log 라인을 파싱해주는 함수"]
+    /// 예) `[2025-01-01T00:00:00,000][WARN ][index.search.slowlog.query] [node-1] [my-index][0] took[1.2s], took_millis[1200], ... source[{...}]`
+    fn parse_plain_line(line: &str) -> Option<Self> {
+        /* 구문(source)에 logger 이름과 같은 문자열이 있어도 잘못 분류하지 않도록 logger 자리만 본다. */
+        let slowlog_type: String = classify_slowlog_type(plain_logger(line)?)?;
+
+        let took_pos: usize = line.find(" took[")?;
+        let took_millis: u64 = extract_bracket_value(line, "took_millis[")?.parse().ok()?;
+
+        /* took[ 직전의 `[index][shard]` 혹은 `[index/uuid]` 부분 */
+        let prefix: &str = line[..took_pos].trim_end();
+        let index_part: &str = &prefix[prefix.rfind("] [").map(|pos| pos + 2).unwrap_or(0)..];
+        let (index_name, shard) = parse_index_and_shard(index_part)?;
+
+        /* search slow log 는 source[...] 뒤에 id[...] 가 붙고, indexing slow log 는 source[...] 로 끝난다. */
+        let source: String = line
+            .find("source[")
+            .map(|pos| {
+                let source: &str = &line[pos + "source[".len()..];
+                source
+                    .find("], id[")
+                    .or_else(|| source.rfind(']'))
+                    .map(|end| &source[..end])
+                    .unwrap_or(source)
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        Some(Self::new(
+            slowlog_type,
+            index_name,
+            shard,
+            took_millis,
+            truncate_source(&source),
+        ))
+    }
+}
+
+#[doc = "logger 이름으로 search/indexing slow log 여부를 구분해주는 함수"]
+/// ES 7 plain 포맷은 logger 이름을 줄여서(`i.s.s.query`, `i.i.s.index`) 남긴다.
+fn classify_slowlog_type(logger: &str) -> Option<String> {
+    if logger.contains("search_slowlog") || logger.contains("search.slowlog") || logger.starts_with("i.s.s.") {
+        Some(String::from("search"))
+    } else if logger.contains("indexing_slowlog") || logger.contains("indexing.slowlog") || logger.starts_with("i.i.s.") {
+        Some(String::from("indexing"))
+    } else {
+        None
+    }
+}
+
+#[doc = "plain 포맷 라인 앞부분의 `[시각][레벨][logger]` 에서 logger 이름을 꺼내주는 함수"]
+fn plain_logger(line: &str) -> Option<&str> {
+    let mut rest: &str = line;
+    let mut groups: Vec<&str> = Vec::new();
+
+    while let Some(group) = rest.strip_prefix('[') {
+        let end: usize = group.find(']')?;
+        groups.push(group[..end].trim());
+        rest = &group[end + 1..];
+    }
+
+    groups.get(2).copied()
+}
+
+#[doc = "`[index][shard]` 혹은 `[index/uuid]` 형태의 문자열에서 인덱스와 샤드를 꺼내주는 함수"]
+fn parse_index_and_shard(message: &str) -> Option<(String, Option<String>)> {
+    let groups: Vec<&str> = message
+        .split(['[', ']'])
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .collect();
+
+    let index_name: &str = groups.first()?;
+    let index_name: &str = index_name.split('/').next().unwrap_or(index_name);
+    let shard: Option<String> = groups
+        .get(1)
+        .filter(|shard| shard.chars().all(|c| c.is_ascii_digit()))
+        .map(|shard| shard.to_string());
+
+    Some((index_name.to_string(), shard))
+}
+
+#[doc = "`key[value]` 형태에서 value 를 꺼내주는 함수"]
+fn extract_bracket_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    let start: usize = line.find(key)? + key.len();
+    let end: usize = line[start..].find(']')? + start;
+    Some(&line[start..end])
+}
+
+#[doc = "샘플 구문이 너무 긴 경우 잘라주는 함수"]
+fn truncate_source(source: &str) -> String {
+    source.chars().take(MAX_SOURCE_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_es8_json_search_line() {
+        let line: &str = r#"{"@timestamp":"2025-01-01T00:00:00.000Z", "log.level":"WARN", "elasticsearch.slowlog.message":"[my-index][3]", "elasticsearch.slowlog.source":"{\"query\":{\"match_all\":{}}}", "elasticsearch.slowlog.took":"1.2s", "elasticsearch.slowlog.took_millis":1200, "event.dataset":"elasticsearch.index_search_slowlog", "log.logger":"index.search.slowlog.query"}"#;
+
+        let entry: SlowLogEntry = SlowLogEntry::parse_line(line).unwrap();
+
+        assert_eq!(entry.slowlog_type, "search");
+        assert_eq!(entry.index_name, "my-index");
+        assert_eq!(entry.shard.as_deref(), Some("3"));
+        assert_eq!(entry.took_millis, 1200);
+        assert_eq!(entry.source, r#"{"query":{"match_all":{}}}"#);
+    }
+
+    #[test]
+    fn parses_es7_json_indexing_line() {
+        let line: &str = r#"{"type": "index_indexing_slowlog", "timestamp": "2021-01-01T00:00:00,000Z", "level": "WARN", "component": "i.i.s.index", "node.name": "node-1", "message": "[my-index/aBcDeF]", "took": "15ms", "took_millis": "15", "source": "{\"field\":\"value\"}"}"#;
+
+        let entry: SlowLogEntry = SlowLogEntry::parse_line(line).unwrap();
+
+        assert_eq!(entry.slowlog_type, "indexing");
+        assert_eq!(entry.index_name, "my-index");
+        assert_eq!(entry.shard, None);
+        assert_eq!(entry.took_millis, 15);
+        assert_eq!(entry.source, r#"{"field":"value"}"#);
+    }
+
+    #[test]
+    fn parses_plain_search_line() {
+        let line: &str = r#"[2025-01-01T00:00:00,000][WARN ][index.search.slowlog.query] [node-1] [my-index][0] took[1.2s], took_millis[1200], total_hits[10], stats[], search_type[QUERY_THEN_FETCH], total_shards[1], source[{"query":{"match_all":{}}}], id[], "#;
+
+        let entry: SlowLogEntry = SlowLogEntry::parse_line(line).unwrap();
+
+        assert_eq!(entry.slowlog_type, "search");
+        assert_eq!(entry.index_name, "my-index");
+        assert_eq!(entry.shard.as_deref(), Some("0"));
+        assert_eq!(entry.took_millis, 1200);
+        assert_eq!(entry.source, r#"{"query":{"match_all":{}}}"#);
+    }
+
+    #[test]
+    fn parses_plain_indexing_line_with_abbreviated_logger() {
+        let line: &str = r#"[2021-01-01T00:00:00,000][WARN ][i.i.s.index              ] [node-1] [my-index/aBcDeF] took[15ms], took_millis[15], type[_doc], id[1], routing[], source[{"field":"value"}]"#;
+
+        let entry: SlowLogEntry = SlowLogEntry::parse_line(line).unwrap();
+
+        assert_eq!(entry.slowlog_type, "indexing");
+        assert_eq!(entry.index_name, "my-index");
+        assert_eq!(entry.shard, None);
+        assert_eq!(entry.took_millis, 15);
+        assert_eq!(entry.source, r#"{"field":"value"}"#);
+    }
+
+    #[test]
+    fn classifies_plain_line_by_logger_only() {
+        let line: &str = r#"[2025-01-01T00:00:00,000][WARN ][index.indexing.slowlog.index] [node-1] [logs/aBcDeF] took[15ms], took_millis[15], type[_doc], id[1], routing[], source[{"message":"index.search.slowlog.query is slow"}]"#;
+
+        assert_eq!(SlowLogEntry::parse_line(line).unwrap().slowlog_type, "indexing");
+        assert!(SlowLogEntry::parse_line("[2025-01-01T00:00:00,000][INFO ][o.e.n.Node] [node-1] started").is_none());
+    }
+}
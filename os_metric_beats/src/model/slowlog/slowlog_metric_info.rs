@@ -0,0 +1,42 @@
+use crate::common::*;
+
+use crate::model::elastic::es_node_identity::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default, Builder)]
+#[builder(setter(into), default)]
+pub struct SlowLogMetricInfo {
+    pub timestamp: String,
+    pub host: String,
+    pub slowlog_type: String,
+    pub slowlog_index: String,
+    pub slow_count: u64,
+    pub took_max_ms: u64,
+    pub took_avg_ms: f64,
+    pub top_shard: Option<String>,
+    pub worst_source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub es_node: Option<EsNodeIdentity>,
+}
+
+impl SlowLogMetricInfo {
+    #[doc = "slow log 집계 문서의 명시적 mapping"]
+    pub fn index_mappings() -> Value {
+        json!({
+            "dynamic_templates": [
+                { "strings_as_keyword": { "match_mapping_type": "string", "mapping": { "type": "keyword", "ignore_above": 1024 } } }
+            ],
+            "properties": {
+                "timestamp": { "type": "date" },
+                "host": { "type": "keyword" },
+                "slowlog_type": { "type": "keyword" },
+                "slowlog_index": { "type": "keyword" },
+                "slow_count": { "type": "long" },
+                "took_max_ms": { "type": "long" },
+                "took_avg_ms": { "type": "double" },
+                "top_shard": { "type": "keyword" },
+                "worst_source": { "type": "text" },
+                "es_node": EsNodeIdentity::index_mappings()
+            }
+        })
+    }
+}
@@ -0,0 +1,65 @@
+use crate::common::*;
+
+use crate::model::slowlog::slowlog_entry::*;
+
+#[derive(Clone, Serialize, Deserialize, Debug, Default)]
+pub struct SlowLogStats {
+    pub slowlog_type: String,
+    pub index_name: String,
+    pub slow_count: u64,
+    pub took_max_ms: u64,
+    pub took_avg_ms: f64,
+    pub top_shard: Option<String>,
+    pub worst_source: String,
+}
+
+impl SlowLogStats {
+    #[doc = "수집 구간 동안 읽은 slow log 를 (종류, 인덱스) 단위로 집계해주는 함수"]
+    /// # Arguments
+    /// * `entries` - 이번 구간에 새로 추가된 slow log
+    ///
+    /// # Returns
+    /// * Vec<SlowLogStats>
+    pub fn aggregate(entries: Vec<SlowLogEntry>) -> Vec<Self> {
+        let mut grouped: HashMap<(String, String), Vec<SlowLogEntry>> = HashMap::new();
+
+        for entry in entries {
+            grouped
+                .entry((entry.slowlog_type.clone(), entry.index_name.clone()))
+                .or_default()
+                .push(entry);
+        }
+
+        grouped
+            .into_iter()
+            .map(|((slowlog_type, index_name), entries)| {
+                let slow_count: u64 = entries.len() as u64;
+                let took_total_ms: u64 = entries.iter().map(|entry| entry.took_millis).sum();
+
+                let mut shard_counts: HashMap<&str, u64> = HashMap::new();
+                for shard in entries.iter().filter_map(|entry| entry.shard.as_deref()) {
+                    *shard_counts.entry(shard).or_default() += 1;
+                }
+
+                let top_shard: Option<String> = shard_counts
+                    .into_iter()
+                    .max_by_key(|(_shard, count)| *count)
+                    .map(|(shard, _count)| shard.to_string());
+
+                /* 가장 오래 걸린 구문을 샘플로 보관 */
+                let worst: Option<&SlowLogEntry> =
+                    entries.iter().max_by_key(|entry| entry.took_millis);
+
+                SlowLogStats {
+                    slowlog_type,
+                    index_name,
+                    slow_count,
+                    took_max_ms: worst.map(|entry| entry.took_millis).unwrap_or(0),
+                    took_avg_ms: took_total_ms as f64 / slow_count as f64,
+                    top_shard,
+                    worst_source: worst.map(|entry| entry.source.clone()).unwrap_or_default(),
+                }
+            })
+            .collect()
+    }
+}
@@ -1,8 +1,22 @@
 use crate::common::*;
 
+use crate::model::elastic::secret_value::*;
+use crate::model::exporter::{influx_config::*, otlp_config::*, prometheus_config::*, remote_write_config::*};
+
 #[derive(Serialize, Deserialize, Debug, Getters)]
 #[getset(get = "pub")]
 pub struct SystemConfig {
     pub os_server_ip: String,
     pub os_ver: String,
+    pub local_es_url: Option<String>,
+    /* 로컬 노드 접속에 인증/TLS 설정을 빌려 쓸 출력 이름 (없으면 첫 번째 출력) */
+    pub local_es_output: Option<String>,
+    pub local_es_id: Option<String>,
+    pub local_es_pw: Option<SecretValue>,
+    #[serde(default)]
+    pub host_tags: HashMap<String, String>,
+    pub prometheus: Option<PrometheusConfig>,
+    pub remote_write: Option<RemoteWriteConfig>,
+    pub otlp: Option<OtlpConfig>,
+    pub influx: Option<InfluxConfig>,
 }
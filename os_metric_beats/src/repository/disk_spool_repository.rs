@@ -0,0 +1,561 @@
+use crate::common::*;
+
+use crate::model::elastic::{es_document::*, spool_config::*};
+
+use std::io::Read;
+
+#[doc = "spool segment 파일 확장자"]
+const SEGMENT_EXTENSION: &str = "seg";
+
+#[doc = "segment 별 재전송 위치(byte offset)를 기록하는 sidecar 파일 확장자"]
+const CURSOR_EXTENSION: &str = "cursor";
+
+#[doc = "record 헤더 크기: payload 길이(u32) + crc32(u32)"]
+const RECORD_HEADER_LEN: usize = 8;
+
+#[doc = "peek_oldest 로 꺼낸 재전송 대상"]
+#[derive(Debug, Default)]
+pub struct SpoolBatch {
+    pub docs: Vec<EsDocument>,
+    /* docs 와 같은 순서의 record 시작 위치 - 일부만 재전송 완료로 기록할 때 사용 */
+    pub doc_offsets: Vec<u64>,
+    /* 역직렬화에 실패한 record - dead-letter 로 보낸다. */
+    pub undecodable: Vec<UndecodableRecord>,
+}
+
+#[doc = "spool 에서 역직렬화하지 못한 record"]
+#[derive(Debug)]
+pub struct UndecodableRecord {
+    pub offset: u64,
+    pub payload: String,
+    pub reason: String,
+}
+
+impl SpoolBatch {
+    pub fn len(&self) -> usize {
+        self.docs.len() + self.undecodable.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[doc = "Elasticsearch 장애 시 전송하지 못한 문서를 디스크에 순서대로 보관해주는 저장소"]
+/// 문서는 `spool-<seq>.seg` segment 파일에 `[길이][crc32][json]` record 형태로 추가된다.
+/// 전체 크기가 max_bytes 를 넘으면 가장 오래된 segment 부터 삭제한다.
+/// 가장 오래된 segment 의 재전송 위치는 `spool-<seq>.cursor` 에 byte offset 으로 남겨서
+/// 재전송 도중 재시작하더라도 이미 보낸 문서를 다시 보내지 않는다.
+#[derive(Debug)]
+pub struct DiskSpoolRepository {
+    spool_config: SpoolConfig,
+    segments: VecDeque<u64>,
+    total_bytes: u64,
+    /* 가장 오래된 segment 에서 전송 완료된 위치 */
+    replayed_offset: u64,
+    /* peek_oldest 로 꺼낸 문서들이 끝나는 위치 - commit_replayed 에서 replayed_offset 이 된다. */
+    peeked_offset: u64,
+}
+
+impl DiskSpoolRepository {
+    #[doc = "spool 디렉토리를 열고 이전 실행에서 남은 segment 들을 불러오는 함수"]
+    pub fn new(spool_config: SpoolConfig) -> Result<Self, anyhow::Error> {
+        fs::create_dir_all(spool_config.path())?;
+
+        let mut segments: Vec<u64> = Vec::new();
+        let mut total_bytes: u64 = 0;
+
+        for entry in fs::read_dir(spool_config.path())?.flatten() {
+            let path: std::path::PathBuf = entry.path();
+
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+                continue;
+            }
+
+            let seq: Option<u64> = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.strip_prefix("spool-"))
+                .and_then(|seq| seq.parse().ok());
+
+            if let Some(seq) = seq {
+                segments.push(seq);
+                total_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+
+        segments.sort_unstable();
+
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository {
+            spool_config,
+            segments: segments.into(),
+            total_bytes,
+            replayed_offset: 0,
+            peeked_offset: 0,
+        };
+
+        /* 비정상 종료로 마지막 segment 끝에 일부만 기록된 record 가 남았을 수 있다. */
+        if let Some(last_seq) = spool.segments.back().copied() {
+            spool.truncate_torn_tail(last_seq)?;
+        }
+
+        if let Some(oldest_seq) = spool.segments.front().copied() {
+            spool.replayed_offset = spool.load_cursor(oldest_seq);
+            spool.peeked_offset = spool.replayed_offset;
+
+            info!(
+                "Loaded {} spool segments ({} bytes) from {}, resuming at offset {} of segment {}",
+                spool.segments.len(),
+                spool.total_bytes,
+                spool.spool_config.path(),
+                spool.replayed_offset,
+                oldest_seq
+            );
+        }
+
+        Ok(spool)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    #[doc = "segment 번호로 파일 경로를 만들어주는 함수"]
+    fn segment_path(&self, seq: u64) -> std::path::PathBuf {
+        std::path::Path::new(self.spool_config.path())
+            .join(format!("spool-{:020}.{}", seq, SEGMENT_EXTENSION))
+    }
+
+    #[doc = "segment 번호로 재전송 위치 sidecar 파일 경로를 만들어주는 함수"]
+    fn cursor_path(&self, seq: u64) -> std::path::PathBuf {
+        std::path::Path::new(self.spool_config.path())
+            .join(format!("spool-{:020}.{}", seq, CURSOR_EXTENSION))
+    }
+
+    #[doc = "segment 의 재전송 위치를 읽어주는 함수 - 기록이 없으면 처음(0)부터"]
+    fn load_cursor(&self, seq: u64) -> u64 {
+        fs::read_to_string(self.cursor_path(seq))
+            .ok()
+            .and_then(|offset| offset.trim().parse().ok())
+            .unwrap_or(0)
+    }
+
+    #[doc = "segment 의 재전송 위치를 임시 파일에 쓰고 rename 해서 원자적으로 기록해주는 함수"]
+    fn save_cursor(&self, seq: u64, offset: u64) -> Result<(), anyhow::Error> {
+        let cursor_path: std::path::PathBuf = self.cursor_path(seq);
+        let tmp_path: std::path::PathBuf = cursor_path.with_extension(format!("{}.tmp", CURSOR_EXTENSION));
+
+        let mut tmp_file: File = File::create(&tmp_path)?;
+        tmp_file.write_all(offset.to_string().as_bytes())?;
+        tmp_file.sync_all()?;
+        fs::rename(&tmp_path, &cursor_path)?;
+
+        Ok(())
+    }
+
+    #[doc = "segment 파일과 재전송 위치 파일을 삭제하고 크기를 차감해주는 함수"]
+    fn delete_segment(&mut self, seq: u64) -> Result<std::path::PathBuf, anyhow::Error> {
+        let path: std::path::PathBuf = self.segment_path(seq);
+        let segment_len: u64 = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        fs::remove_file(&path)?;
+        let _ = fs::remove_file(self.cursor_path(seq));
+        self.total_bytes = self.total_bytes.saturating_sub(segment_len);
+
+        Ok(path)
+    }
+
+    #[doc = "segment 에서 checksum 까지 온전한 record 들이 끝나는 위치를 구해주는 함수"]
+    /// # Returns
+    /// * usize - 처음으로 손상(또는 일부만 기록)된 record 의 시작 위치, 모두 온전하면 파일 길이
+    fn valid_len(segment_bytes: &[u8]) -> usize {
+        let mut cursor: usize = 0;
+
+        while cursor + RECORD_HEADER_LEN <= segment_bytes.len() {
+            let payload_len: usize = u32::from_le_bytes([
+                segment_bytes[cursor],
+                segment_bytes[cursor + 1],
+                segment_bytes[cursor + 2],
+                segment_bytes[cursor + 3],
+            ]) as usize;
+            let checksum: u32 = u32::from_le_bytes([
+                segment_bytes[cursor + 4],
+                segment_bytes[cursor + 5],
+                segment_bytes[cursor + 6],
+                segment_bytes[cursor + 7],
+            ]);
+            let payload_end: usize = cursor + RECORD_HEADER_LEN + payload_len;
+
+            if payload_end > segment_bytes.len()
+                || crc32fast::hash(&segment_bytes[cursor + RECORD_HEADER_LEN..payload_end]) != checksum
+            {
+                break;
+            }
+
+            cursor = payload_end;
+        }
+
+        cursor
+    }
+
+    #[doc = "segment 끝의 손상된 record 부터 잘라내서 이후 추가되는 record 가 읽히지 않는 일이 없게 해주는 함수"]
+    fn truncate_torn_tail(&mut self, seq: u64) -> Result<(), anyhow::Error> {
+        let path: std::path::PathBuf = self.segment_path(seq);
+        let segment_bytes: Vec<u8> = fs::read(&path)?;
+        let valid_len: usize = Self::valid_len(&segment_bytes);
+
+        if valid_len < segment_bytes.len() {
+            warn!(
+                "[WARN][DiskSpoolRepository->truncate_torn_tail] Truncating {} bytes of torn records at the end of {:?}",
+                segment_bytes.len() - valid_len,
+                path
+            );
+
+            let segment_file: File = fs::OpenOptions::new().write(true).open(&path)?;
+            segment_file.set_len(valid_len as u64)?;
+            segment_file.sync_all()?;
+            self.total_bytes = self
+                .total_bytes
+                .saturating_sub((segment_bytes.len() - valid_len) as u64);
+        }
+
+        Ok(())
+    }
+
+    #[doc = "문서들을 spool 의 마지막 segment 에 순서대로 추가해주는 함수"]
+    /// # Arguments
+    /// * `docs` - 전송하지 못한 문서 목록
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    pub fn append(&mut self, docs: &[EsDocument]) -> Result<(), anyhow::Error> {
+        let mut segment_file: Option<(u64, File)> = None;
+
+        for doc in docs {
+            let payload: Vec<u8> = serde_json::to_vec(doc)?;
+            let mut record: Vec<u8> = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+            record.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+            record.extend_from_slice(&crc32fast::hash(&payload).to_le_bytes());
+            record.extend_from_slice(&payload);
+
+            let seq: u64 = self.writable_segment()?;
+
+            /* segment 가 바뀌면 이전 segment 를 디스크에 내리고 새 segment 를 연다. */
+            if segment_file.as_ref().map(|(open_seq, _)| *open_seq) != Some(seq) {
+                if let Some((_, prev_file)) = segment_file.take() {
+                    prev_file.sync_data()?;
+                }
+
+                let file: File = fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(self.segment_path(seq))?;
+                segment_file = Some((seq, file));
+            }
+
+            if let Some((_, file)) = segment_file.as_mut() {
+                file.write_all(&record)?;
+            }
+
+            self.total_bytes += record.len() as u64;
+        }
+
+        if let Some((_, file)) = segment_file {
+            file.sync_data()?;
+        }
+
+        self.enforce_size_cap()?;
+
+        Ok(())
+    }
+
+    #[doc = "새 record 를 쓸 segment 번호를 반환해주는 함수 - 마지막 segment 가 segment_bytes 를 넘었다면 새로 만든다."]
+    fn writable_segment(&mut self) -> Result<u64, anyhow::Error> {
+        let last_seq: Option<u64> = self.segments.back().copied();
+
+        if let Some(last_seq) = last_seq {
+            let segment_len: u64 = fs::metadata(self.segment_path(last_seq))
+                .map(|m| m.len())
+                .unwrap_or(0);
+
+            if segment_len < *self.spool_config.segment_bytes() {
+                return Ok(last_seq);
+            }
+        }
+
+        let next_seq: u64 = last_seq.map(|seq| seq + 1).unwrap_or(0);
+        self.segments.push_back(next_seq);
+
+        Ok(next_seq)
+    }
+
+    #[doc = "spool 전체 크기가 상한을 넘으면 가장 오래된 segment 부터 버리는 함수"]
+    fn enforce_size_cap(&mut self) -> Result<(), anyhow::Error> {
+        while self.total_bytes > *self.spool_config.max_bytes() && self.segments.len() > 1 {
+            if let Some(oldest_seq) = self.segments.pop_front() {
+                let path: std::path::PathBuf = self.delete_segment(oldest_seq)?;
+                self.replayed_offset = self.segments.front().map(|seq| self.load_cursor(*seq)).unwrap_or(0);
+                self.peeked_offset = self.replayed_offset;
+
+                warn!(
+                    "[WARN][DiskSpoolRepository->enforce_size_cap] Spool exceeded {} bytes, dropped oldest segment: {:?}",
+                    self.spool_config.max_bytes(),
+                    path
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    #[doc = "가장 오래된 segment 에서 아직 재전송하지 않은 record 를 최대 max_docs 개 꺼내주는 함수"]
+    /// checksum 까지 온전한 record 가 끝나는 위치(valid_len)까지만 읽고, 그 위치까지 모두 재전송한 segment 만 삭제한다.
+    /// 역직렬화에 실패한 record 는 원문을 함께 반환해서 dead-letter 로 보내고 건너뛴다.
+    /// segment 파일이 사라졌다면 해당 segment 를 목록에서 제거한다.
+    ///
+    /// # Arguments
+    /// * `max_docs` - 한 번에 꺼낼 최대 record 수
+    ///
+    /// # Returns
+    /// * Result<SpoolBatch, anyhow::Error> - 비어있다면 가장 오래된 segment 의 재전송이 끝난 것
+    pub fn peek_oldest(&mut self, max_docs: usize) -> Result<SpoolBatch, anyhow::Error> {
+        let mut batch: SpoolBatch = SpoolBatch::default();
+
+        let oldest_seq: u64 = match self.segments.front() {
+            Some(seq) => *seq,
+            None => return Ok(batch),
+        };
+
+        let mut segment_bytes: Vec<u8> = Vec::new();
+        match File::open(self.segment_path(oldest_seq)) {
+            Ok(mut segment_file) => {
+                segment_file.read_to_end(&mut segment_bytes)?;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                warn!(
+                    "[WARN][DiskSpoolRepository->peek_oldest] Segment file {:?} is missing, removing it from spool",
+                    self.segment_path(oldest_seq)
+                );
+                self.segments.pop_front();
+                let _ = fs::remove_file(self.cursor_path(oldest_seq));
+                self.replayed_offset = self.segments.front().map(|seq| self.load_cursor(*seq)).unwrap_or(0);
+                self.peeked_offset = self.replayed_offset;
+                return Ok(batch);
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        let start: usize = (self.replayed_offset as usize).min(segment_bytes.len());
+        let valid_len: usize = start + Self::valid_len(&segment_bytes[start..]);
+        let mut cursor: usize = start;
+
+        while cursor < valid_len && batch.len() < max_docs {
+            let payload_len: usize =
+                u32::from_le_bytes(segment_bytes[cursor..cursor + 4].try_into()?) as usize;
+            let payload_start: usize = cursor + RECORD_HEADER_LEN;
+            let payload_end: usize = payload_start + payload_len;
+            let payload: &[u8] = &segment_bytes[payload_start..payload_end];
+
+            match serde_json::from_slice::<EsDocument>(payload) {
+                Ok(doc) => {
+                    batch.docs.push(doc);
+                    batch.doc_offsets.push(cursor as u64);
+                }
+                Err(e) => {
+                    error!(
+                        "[ERROR][DiskSpoolRepository->peek_oldest] Undecodable record in segment {} at offset {}: {:?}",
+                        oldest_seq, cursor, e
+                    );
+                    batch.undecodable.push(UndecodableRecord {
+                        offset: cursor as u64,
+                        payload: String::from_utf8_lossy(payload).into_owned(),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+
+            cursor = payload_end;
+        }
+
+        self.peeked_offset = cursor as u64;
+
+        /* valid_len 까지 모두 재전송한 segment 만 삭제한다. */
+        if cursor == start && cursor >= valid_len {
+            if valid_len < segment_bytes.len() {
+                error!(
+                    "[ERROR][DiskSpoolRepository->peek_oldest] Discarding {} bytes of corrupted records at offset {} of segment {}",
+                    segment_bytes.len() - valid_len,
+                    valid_len,
+                    oldest_seq
+                );
+            }
+            self.remove_oldest()?;
+        }
+
+        Ok(batch)
+    }
+
+    #[doc = "peek_oldest 로 꺼낸 문서(건너뛴 record 포함)가 전송 완료되었음을 디스크에 기록해주는 함수"]
+    pub fn commit_replayed(&mut self) -> Result<(), anyhow::Error> {
+        let Some(oldest_seq) = self.segments.front().copied() else {
+            return Ok(());
+        };
+
+        self.replayed_offset = self.peeked_offset;
+        self.save_cursor(oldest_seq, self.replayed_offset)
+    }
+
+    #[doc = "peek_oldest 로 꺼낸 record 중 offset 앞쪽까지만 전송 완료로 기록해주는 함수"]
+    /// offset 부터의 record 는 다음 재전송 때 다시 읽는다.
+    ///
+    /// # Arguments
+    /// * `offset` - 다시 보내야 하는 첫 record 의 시작 위치
+    pub fn commit_replayed_until(&mut self, offset: u64) -> Result<(), anyhow::Error> {
+        let Some(oldest_seq) = self.segments.front().copied() else {
+            return Ok(());
+        };
+
+        self.replayed_offset = offset.clamp(self.replayed_offset, self.peeked_offset);
+        self.peeked_offset = self.replayed_offset;
+        self.save_cursor(oldest_seq, self.replayed_offset)
+    }
+
+    #[doc = "재전송이 끝난 가장 오래된 segment 를 삭제하는 함수"]
+    fn remove_oldest(&mut self) -> Result<(), anyhow::Error> {
+        if let Some(oldest_seq) = self.segments.pop_front() {
+            self.delete_segment(oldest_seq)?;
+        }
+
+        self.replayed_offset = self.segments.front().map(|seq| self.load_cursor(*seq)).unwrap_or(0);
+        self.peeked_offset = self.replayed_offset;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[doc = "테스트마다 비어있는 spool 디렉토리를 만들어주는 함수"]
+    fn temp_spool_config(name: &str, segment_bytes: u64) -> SpoolConfig {
+        let path: std::path::PathBuf = std::env::temp_dir().join(format!(
+            "os_metric_beats-spool-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+
+        SpoolConfig {
+            path: path.to_string_lossy().into_owned(),
+            segment_bytes,
+            ..SpoolConfig::default()
+        }
+    }
+
+    fn doc(n: u64) -> EsDocument {
+        EsDocument::new(String::from("metric"), Default::default(), json!({ "n": n }))
+    }
+
+    fn doc_numbers(batch: &SpoolBatch) -> Vec<u64> {
+        batch.docs.iter().map(|doc| doc.source["n"].as_u64().unwrap()).collect()
+    }
+
+    #[test]
+    fn torn_tail_is_truncated_on_reopen() {
+        let spool_config: SpoolConfig = temp_spool_config("torn", 1024 * 1024);
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        spool.append(&[doc(1), doc(2)]).unwrap();
+        let segment_path: std::path::PathBuf = spool.segment_path(0);
+        drop(spool);
+
+        /* 100 byte 라고 기록된 record 의 payload 가 일부만 쓰인 상태 */
+        let mut segment_file: File = fs::OpenOptions::new().append(true).open(&segment_path).unwrap();
+        segment_file.write_all(&100u32.to_le_bytes()).unwrap();
+        segment_file.write_all(&0u32.to_le_bytes()).unwrap();
+        segment_file.write_all(b"{\"in").unwrap();
+        drop(segment_file);
+
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        spool.append(&[doc(3)]).unwrap();
+
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        assert_eq!(doc_numbers(&batch), vec![1, 2, 3]);
+        assert!(batch.undecodable.is_empty());
+
+        fs::remove_dir_all(spool_config.path()).unwrap();
+    }
+
+    #[test]
+    fn crc_mismatch_discards_rest_of_segment() {
+        /* segment_bytes 가 작아서 record 마다 segment 가 새로 만들어진다. */
+        let spool_config: SpoolConfig = temp_spool_config("crc", 1);
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        spool.append(&[doc(1)]).unwrap();
+        spool.append(&[doc(2)]).unwrap();
+
+        let segment_path: std::path::PathBuf = spool.segment_path(0);
+        let mut segment_bytes: Vec<u8> = fs::read(&segment_path).unwrap();
+        let last: usize = segment_bytes.len() - 1;
+        segment_bytes[last] ^= 0xff;
+        fs::write(&segment_path, &segment_bytes).unwrap();
+
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        assert!(batch.is_empty());
+        assert!(!segment_path.exists());
+
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        assert_eq!(doc_numbers(&batch), vec![2]);
+
+        fs::remove_dir_all(spool_config.path()).unwrap();
+    }
+
+    #[test]
+    fn undecodable_record_is_returned_with_payload() {
+        let spool_config: SpoolConfig = temp_spool_config("undecodable", 1024 * 1024);
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        spool.append(&[doc(1)]).unwrap();
+
+        /* checksum 은 맞지만 EsDocument 가 아닌 record */
+        let payload: &[u8] = b"not a document";
+        let mut segment_file: File = fs::OpenOptions::new()
+            .append(true)
+            .open(spool.segment_path(0))
+            .unwrap();
+        segment_file.write_all(&(payload.len() as u32).to_le_bytes()).unwrap();
+        segment_file.write_all(&crc32fast::hash(payload).to_le_bytes()).unwrap();
+        segment_file.write_all(payload).unwrap();
+        drop(segment_file);
+
+        spool.append(&[doc(2)]).unwrap();
+
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        assert_eq!(doc_numbers(&batch), vec![1, 2]);
+        assert_eq!(batch.undecodable.len(), 1);
+        assert_eq!(batch.undecodable[0].payload, "not a document");
+
+        fs::remove_dir_all(spool_config.path()).unwrap();
+    }
+
+    #[test]
+    fn replay_cursor_survives_reopen() {
+        let spool_config: SpoolConfig = temp_spool_config("cursor", 1024 * 1024);
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        spool.append(&[doc(1), doc(2), doc(3)]).unwrap();
+
+        /* 두 번째 문서부터 다시 보내야 하는 경우 */
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        spool.commit_replayed_until(batch.doc_offsets[1]).unwrap();
+        drop(spool);
+
+        let mut spool: DiskSpoolRepository = DiskSpoolRepository::new(spool_config.clone()).unwrap();
+        let batch: SpoolBatch = spool.peek_oldest(10).unwrap();
+        assert_eq!(doc_numbers(&batch), vec![2, 3]);
+
+        spool.commit_replayed().unwrap();
+        assert!(spool.peek_oldest(10).unwrap().is_empty());
+        assert!(spool.is_empty());
+
+        fs::remove_dir_all(spool_config.path()).unwrap();
+    }
+}
@@ -0,0 +1,204 @@
+use crate::common::*;
+
+use crate::model::elastic::{es_node_stats::*, node_pool_config::*};
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Mutex,
+};
+
+#[doc = "응답 시간 이동평균(EWMA)에서 최근 값의 가중치"]
+const LATENCY_EWMA_ALPHA: f64 = 0.2;
+
+#[derive(Debug, Clone)]
+pub(crate) struct EsClient {
+    pub(crate) url: Url,
+    pub(crate) es_conn: Elasticsearch,
+    /* _bulk 전용 - connect timeout, keep-alive, idle connection 설정을 적용한 client */
+    pub(crate) bulk_client: reqwest::Client,
+    /* ca_fingerprint 사용 시 - 지문을 확인한 뒤에 만드는 관리 API 용 transport (es_conn 대신 사용) */
+    pub(crate) pinned_conn: Option<Arc<tokio::sync::OnceCell<Elasticsearch>>>,
+}
+
+#[derive(Debug)]
+struct PooledNode {
+    client: EsClient,
+    /* 설정 파일에 지정된 노드는 sniff 결과에 없어도 제거하지 않는다. */
+    seed: bool,
+    consecutive_failures: u32,
+    dead_until: Option<Instant>,
+    request_count: u64,
+    failure_count: u64,
+    latency_avg_ms: f64,
+    latency_last_ms: f64,
+}
+
+impl PooledNode {
+    fn new(client: EsClient, seed: bool) -> Self {
+        PooledNode {
+            client,
+            seed,
+            consecutive_failures: 0,
+            dead_until: None,
+            request_count: 0,
+            failure_count: 0,
+            latency_avg_ms: 0.0,
+            latency_last_ms: 0.0,
+        }
+    }
+}
+
+#[doc = "노드 별 상태를 기억해서 죽은 노드는 backoff 동안 건너뛰는 connection pool"]
+#[derive(Debug)]
+pub(crate) struct EsNodePool {
+    nodes: Mutex<Vec<PooledNode>>,
+    next: AtomicUsize,
+    config: NodePoolConfig,
+}
+
+impl EsNodePool {
+    pub(crate) fn new(seed_clients: Vec<EsClient>, config: NodePoolConfig) -> Self {
+        EsNodePool {
+            nodes: Mutex::new(
+                seed_clients
+                    .into_iter()
+                    .map(|client| PooledNode::new(client, true))
+                    .collect(),
+            ),
+            next: AtomicUsize::new(0),
+            config,
+        }
+    }
+
+    fn lock_nodes(&self) -> std::sync::MutexGuard<'_, Vec<PooledNode>> {
+        /* 노드 상태는 통계 용도이므로 poison 되더라도 계속 사용한다. */
+        self.nodes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[doc = "이번 요청에서 시도할 노드 순서를 정해주는 함수"]
+    /// 1) backoff 가 끝난 죽은 노드 (부활 여부 확인을 위해 한 번 먼저 시도)
+    /// 2) 살아있는 노드 (round-robin)
+    /// 3) 모두 죽어있다면 가장 먼저 backoff 가 끝나는 노드부터
+    pub(crate) fn candidates(&self) -> Vec<EsClient> {
+        let nodes = self.lock_nodes();
+        let now: Instant = Instant::now();
+
+        let resurrectable: Vec<EsClient> = nodes
+            .iter()
+            .filter(|node| node.dead_until.is_some_and(|dead_until| dead_until <= now))
+            .map(|node| node.client.clone())
+            .collect();
+
+        let mut alive: Vec<EsClient> = nodes
+            .iter()
+            .filter(|node| node.dead_until.is_none())
+            .map(|node| node.client.clone())
+            .collect();
+
+        if !alive.is_empty() {
+            let offset: usize = self.next.fetch_add(1, Ordering::Relaxed) % alive.len();
+            alive.rotate_left(offset);
+        }
+
+        let mut candidates: Vec<EsClient> = resurrectable.into_iter().chain(alive).collect();
+
+        if candidates.is_empty() {
+            let mut dead: Vec<&PooledNode> = nodes.iter().collect();
+            dead.sort_by_key(|node| node.dead_until);
+            candidates = dead.into_iter().map(|node| node.client.clone()).collect();
+        }
+
+        candidates
+    }
+
+    pub(crate) fn mark_success(&self, url: &Url, latency: Duration) {
+        let mut nodes = self.lock_nodes();
+        let Some(node) = nodes.iter_mut().find(|node| node.client.url == *url) else {
+            return;
+        };
+
+        if node.dead_until.is_some() {
+            info!("[EsNodePool->mark_success] Elasticsearch node {} is alive again", url);
+        }
+
+        let latency_ms: f64 = latency.as_secs_f64() * 1000.0;
+        node.latency_avg_ms = if node.request_count == 0 {
+            latency_ms
+        } else {
+            LATENCY_EWMA_ALPHA * latency_ms + (1.0 - LATENCY_EWMA_ALPHA) * node.latency_avg_ms
+        };
+        node.latency_last_ms = latency_ms;
+        node.request_count += 1;
+        node.consecutive_failures = 0;
+        node.dead_until = None;
+    }
+
+    #[doc = "노드를 죽은 상태로 표시해주는 함수 - 연속 실패 횟수에 따라 backoff 가 두 배씩 늘어난다."]
+    pub(crate) fn mark_failure(&self, url: &Url) {
+        let mut nodes = self.lock_nodes();
+        let Some(node) = nodes.iter_mut().find(|node| node.client.url == *url) else {
+            return;
+        };
+
+        node.request_count += 1;
+        node.failure_count += 1;
+        node.consecutive_failures += 1;
+
+        let backoff_secs: u64 = self
+            .config
+            .dead_backoff_base_secs()
+            .saturating_mul(1u64 << (node.consecutive_failures - 1).min(16))
+            .min(*self.config.dead_backoff_max_secs());
+        node.dead_until = Some(Instant::now() + Duration::from_secs(backoff_secs));
+
+        warn!(
+            "[WARN][EsNodePool->mark_failure] Elasticsearch node {} marked dead for {}s (consecutive failures: {})",
+            url, backoff_secs, node.consecutive_failures
+        );
+    }
+
+    pub(crate) fn node_urls(&self) -> Vec<Url> {
+        self.lock_nodes()
+            .iter()
+            .map(|node| node.client.url.clone())
+            .collect()
+    }
+
+    #[doc = "sniff 로 찾은 노드 목록을 반영해주는 함수 - 새 노드는 추가하고 사라진 (seed 가 아닌) 노드는 제거한다."]
+    /// `sniffed_urls` 에는 publish_address 로 이미 pool 에 있는 것으로 확인된 노드의 기존 URL 과 새 노드의 URL 이 들어있다.
+    pub(crate) fn sync_sniffed(&self, sniffed_urls: &[Url], new_clients: Vec<EsClient>) {
+        let mut nodes = self.lock_nodes();
+
+        nodes.retain(|node| {
+            let keep: bool = node.seed || sniffed_urls.contains(&node.client.url);
+            if !keep {
+                info!("[EsNodePool->sync_sniffed] Elasticsearch node {} removed from pool", node.client.url);
+            }
+            keep
+        });
+
+        for client in new_clients {
+            if nodes.iter().all(|node| node.client.url != client.url) {
+                info!("[EsNodePool->sync_sniffed] Elasticsearch node {} added to pool", client.url);
+                nodes.push(PooledNode::new(client, false));
+            }
+        }
+    }
+
+    pub(crate) fn node_stats(&self, output_name: &str) -> Vec<EsNodeStats> {
+        self.lock_nodes()
+            .iter()
+            .map(|node| {
+                EsNodeStats::new(
+                    output_name.to_string(),
+                    node.client.url.to_string(),
+                    node.dead_until.is_none(),
+                    node.request_count,
+                    node.failure_count,
+                    node.latency_avg_ms,
+                    node.latency_last_ms,
+                )
+            })
+            .collect()
+    }
+}
@@ -1,77 +1,144 @@
 use crate::common::*;
 
-use crate::utils_module::io_utils::*;
+use crate::common_enums::{doc_type::*, es_flavour::*, tls_verification_mode::*};
 
+use crate::utils_module::compress_utils::*;
+use crate::utils_module::tls_utils::*;
+
+use crate::repository::es_node_pool::*;
+
+use crate::model::elastic::{
+    auth_config::*, bulk_config::*, data_stream_config::*, dead_letter_config::*, document_route_config::*,
+    es_node_stats::*, es_response_error::*, es_transport_stats::*, ilm_config::*, node_pool_config::*,
+    retention_config::*, retry_config::*, routing_rule::*, spool_config::*, template_config::*,
+    tls_config::*, transport_config::*,
+};
 use crate::model::elastic_info_config::*;
+use crate::model::elastic_outputs_config::*;
 
 use crate::env_configuration::env_config::*;
 
-#[doc = "Elasticsearch connection 을 싱글톤으로 관리하기 위한 전역 변수."]
-static ELASTICSEARCH_CLIENT: once_lazy<Arc<EsRepositoryPub>> =
+use std::sync::Mutex;
+
+#[doc = "출력(클러스터) 별 Elasticsearch connection 을 싱글톤으로 관리하기 위한 전역 변수."]
+static ELASTICSEARCH_CLIENTS: once_lazy<Vec<Arc<EsRepositoryPub>>> =
     once_lazy::new(initialize_elastic_clients);
 
 #[doc = "Function to initialize Elasticsearch connection instances"]
-pub fn initialize_elastic_clients() -> Arc<EsRepositoryPub> {
-    let cluster_config: ElasticInfoConfig =
-        match read_toml_from_file::<ElasticInfoConfig>(&ELASTIC_SERVER_INFO) {
-            Ok(cluster_config) => cluster_config,
+pub fn initialize_elastic_clients() -> Vec<Arc<EsRepositoryPub>> {
+    let outputs: BTreeMap<String, ElasticInfoConfig> =
+        match ElasticOutputsConfig::load(&ELASTIC_SERVER_INFO) {
+            Ok(outputs) => outputs,
             Err(e) => {
                 error!("{:?}", e);
                 panic!("{:?}", e)
             }
         };
-    
-    let es_hosts: Vec<String> = cluster_config.hosts().clone();
-    let es_id: String = cluster_config.es_id().clone().unwrap_or(String::from(""));
-    let es_pw: String = cluster_config.es_pw().clone().unwrap_or(String::from(""));
-    let index_pattern: String = cluster_config.index_pattern().clone();
-
-    let es_helper: EsRepositoryPub =
-        match EsRepositoryPub::new(es_hosts, &es_id, &es_pw, &index_pattern) {
-            Ok(es_helper) => es_helper,
-            Err(err) => {
-                error!("{:?}", err);
-                panic!("{:?}", err)
-            }
-        };
 
-    Arc::new(es_helper)
+    outputs
+        .iter()
+        .map(|(output_name, cluster_config)| {
+            match EsRepositoryPub::new(output_name, cluster_config) {
+                Ok(es_helper) => Arc::new(es_helper),
+                Err(err) => {
+                    error!("[ERROR][initialize_elastic_clients] output '{}': {:?}", output_name, err);
+                    panic!("[ERROR][initialize_elastic_clients] output '{}': {:?}", output_name, err)
+                }
+            }
+        })
+        .collect()
 }
 
-#[doc = "엘라스틱 서치 커넥션을 가져와주는 get() 함수"]
-pub fn get_elastic_conn() -> Arc<EsRepositoryPub> {
-    let es_conn = &ELASTICSEARCH_CLIENT;
-    Arc::clone(es_conn)
+#[doc = "설정된 모든 출력의 엘라스틱 서치 커넥션을 가져와주는 get() 함수"]
+pub fn get_elastic_conns() -> Vec<Arc<EsRepositoryPub>> {
+    ELASTICSEARCH_CLIENTS.iter().map(Arc::clone).collect()
 }
 
 #[async_trait]
 pub trait EsRepository {
-    async fn post_doc(&self, index_name: &str, document: Value) -> Result<(), anyhow::Error>;
+    async fn post_bulk(&self, bulk_body: String) -> Result<Value, anyhow::Error>;
+    async fn get_index_template(&self, template_name: &str) -> Result<Option<Value>, anyhow::Error>;
+    async fn put_index_template(&self, template_name: &str, template_body: &Value) -> Result<(), anyhow::Error>;
+    async fn get_mappings(&self, index_pattern: &str) -> Result<Value, anyhow::Error>;
+    async fn get_ilm_policy(&self, policy_name: &str) -> Result<Option<Value>, anyhow::Error>;
+    async fn put_ilm_policy(&self, policy_name: &str, policy_body: &Value) -> Result<(), anyhow::Error>;
+    async fn get_ism_policy(&self, policy_name: &str) -> Result<Option<Value>, anyhow::Error>;
+    async fn put_ism_policy(
+        &self,
+        policy_name: &str,
+        policy_body: &Value,
+        if_match: Option<(i64, i64)>,
+    ) -> Result<(), anyhow::Error>;
+    async fn get_server_info(&self) -> Result<Value, anyhow::Error>;
+    async fn get_index_names(&self, index_pattern: &str) -> Result<Vec<String>, anyhow::Error>;
+    async fn delete_index(&self, index_name: &str) -> Result<(), anyhow::Error>;
+    async fn create_doc(&self, index_name: &str, doc_id: &str, document: &Value) -> Result<Option<Value>, anyhow::Error>;
+    async fn get_doc(&self, index_name: &str, doc_id: &str) -> Result<Option<Value>, anyhow::Error>;
+    async fn put_doc_if_match(
+        &self,
+        index_name: &str,
+        doc_id: &str,
+        document: &Value,
+        seq_no: i64,
+        primary_term: i64,
+    ) -> Result<Option<Value>, anyhow::Error>;
+    async fn delete_doc_if_match(
+        &self,
+        index_name: &str,
+        doc_id: &str,
+        seq_no: i64,
+        primary_term: i64,
+    ) -> Result<bool, anyhow::Error>;
+    async fn sniff_nodes(&self) -> Result<(), anyhow::Error>;
 }
 
 #[derive(Debug, Clone, Getters)]
 #[getset(get = "pub")]
 pub struct EsRepositoryPub {
-    es_clients: Vec<EsClient>,
+    output_name: String,
+    flavour: EsFlavour,
+    #[getset(skip)]
+    detected_flavour: Arc<OnceCell<EsFlavour>>,
+    #[getset(skip)]
+    node_pool: Arc<EsNodePool>,
+    #[getset(skip)]
+    auth_header: Option<HeaderValue>,
+    #[getset(skip)]
+    tls_config: Option<TlsConfig>,
+    #[getset(skip)]
+    transport_stats: Arc<Mutex<EsTransportStats>>,
+    transport_config: TransportConfig,
     index_pattern: String,
-}
-
-#[derive(Debug, Clone)]
-pub(crate) struct EsClient {
-    es_conn: Elasticsearch,
+    slowlog_index_pattern: String,
+    document_routes: HashMap<DocType, DocumentRouteConfig>,
+    routing_rules: Vec<RoutingRule>,
+    bulk_config: BulkConfig,
+    retry_config: RetryConfig,
+    spool_config: Option<SpoolConfig>,
+    dead_letter_config: Option<DeadLetterConfig>,
+    template_config: TemplateConfig,
+    data_stream_config: Option<DataStreamConfig>,
+    ilm_config: Option<IlmConfig>,
+    retention_config: Option<RetentionConfig>,
+    node_pool_config: NodePoolConfig,
 }
 
 impl EsRepositoryPub {
-    pub fn new(
-        hosts: Vec<String>,
-        es_id: &str,
-        es_pw: &str,
-        index_pattern: &str,
-    ) -> Result<Self, anyhow::Error> {
+    pub fn new(output_name: &str, cluster_config: &ElasticInfoConfig) -> Result<Self, anyhow::Error> {
+        let hosts: &Vec<String> = cluster_config.hosts();
+        let tls_config: &Option<TlsConfig> = cluster_config.tls();
+        let transport_config: &TransportConfig = cluster_config.transport();
+
         if hosts.is_empty() {
             return Err(anyhow::anyhow!("No Elasticsearch hosts provided"));
         }
 
+        /* 자격증명은 URL 이 아닌 Authorization 헤더로 전송 - 로그에 URL 이 남아도 노출되지 않음 */
+        let auth_header: Option<HeaderValue> = match cluster_config.resolve_auth() {
+            Some(auth) => Some(auth.to_header_value()?),
+            None => None,
+        };
+
         let mut es_clients: Vec<EsClient> = Vec::new();
 
         for url in hosts {
@@ -79,93 +146,516 @@ impl EsRepositoryPub {
                 return Err(anyhow::anyhow!("Empty host found in hosts configuration"));
             }
 
-            /* Elasticsearch 에 비밀번호를 설정해둔 경우와 그렇지 않은 경우를 고려함 */
-            let parse_url: String = if !es_id.is_empty() && !es_pw.is_empty() {
-                let encoded_id = urlencoding::encode(es_id);
-                let encoded_pw = urlencoding::encode(es_pw);
-                format!("http://{}:{}@{}", encoded_id, encoded_pw, url.trim())
-            } else {
-                format!("http://{}", url.trim())
-            };
-
-            let es_url: Url = Url::parse(&parse_url)
-                .map_err(|e| anyhow::anyhow!("[ERROR][EsRepositoryPub->new] Failed to parse URL '{}': {}", parse_url, e))?;
-            let conn_pool: SingleNodeConnectionPool = SingleNodeConnectionPool::new(es_url);
-            let transport: elasticsearch::http::transport::Transport =
-                TransportBuilder::new(conn_pool)
-                    .timeout(Duration::new(5, 0))
-                    .build()?;
-
-            let elastic_conn: Elasticsearch = Elasticsearch::new(transport);
-            let es_client: EsClient = EsClient { es_conn: elastic_conn };
-            es_clients.push(es_client);
+            es_clients.push(create_es_client(
+                parse_host_url(url, tls_config.as_ref())?,
+                auth_header.as_ref(),
+                tls_config.as_ref(),
+                transport_config,
+            )?);
         }
 
+        let index_pattern: String = cluster_config.index_pattern().clone();
+        /* slow log 집계 문서는 별도 인덱스를 지정하지 않으면 시스템 지표와 같은 인덱스에 색인 */
+        let slowlog_index_pattern: String = cluster_config
+            .slowlog_index_pattern()
+            .clone()
+            .unwrap_or(index_pattern.clone());
+
         Ok(EsRepositoryPub {
-            es_clients,
-            index_pattern: index_pattern.to_string(),
+            output_name: output_name.to_string(),
+            flavour: *cluster_config.flavour(),
+            detected_flavour: Arc::new(OnceCell::new()),
+            node_pool: Arc::new(EsNodePool::new(es_clients, cluster_config.node_pool().clone())),
+            auth_header,
+            tls_config: tls_config.clone(),
+            transport_stats: Arc::new(Mutex::new(EsTransportStats::default())),
+            transport_config: transport_config.clone(),
+            index_pattern,
+            slowlog_index_pattern,
+            document_routes: cluster_config.documents().clone(),
+            routing_rules: cluster_config.routing_rules().clone(),
+            bulk_config: cluster_config.bulk().clone(),
+            retry_config: cluster_config.retry().clone(),
+            spool_config: cluster_config.spool().clone(),
+            dead_letter_config: cluster_config.dead_letter().clone(),
+            template_config: cluster_config.template().clone(),
+            data_stream_config: cluster_config.data_stream().clone(),
+            ilm_config: cluster_config.ilm().clone(),
+            retention_config: cluster_config.retention().clone(),
+            node_pool_config: cluster_config.node_pool().clone(),
         })
     }
 
+    #[doc = "전송 대상 노드 별 요청 수/실패 수/응답 시간 - 에이전트 자체 모니터링 용도"]
+    pub fn node_stats(&self) -> Vec<EsNodeStats> {
+        self.node_pool.node_stats(&self.output_name)
+    }
+
+    #[doc = "_bulk body 의 압축 전/후 누적 크기 - 압축을 사용하지 않으면 두 값이 같다."]
+    pub fn transport_stats(&self) -> EsTransportStats {
+        let mut stats: EsTransportStats = self
+            .transport_stats
+            .lock()
+            .map(|stats| stats.clone())
+            .unwrap_or_default();
+        stats.output = self.output_name.clone();
+        stats
+    }
+
+    #[doc = "문서 종류별 기본 색인 대상 - data stream 모드면 data stream 이름, 아니면 index_pattern + 날짜"]
+    /// # Arguments
+    /// * `doc_type` - 문서 종류
+    /// * `index_date` - 날짜별 인덱스 접미사 (YYYYMMDD)
+    ///
+    /// # Returns
+    /// * String
+    pub fn default_index_name(&self, doc_type: DocType, index_date: &str) -> String {
+        /* data stream 모드에서는 rollover/retention 을 클러스터가 관리하므로 날짜별 인덱스를 만들지 않는다. */
+        match (&self.data_stream_config, doc_type) {
+            (Some(data_stream_config), DocType::Metric) => data_stream_config.metric_data_stream(),
+            (Some(data_stream_config), DocType::Slowlog) => data_stream_config.slowlog_data_stream(),
+            (None, DocType::Metric) => format!("{}{}", self.index_pattern, index_date),
+            (None, DocType::Slowlog) => format!("{}{}", self.slowlog_index_pattern, index_date),
+        }
+    }
+
+    #[doc = "출력 대상 클러스터의 배포판을 확인해주는 함수 - auto 인 경우 한 번만 조회하고 결과를 재사용한다."]
+    /// # Returns
+    /// * Result<EsFlavour, anyhow::Error> - Elasticsearch / OpenSearch 중 하나
+    pub async fn resolve_flavour(&self) -> Result<EsFlavour, anyhow::Error> {
+        if self.flavour != EsFlavour::Auto {
+            return Ok(self.flavour);
+        }
+
+        if let Some(flavour) = self.detected_flavour.get() {
+            return Ok(*flavour);
+        }
+
+        let server_info: Value = self.get_server_info().await?;
+        let flavour: EsFlavour = EsFlavour::from_server_info(&server_info);
+
+        info!(
+            "[EsRepositoryPub->resolve_flavour] [{}] Detected {:?} {}",
+            self.output_name,
+            flavour,
+            server_info["version"]["number"].as_str().unwrap_or("unknown")
+        );
+
+        Ok(*self.detected_flavour.get_or_init(|| flavour))
+    }
+
+    fn record_bulk_bytes(&self, uncompressed_bytes: usize, compressed_bytes: usize) {
+        if let Ok(mut stats) = self.transport_stats.lock() {
+            stats.bulk_request_count += 1;
+            stats.uncompressed_bytes += uncompressed_bytes as u64;
+            stats.compressed_bytes += compressed_bytes as u64;
+        }
+    }
+
+    #[doc = "ca_fingerprint 를 사용하는 노드라면 지문을 확인한 관리 API 용 transport 로 바꿔주는 함수"]
+    /// 지문 확인은 노드마다 처음 한 번만 비동기로 수행되며, 실패하면 그 노드만 실패 처리되고 다음 요청에서 다시 시도한다.
+    ///
+    /// # Arguments
+    /// * `es_client` - 요청을 보낼 노드
+    /// * `auth_header` - 지문 확인 후 만드는 transport 에 붙일 Authorization 헤더
+    ///
+    /// # Returns
+    /// * Result<EsClient, anyhow::Error>
+    async fn with_pinned_conn(
+        &self,
+        mut es_client: EsClient,
+        auth_header: Option<&HeaderValue>,
+    ) -> Result<EsClient, anyhow::Error> {
+        let (Some(pinned_conn), Some(tls_config)) = (es_client.pinned_conn.clone(), self.tls_config.as_ref()) else {
+            return Ok(es_client);
+        };
+
+        let es_conn: &Elasticsearch = pinned_conn
+            .get_or_try_init(|| async {
+                let mut trusted_certs: Vec<Vec<u8>> = load_trusted_ca_certs(tls_config)?;
+                let connect_timeout: Duration = Duration::from_secs(*self.transport_config.connect_timeout_secs());
+                trusted_certs
+                    .push(fetch_pinned_certificate(&es_client.url, tls_config, &trusted_certs, connect_timeout).await?);
+
+                build_es_conn(
+                    &es_client.url,
+                    auth_header,
+                    Some(tls_config),
+                    &self.transport_config,
+                    &trusted_certs,
+                )
+            })
+            .await?;
+
+        es_client.es_conn = es_conn.clone();
+        Ok(es_client)
+    }
+
+    #[doc = "이 출력의 인증/TLS/transport 설정으로 로컬 Elasticsearch 노드에 접속하는 클라이언트를 만들어주는 함수"]
+    /// # Arguments
+    /// * `local_es_url` - 로컬 노드 주소 (scheme 이 없으면 TLS 설정 여부에 따라 https/http)
+    /// * `auth` - 출력의 인증 대신 사용할 인증 (system config 의 local_es_id / local_es_pw)
+    ///
+    /// # Returns
+    /// * Result<EsClient, anyhow::Error>
+    pub(crate) fn create_local_client(
+        &self,
+        local_es_url: &str,
+        auth: Option<&AuthConfig>,
+    ) -> Result<EsClient, anyhow::Error> {
+        let auth_header: Option<HeaderValue> = match auth {
+            Some(auth) => Some(auth.to_header_value()?),
+            None => self.auth_header.clone(),
+        };
+
+        create_es_client(
+            parse_host_url(local_es_url, self.tls_config.as_ref())?,
+            auth_header.as_ref(),
+            self.tls_config.as_ref(),
+            &self.transport_config,
+        )
+    }
+
+    #[doc = "create_local_client 로 만든 클라이언트의 관리 API 용 transport 를 반환해주는 함수 - ca_fingerprint 를 쓰면 처음 한 번 지문을 확인한다."]
+    /// # Arguments
+    /// * `es_client` - 로컬 노드 클라이언트
+    /// * `auth` - create_local_client 에 넘긴 인증
+    ///
+    /// # Returns
+    /// * Result<Elasticsearch, anyhow::Error>
+    pub(crate) async fn local_conn(
+        &self,
+        es_client: &EsClient,
+        auth: Option<&AuthConfig>,
+    ) -> Result<Elasticsearch, anyhow::Error> {
+        let auth_header: Option<HeaderValue> = match auth {
+            Some(auth) => Some(auth.to_header_value()?),
+            None => self.auth_header.clone(),
+        };
+
+        Ok(self.with_pinned_conn(es_client.clone(), auth_header.as_ref()).await?.es_conn)
+    }
+
     #[doc = "Common logic: common node failure handling and node selection"]
+    /// 5xx/429 응답도 노드 실패로 표시하고 다음 노드로 넘어가며, 모든 노드가 그렇게 응답하면
+    /// 마지막 응답을 그대로 돌려줘서 호출자가 상태코드(Retry-After 포함)로 재시도 여부를 판단하게 한다.
+    ///
     /// # Arguments
     /// * `operation` - Elasticsearch 특정 노드의 함수
     ///
     /// # Returns
     /// * Result<T, anyhow::Error>
-    async fn execute_on_any_node<F, Fut>(&self, operation: F) -> Result<Response, anyhow::Error>
+    async fn execute_on_any_node<F, Fut, T>(&self, operation: F) -> Result<T, anyhow::Error>
     where
         F: Fn(EsClient) -> Fut + Send + Sync,
-        Fut: Future<Output = Result<Response, anyhow::Error>> + Send,
+        Fut: Future<Output = Result<T, anyhow::Error>> + Send,
+        T: NodeResponse,
     {
         let mut last_error: Option<anyhow::Error> = None;
-        let mut rng: StdRng = StdRng::from_entropy(); /* 랜덤 시드로 생성 */
+        let mut last_failed_response: Option<T> = None;
 
-        /*  클라이언트 목록을 셔플 -> StdRng를 사용하여 셔플 */
-        let mut shuffled_clients: Vec<EsClient> = self.es_clients.clone();
-        shuffled_clients.shuffle(&mut rng);
+        /* 죽은 노드는 backoff 동안 건너뛰고, 살아있는 노드를 round-robin 으로 시도 */
+        for es_client in self.node_pool.candidates() {
+            let url: Url = es_client.url.clone();
+            let started_at: Instant = Instant::now();
 
-        /* 셔플된 클라이언트들에 대해 순차적으로 operation 수행 */
-        for es_client in shuffled_clients {
-            match operation(es_client).await {
-                Ok(response) => return Ok(response),
+            let result: Result<T, anyhow::Error> = match self.with_pinned_conn(es_client, self.auth_header.as_ref()).await {
+                Ok(es_client) => operation(es_client).await,
+                Err(err) => Err(err),
+            };
+
+            match result {
+                Ok(response) if response.is_node_failure() => {
+                    warn!(
+                        "[WARN][EsRepositoryPub->execute_on_any_node] Elasticsearch node {} responded with status {}",
+                        url,
+                        response.status_u16()
+                    );
+                    self.node_pool.mark_failure(&url);
+                    last_failed_response = Some(response);
+                }
+                Ok(response) => {
+                    self.node_pool.mark_success(&url, started_at.elapsed());
+                    return Ok(response);
+                }
                 Err(err) => {
+                    self.node_pool.mark_failure(&url);
                     last_error = Some(err);
                 }
             }
         }
 
-        /* 모든 노드에서 실패했을 경우 에러 반환 */
-        Err(anyhow::anyhow!(
-            "All Elasticsearch nodes failed. Last error: {:?}",
-            last_error
-        ))
+        if let Some(response) = last_failed_response {
+            return Ok(response);
+        }
+
+        /* 모든 노드에서 실패했을 경우 마지막 에러를 원인으로 보존해서 반환 (RetryClass 분류에 사용) */
+        Err(match last_error {
+            Some(err) => err.context("All Elasticsearch nodes failed"),
+            None => anyhow!("All Elasticsearch nodes failed: no node available"),
+        })
     }
 }
 
 #[async_trait]
 impl EsRepository for EsRepositoryPub {
-    #[doc = "Elasticsearch 에 색인해주는 함수"]
+    #[doc = "_bulk API 로 여러 문서를 한 번에 색인해주는 함수"]
     /// # Arguments
-    /// * `index_name`- 인덱스 이름
-    /// * `document`  - 색인할 문서
+    /// * `bulk_body` - action/source 쌍으로 이루어진 NDJSON body
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - 문서별 처리 결과가 담긴 _bulk 응답 body
+    async fn post_bulk(&self, bulk_body: String) -> Result<Value, anyhow::Error> {
+        let compression: bool = *self.transport_config.compression();
+        let uncompressed_bytes: usize = bulk_body.len();
+
+        /* 노드를 바꿔 재시도하더라도 압축은 한 번만 수행 */
+        let request_body: Vec<u8> = if compression {
+            gzip_compress(bulk_body.as_bytes(), *self.transport_config.compression_level())?
+        } else {
+            bulk_body.into_bytes()
+        };
+        self.record_bulk_bytes(uncompressed_bytes, request_body.len());
+
+        /* 전송량이 가장 많은 요청이므로 연결 설정을 조정한 _bulk 전용 client 로 보낸다. */
+        let response: reqwest::Response = self
+            .execute_on_any_node(|es_client| {
+                let body: Vec<u8> = request_body.clone();
+                async move {
+                    let mut bulk_url: Url = es_client.url.clone();
+                    bulk_url
+                        .path_segments_mut()
+                        .map_err(|_| anyhow!("[ERROR][EsRepositoryPub->post_bulk] Invalid node URL: {}", es_client.url))?
+                        .pop_if_empty()
+                        .push("_bulk");
+
+                    let mut request: reqwest::RequestBuilder = es_client
+                        .bulk_client
+                        .post(bulk_url)
+                        .header(CONTENT_TYPE, "application/x-ndjson");
+                    if compression {
+                        request = request.header(CONTENT_ENCODING, "gzip");
+                    }
+
+                    Ok(request.body(body).send().await?)
+                }
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response.json::<Value>().await?)
+        } else {
+            /* 상태코드와 Retry-After 를 보존해서 호출자가 재시도 여부를 판단할 수 있게 한다. */
+            let response_error: EsResponseError = EsResponseError::from_http_response("post_bulk", &response);
+            let error_body: Option<Value> = response.json::<Value>().await.ok();
+            Err(anyhow::Error::new(response_error.with_error_body(error_body.as_ref())))
+        }
+    }
+
+    #[doc = "composable index template 을 조회해주는 함수"]
+    /// # Arguments
+    /// * `template_name` - 조회할 템플릿 이름
+    ///
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - 템플릿이 없으면 None
+    async fn get_index_template(&self, template_name: &str) -> Result<Option<Value>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .indices()
+                    .get_index_template(IndicesGetIndexTemplateParts::Name(template_name))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().as_u16() == 404 {
+            return Ok(None);
+        }
+
+        if response.status_code().is_success() {
+            let body: Value = response.json::<Value>().await?;
+            Ok(body["index_templates"]
+                .as_array()
+                .and_then(|templates| templates.first())
+                .map(|template| template["index_template"].clone()))
+        } else {
+            Err(anyhow!(
+                "[Elasticsearch Error][get_index_template()] Failed to get template '{}': Status Code: {}",
+                template_name,
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "composable index template 을 생성/갱신해주는 함수"]
+    /// # Arguments
+    /// * `template_name` - 템플릿 이름
+    /// * `template_body` - index_patterns, template, priority 등이 담긴 템플릿 본문
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn put_index_template(&self, template_name: &str, template_body: &Value) -> Result<(), anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .indices()
+                    .put_index_template(IndicesPutIndexTemplateParts::Name(template_name))
+                    .body(template_body.clone())
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let error_body: String = response.text().await.unwrap_or_default();
+            Err(anyhow!(
+                "[Elasticsearch Error][put_index_template()] Failed to put template '{}': Status Code: {}, {}",
+                template_name,
+                status_code,
+                error_body
+            ))
+        }
+    }
+
+    #[doc = "index pattern 에 해당하는 기존 인덱스들의 mapping 을 조회해주는 함수"]
+    /// # Arguments
+    /// * `index_pattern` - 조회할 인덱스 패턴 (ex. os_metric_*)
+    ///
+    /// # Returns
+    /// * Result<Value, anyhow::Error> - 인덱스 이름을 key 로 하는 mapping 목록, 인덱스가 없으면 빈 객체
+    async fn get_mappings(&self, index_pattern: &str) -> Result<Value, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .indices()
+                    .get_mapping(IndicesGetMappingParts::Index(&[index_pattern]))
+                    .allow_no_indices(true)
+                    .ignore_unavailable(true)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(response.json::<Value>().await?)
+        } else {
+            Err(anyhow!(
+                "[Elasticsearch Error][get_mappings()] Failed to get mappings of '{}': Status Code: {}",
+                index_pattern,
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "ILM policy 를 조회해주는 함수"]
+    /// # Arguments
+    /// * `policy_name` - 조회할 policy 이름
+    ///
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - policy 가 없으면 None
+    async fn get_ilm_policy(&self, policy_name: &str) -> Result<Option<Value>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .ilm()
+                    .get_lifecycle(IlmGetLifecycleParts::Policy(policy_name))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().as_u16() == 404 {
+            return Ok(None);
+        }
+
+        if response.status_code().is_success() {
+            let body: Value = response.json::<Value>().await?;
+            Ok(body.get(policy_name).map(|policy| policy["policy"].clone()))
+        } else {
+            Err(anyhow!(
+                "[Elasticsearch Error][get_ilm_policy()] Failed to get ILM policy '{}': Status Code: {}",
+                policy_name,
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "ILM policy 를 생성/갱신해주는 함수"]
+    /// # Arguments
+    /// * `policy_name` - policy 이름
+    /// * `policy_body` - phases 가 담긴 policy 본문
     ///
     /// # Returns
     /// * Result<(), anyhow::Error>
-    async fn post_doc(&self, index_name: &str, document: Value) -> Result<(), anyhow::Error> {
-        /* 클로저 내에서 사용할 복사본을 생성 */
-        let document_clone: Value = document.clone();
+    async fn put_ilm_policy(&self, policy_name: &str, policy_body: &Value) -> Result<(), anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .ilm()
+                    .put_lifecycle(IlmPutLifecycleParts::Policy(policy_name))
+                    .body(policy_body.clone())
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let error_body: String = response.text().await.unwrap_or_default();
+            Err(anyhow!(
+                "[Elasticsearch Error][put_ilm_policy()] Failed to put ILM policy '{}': Status Code: {}, {}",
+                policy_name,
+                status_code,
+                error_body
+            ))
+        }
+    }
+
+    #[doc = "OpenSearch ISM policy 를 조회해주는 함수"]
+    /// # Arguments
+    /// * `policy_name` - 조회할 policy 이름
+    ///
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - policy 가 없으면 None, 있으면 _seq_no/_primary_term 을 포함한 응답 전체
+    async fn get_ism_policy(&self, policy_name: &str) -> Result<Option<Value>, anyhow::Error> {
+        /* elasticsearch crate 에는 ISM API 가 없으므로 transport 로 직접 요청 */
+        let path: String = format!("/_plugins/_ism/policies/{}", policy_name);
 
         let response: Response = self
             .execute_on_any_node(|es_client| {
-                /* 클로저 내부에서 클론한 값 사용 */
-                let value = document_clone.clone();
+                let path: String = path.clone();
                 async move {
-                    let response = es_client
+                    let response: Response = es_client
                         .es_conn
-                        .index(IndexParts::Index(index_name))
-                        .body(value)
-                        .send()
+                        .send(
+                            Method::Get,
+                            &path,
+                            HeaderMap::new(),
+                            Option::<&Value>::None,
+                            Option::<String>::None,
+                            None,
+                        )
                         .await?;
 
                     Ok(response)
@@ -173,14 +663,604 @@ impl EsRepository for EsRepositoryPub {
             })
             .await?;
 
+        if response.status_code().as_u16() == 404 {
+            return Ok(None);
+        }
+
+        if response.status_code().is_success() {
+            Ok(Some(response.json::<Value>().await?))
+        } else {
+            Err(anyhow!(
+                "[Elasticsearch Error][get_ism_policy()] Failed to get ISM policy '{}': Status Code: {}",
+                policy_name,
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "OpenSearch ISM policy 를 생성/갱신해주는 함수"]
+    /// # Arguments
+    /// * `policy_name` - policy 이름
+    /// * `policy_body` - states 가 담긴 policy 본문
+    /// * `if_match` - 갱신 시 기존 policy 의 (_seq_no, _primary_term) - 생성이면 None
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn put_ism_policy(
+        &self,
+        policy_name: &str,
+        policy_body: &Value,
+        if_match: Option<(i64, i64)>,
+    ) -> Result<(), anyhow::Error> {
+        let path: String = format!("/_plugins/_ism/policies/{}", policy_name);
+        /* ISM 은 기존 policy 갱신 시 if_seq_no/if_primary_term 을 요구한다. */
+        let query: Option<Value> =
+            if_match.map(|(seq_no, primary_term)| json!({ "if_seq_no": seq_no, "if_primary_term": primary_term }));
+
+        let response: Response = self
+            .execute_on_any_node(|es_client| {
+                let path: String = path.clone();
+                let query: Option<Value> = query.clone();
+                let body: String = policy_body.to_string();
+                async move {
+                    let response: Response = es_client
+                        .es_conn
+                        .send(Method::Put, &path, HeaderMap::new(), query.as_ref(), Some(body), None)
+                        .await?;
+
+                    Ok(response)
+                }
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(())
+        } else {
+            let status_code = response.status_code();
+            let error_body: String = response.text().await.unwrap_or_default();
+            Err(anyhow!(
+                "[Elasticsearch Error][put_ism_policy()] Failed to put ISM policy '{}': Status Code: {}, {}",
+                policy_name,
+                status_code,
+                error_body
+            ))
+        }
+    }
+
+    #[doc = "루트 엔드포인트(GET /)로 클러스터 이름/버전/배포판 정보를 조회해주는 함수"]
+    async fn get_server_info(&self) -> Result<Value, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client.es_conn.info().send().await?;
+                Ok(response)
+            })
+            .await?;
+
+        if response.status_code().is_success() {
+            Ok(response.json::<Value>().await?)
+        } else {
+            Err(anyhow!(
+                "[Elasticsearch Error][get_server_info()] Failed to get server info: Status Code: {}",
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "index pattern 에 해당하는 인덱스 이름 목록을 조회해주는 함수"]
+    /// # Arguments
+    /// * `index_pattern` - 조회할 인덱스 패턴 (ex. os_metric_*)
+    ///
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error>
+    async fn get_index_names(&self, index_pattern: &str) -> Result<Vec<String>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .cat()
+                    .indices(CatIndicesParts::Index(&[index_pattern]))
+                    .format("json")
+                    .h(&["index"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if !response.status_code().is_success() {
+            return Err(anyhow!(
+                "[Elasticsearch Error][get_index_names()] Failed to list indices of '{}': Status Code: {}",
+                index_pattern,
+                response.status_code()
+            ));
+        }
+
+        let body: Value = response.json::<Value>().await?;
+        Ok(body
+            .as_array()
+            .map(|indices| {
+                indices
+                    .iter()
+                    .filter_map(|index| index["index"].as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    #[doc = "인덱스를 삭제해주는 함수"]
+    async fn delete_index(&self, index_name: &str) -> Result<(), anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .indices()
+                    .delete(IndicesDeleteParts::Index(&[index_name]))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
         if response.status_code().is_success() {
             Ok(())
         } else {
-            let error_message: String = format!(
-                "[Elasticsearch Error][post_doc()] Failed to index document: Status Code: {}",
+            Err(anyhow!(
+                "[Elasticsearch Error][delete_index()] Failed to delete index '{}': Status Code: {}",
+                index_name,
+                response.status_code()
+            ))
+        }
+    }
+
+    #[doc = "문서가 없을 때만 생성해주는 함수 (_create)"]
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - 이미 문서가 있으면 None, 생성했으면 _seq_no/_primary_term 이 담긴 응답
+    async fn create_doc(&self, index_name: &str, doc_id: &str, document: &Value) -> Result<Option<Value>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .create(CreateParts::IndexId(index_name, doc_id))
+                    .body(document.clone())
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        match response.status_code().as_u16() {
+            409 => Ok(None),
+            status if (200..300).contains(&status) => Ok(Some(response.json::<Value>().await?)),
+            _ => Err(anyhow!(
+                "[Elasticsearch Error][create_doc()] Failed to create '{}/{}': Status Code: {}",
+                index_name,
+                doc_id,
+                response.status_code()
+            )),
+        }
+    }
+
+    #[doc = "문서를 조회해주는 함수"]
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - 문서가 없으면 None, 있으면 _source/_seq_no/_primary_term 이 담긴 응답
+    async fn get_doc(&self, index_name: &str, doc_id: &str) -> Result<Option<Value>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .get(GetParts::IndexId(index_name, doc_id))
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        match response.status_code().as_u16() {
+            404 => Ok(None),
+            status if (200..300).contains(&status) => Ok(Some(response.json::<Value>().await?)),
+            _ => Err(anyhow!(
+                "[Elasticsearch Error][get_doc()] Failed to get '{}/{}': Status Code: {}",
+                index_name,
+                doc_id,
+                response.status_code()
+            )),
+        }
+    }
+
+    #[doc = "조회 이후 다른 곳에서 바뀌지 않았을 때만 문서를 덮어써주는 함수 (optimistic concurrency control)"]
+    /// # Returns
+    /// * Result<Option<Value>, anyhow::Error> - 그 사이 문서가 바뀌었다면 None
+    async fn put_doc_if_match(
+        &self,
+        index_name: &str,
+        doc_id: &str,
+        document: &Value,
+        seq_no: i64,
+        primary_term: i64,
+    ) -> Result<Option<Value>, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .index(IndexParts::IndexId(index_name, doc_id))
+                    .if_seq_no(seq_no)
+                    .if_primary_term(primary_term)
+                    .body(document.clone())
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        match response.status_code().as_u16() {
+            409 => Ok(None),
+            status if (200..300).contains(&status) => Ok(Some(response.json::<Value>().await?)),
+            _ => Err(anyhow!(
+                "[Elasticsearch Error][put_doc_if_match()] Failed to put '{}/{}': Status Code: {}",
+                index_name,
+                doc_id,
+                response.status_code()
+            )),
+        }
+    }
+
+    #[doc = "조회 이후 다른 곳에서 바뀌지 않았을 때만 문서를 삭제해주는 함수"]
+    /// # Returns
+    /// * Result<bool, anyhow::Error> - 그 사이 문서가 바뀌었거나 없다면 false
+    async fn delete_doc_if_match(
+        &self,
+        index_name: &str,
+        doc_id: &str,
+        seq_no: i64,
+        primary_term: i64,
+    ) -> Result<bool, anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .delete(DeleteParts::IndexId(index_name, doc_id))
+                    .if_seq_no(seq_no)
+                    .if_primary_term(primary_term)
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        match response.status_code().as_u16() {
+            404 | 409 => Ok(false),
+            status if (200..300).contains(&status) => Ok(true),
+            _ => Err(anyhow!(
+                "[Elasticsearch Error][delete_doc_if_match()] Failed to delete '{}/{}': Status Code: {}",
+                index_name,
+                doc_id,
+                response.status_code()
+            )),
+        }
+    }
+
+    #[doc = "_nodes/http 로 클러스터의 HTTP 노드 목록을 받아와 connection pool 에 반영해주는 함수"]
+    async fn sniff_nodes(&self) -> Result<(), anyhow::Error> {
+        let response: Response = self
+            .execute_on_any_node(|es_client| async move {
+                let response: Response = es_client
+                    .es_conn
+                    .nodes()
+                    .info(NodesInfoParts::Metric(&["http"]))
+                    .filter_path(&["nodes.*.http.publish_address"])
+                    .send()
+                    .await?;
+
+                Ok(response)
+            })
+            .await?;
+
+        if !response.status_code().is_success() {
+            return Err(anyhow!(
+                "[Elasticsearch Error][sniff_nodes()] Failed to sniff nodes: Status Code: {}",
                 response.status_code()
-            );
-            Err(anyhow!(error_message))
+            ));
+        }
+
+        let body: Value = response.json::<Value>().await?;
+        let scheme: String = self
+            .node_pool
+            .node_urls()
+            .first()
+            .map(|url| url.scheme().to_string())
+            .unwrap_or(String::from("http"));
+
+        let publish_addresses: Vec<&str> = body["nodes"]
+            .as_object()
+            .map(|nodes| {
+                nodes
+                    .values()
+                    .filter_map(|node| node["http"]["publish_address"].as_str())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if publish_addresses.is_empty() {
+            return Err(anyhow!("[ERROR][EsRepositoryPub->sniff_nodes] No HTTP node found in _nodes/http response"));
+        }
+
+        /* seed 는 호스트명으로, sniff 결과는 IP 로 올 수 있으므로 URL 이 아닌 주소(호스트명/IP:port)로 같은 노드인지 비교한다. */
+        let mut known_nodes: Vec<(Url, Vec<String>)> = Vec::new();
+        for url in self.node_pool.node_urls() {
+            let addresses: Vec<String> = resolve_node_addresses(&url).await;
+            known_nodes.push((url, addresses));
+        }
+
+        let mut sniffed_urls: Vec<Url> = Vec::new();
+        let mut new_clients: Vec<EsClient> = Vec::new();
+
+        for publish_address in publish_addresses {
+            let addresses: Vec<String> = publish_address_keys(publish_address);
+            let known_url: Option<&Url> = known_nodes
+                .iter()
+                .find(|(_, known_addresses)| known_addresses.iter().any(|address| addresses.contains(address)))
+                .map(|(url, _)| url);
+
+            if let Some(known_url) = known_url {
+                sniffed_urls.push(known_url.clone());
+                continue;
+            }
+
+            let Some(url) = parse_publish_address(&scheme, publish_address) else {
+                warn!("[WARN][EsRepositoryPub->sniff_nodes] Invalid publish_address: {}", publish_address);
+                continue;
+            };
+
+            match create_es_client(
+                url.clone(),
+                self.auth_header.as_ref(),
+                self.tls_config.as_ref(),
+                &self.transport_config,
+            ) {
+                Ok(es_client) => {
+                    sniffed_urls.push(url);
+                    new_clients.push(es_client);
+                }
+                Err(e) => error!("[ERROR][EsRepositoryPub->sniff_nodes] Failed to create client for {}: {:?}", url, e),
+            }
+        }
+
+        self.node_pool.sync_sniffed(&sniffed_urls, new_clients);
+
+        Ok(())
+    }
+}
+
+#[doc = "노드 하나에 접속하는 Elasticsearch 클라이언트를 만들어주는 함수 - 네트워크 요청 없이 설정만으로 만든다."]
+/// ca_fingerprint 를 사용하면 _bulk client 는 실제 handshake 에서 지문을 검증하고,
+/// 관리 API 용 transport 는 처음 사용할 때 `EsRepositoryPub::with_pinned_conn` 이 지문을 확인한 뒤에 만든다.
+///
+/// # Arguments
+/// * `es_url` - 노드 주소
+/// * `auth_header` - 모든 요청에 붙일 Authorization 헤더
+/// * `tls_config` - https 노드에 적용할 TLS 설정
+/// * `transport_config` - 요청 timeout, proxy 설정
+///
+/// # Returns
+/// * Result<EsClient, anyhow::Error>
+fn create_es_client(
+    es_url: Url,
+    auth_header: Option<&HeaderValue>,
+    tls_config: Option<&TlsConfig>,
+    transport_config: &TransportConfig,
+) -> Result<EsClient, anyhow::Error> {
+    let mut bulk_client_builder: reqwest::ClientBuilder = reqwest::Client::builder()
+        .user_agent(concat!("os_metric_beats/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(*transport_config.request_timeout_secs()))
+        .connect_timeout(Duration::from_secs(*transport_config.connect_timeout_secs()))
+        .pool_idle_timeout(Duration::from_secs(*transport_config.pool_idle_timeout_secs()))
+        .pool_max_idle_per_host(*transport_config.pool_max_idle_per_host())
+        .tcp_keepalive(match *transport_config.tcp_keepalive_secs() {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        });
+
+    if let Some(proxy_url) = transport_config.proxy_url() {
+        let proxy_url: Url = Url::parse(proxy_url)
+            .map_err(|e| anyhow!("[ERROR][EsRepositoryPub->create_es_client] Invalid proxy_url '{}': {}", proxy_url, e))?;
+        let mut bulk_proxy: reqwest::Proxy = reqwest::Proxy::all(proxy_url)?;
+        if let Some(proxy_username) = transport_config.proxy_username() {
+            let proxy_password: Option<String> = match transport_config.proxy_password() {
+                Some(proxy_password) => Some(proxy_password.resolve()?),
+                None => None,
+            };
+            bulk_proxy = bulk_proxy.basic_auth(proxy_username, proxy_password.as_deref().unwrap_or_default());
+        }
+        bulk_client_builder = bulk_client_builder.proxy(bulk_proxy);
+    }
+
+    if let Some(auth_header) = auth_header {
+        bulk_client_builder =
+            bulk_client_builder.default_headers(HeaderMap::from_iter([(AUTHORIZATION, auth_header.clone())]));
+    }
+
+    let mut trusted_certs: Vec<Vec<u8>> = Vec::new();
+    let mut pinned_conn: Option<Arc<tokio::sync::OnceCell<Elasticsearch>>> = None;
+
+    match tls_config {
+        Some(tls_config) if es_url.scheme() == "https" => {
+            trusted_certs = load_trusted_ca_certs(tls_config)?;
+            let pinned: bool =
+                tls_config.ca_fingerprint().is_some() && *tls_config.verification_mode() != TlsVerificationMode::None;
+
+            if pinned {
+                /* 지문 검증기를 넣은 rustls 설정에는 mTLS 인증서도 함께 들어있다. */
+                let (client_config, _verifier) = build_pinned_tls_config(tls_config, &trusted_certs)?;
+                bulk_client_builder = bulk_client_builder.use_preconfigured_tls(client_config);
+                pinned_conn = Some(Arc::new(tokio::sync::OnceCell::new()));
+            } else {
+                if *tls_config.verification_mode() == TlsVerificationMode::None {
+                    bulk_client_builder = bulk_client_builder.danger_accept_invalid_certs(true);
+                }
+                for der in &trusted_certs {
+                    bulk_client_builder = bulk_client_builder.add_root_certificate(reqwest::Certificate::from_der(der)?);
+                }
+                if let Some(identity_pem) = read_client_identity_pem(tls_config)? {
+                    bulk_client_builder = bulk_client_builder.identity(reqwest::Identity::from_pem(&identity_pem)?);
+                }
+            }
+        }
+        Some(_) => {
+            warn!("[WARN][EsRepositoryPub->create_es_client] TLS settings are ignored for non-https host: {}", es_url);
+        }
+        None => (),
+    }
+
+    Ok(EsClient {
+        es_conn: build_es_conn(&es_url, auth_header, tls_config, transport_config, &trusted_certs)?,
+        url: es_url,
+        bulk_client: bulk_client_builder.build()?,
+        pinned_conn,
+    })
+}
+
+#[doc = "execute_on_any_node 가 노드 상태를 판단할 때 사용하는 응답 상태코드"]
+trait NodeResponse {
+    fn status_u16(&self) -> u16;
+
+    #[doc = "노드(또는 클러스터)가 요청을 처리하지 못한 응답인지 여부 - 5xx 와 429"]
+    fn is_node_failure(&self) -> bool {
+        let status: u16 = self.status_u16();
+        status == 429 || status >= 500
+    }
+}
+
+impl NodeResponse for Response {
+    fn status_u16(&self) -> u16 {
+        self.status_code().as_u16()
+    }
+}
+
+impl NodeResponse for reqwest::Response {
+    fn status_u16(&self) -> u16 {
+        self.status().as_u16()
+    }
+}
+
+#[doc = "설정의 host 문자열을 URL 로 바꿔주는 함수 - scheme 이 없는 host 는 TLS 설정 여부에 따라 https/http 로 간주"]
+fn parse_host_url(url: &str, tls_config: Option<&TlsConfig>) -> Result<Url, anyhow::Error> {
+    let host: &str = url.trim();
+    let (scheme, host) = match host.split_once("://") {
+        Some((scheme, rest)) => (scheme.to_string(), rest),
+        None if tls_config.is_some() => (String::from("https"), host),
+        None => (String::from("http"), host),
+    };
+
+    Url::parse(&format!("{}://{}", scheme, host))
+        .map_err(|e| anyhow!("[ERROR][EsRepositoryPub->parse_host_url] Failed to parse URL '{}://{}': {}", scheme, host, e))
+}
+
+#[doc = "관리 API(템플릿, ILM, sniff 등)에 사용하는 elasticsearch transport 를 만들어주는 함수"]
+/// # Arguments
+/// * `es_url` - 노드 주소
+/// * `auth_header` - 모든 요청에 붙일 Authorization 헤더
+/// * `tls_config` - https 노드에 적용할 TLS 설정
+/// * `transport_config` - 요청 timeout, proxy 설정
+/// * `trusted_certs` - 신뢰할 CA 인증서(DER) 목록 (지문을 확인한 인증서 포함)
+///
+/// # Returns
+/// * Result<Elasticsearch, anyhow::Error>
+fn build_es_conn(
+    es_url: &Url,
+    auth_header: Option<&HeaderValue>,
+    tls_config: Option<&TlsConfig>,
+    transport_config: &TransportConfig,
+    trusted_certs: &[Vec<u8>],
+) -> Result<Elasticsearch, anyhow::Error> {
+    let mut transport_builder: TransportBuilder =
+        TransportBuilder::new(SingleNodeConnectionPool::new(es_url.clone()))
+            .timeout(Duration::from_secs(*transport_config.request_timeout_secs()));
+
+    if let Some(proxy_url) = transport_config.proxy_url() {
+        let proxy_url: Url = Url::parse(proxy_url)
+            .map_err(|e| anyhow!("[ERROR][EsRepositoryPub->build_es_conn] Invalid proxy_url '{}': {}", proxy_url, e))?;
+        let proxy_password: Option<String> = match transport_config.proxy_password() {
+            Some(proxy_password) => Some(proxy_password.resolve()?),
+            None => None,
+        };
+
+        transport_builder = transport_builder.proxy(
+            proxy_url,
+            transport_config.proxy_username().as_deref(),
+            proxy_password.as_deref(),
+        );
+    }
+
+    if let Some(auth_header) = auth_header {
+        transport_builder = transport_builder.header(AUTHORIZATION, auth_header.clone());
+    }
+
+    if let Some(tls_config) = tls_config.filter(|_| es_url.scheme() == "https") {
+        transport_builder =
+            transport_builder.cert_validation(build_cert_validation(tls_config, trusted_certs, es_url)?);
+
+        if let Some(client_cert) = build_client_certificate(tls_config)? {
+            transport_builder = transport_builder.auth(client_cert);
+        }
+    }
+
+    Ok(Elasticsearch::new(transport_builder.build()?))
+}
+
+#[doc = "_nodes/http 의 publish_address 로 노드를 비교할 주소 목록을 만들어주는 함수 (ex. [\"10.0.0.1:9200\", \"es-node-1:9200\"])"]
+fn publish_address_keys(publish_address: &str) -> Vec<String> {
+    match publish_address.split_once('/') {
+        Some((hostname, ip_port)) => {
+            let mut keys: Vec<String> = vec![ip_port.to_string()];
+            if let (false, Some((_, port))) = (hostname.is_empty(), ip_port.rsplit_once(':')) {
+                keys.push(format!("{}:{}", hostname.to_lowercase(), port));
+            }
+            keys
+        }
+        None => vec![publish_address.to_string()],
+    }
+}
+
+#[doc = "pool 노드 URL 의 호스트명:port 와 DNS 로 찾은 IP:port 목록을 만들어주는 함수 - sniff 결과의 publish_address 와 비교한다."]
+async fn resolve_node_addresses(url: &Url) -> Vec<String> {
+    let (Some(host), Some(port)) = (url.host_str(), url.port_or_known_default()) else {
+        return Vec::new();
+    };
+
+    let mut addresses: Vec<String> = vec![format!("{}:{}", host, port)];
+
+    /* IP 로 지정한 노드는 조회할 필요가 없다. */
+    if host.trim_matches(|c| c == '[' || c == ']').parse::<std::net::IpAddr>().is_err() {
+        match tokio::net::lookup_host((host, port)).await {
+            Ok(resolved) => addresses.extend(resolved.map(|addr| addr.to_string())),
+            Err(e) => warn!("[WARN][EsRepositoryPub->resolve_node_addresses] Failed to resolve {}: {:?}", host, e),
         }
     }
-}
\ No newline at end of file
+
+    addresses
+}
+
+#[doc = "_nodes/http 의 publish_address 를 URL 로 바꿔주는 함수"]
+/// publish_address 는 "10.0.0.1:9200" 또는 "es-node-1/10.0.0.1:9200" 형태이며,
+/// https 인 경우 인증서 호스트명 검증을 위해 호스트명을 우선 사용한다.
+fn parse_publish_address(scheme: &str, publish_address: &str) -> Option<Url> {
+    let address: String = match publish_address.split_once('/') {
+        Some((hostname, ip_port)) => {
+            let port: &str = ip_port.rsplit_once(':').map(|(_, port)| port)?;
+            if scheme == "https" && !hostname.is_empty() {
+                format!("{}:{}", hostname, port)
+            } else {
+                ip_port.to_string()
+            }
+        }
+        None => publish_address.to_string(),
+    };
+
+    Url::parse(&format!("{}://{}", scheme, address)).ok()
+}
@@ -1 +1,3 @@
+pub mod disk_spool_repository;
+pub mod es_node_pool;
 pub mod es_repository;
@@ -0,0 +1,613 @@
+use crate::common::*;
+
+use crate::common_enums::{bulk_op_type::*, retry_class::*};
+
+use crate::repository::{disk_spool_repository::*, es_repository::*};
+
+use crate::model::elastic::{
+    bulk_config::*, bulk_item_result::*, bulk_queue::*, dead_letter_config::*, dead_letter_record::*,
+    es_document::*, retry_config::*, spool_config::*,
+};
+
+use crate::traits::bulk_shipper_service::*;
+
+#[doc = "큐의 대기시간(max_age)을 확인하는 주기"]
+const FLUSH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+pub struct BulkShipperServiceImpl {
+    sender: mpsc::Sender<EsDocument>,
+}
+
+impl BulkShipperServiceImpl {
+    #[doc = "문서를 모아서 _bulk 로 보내주는 백그라운드 작업을 띄우고 핸들을 반환하는 함수"]
+    /// # Arguments
+    /// * `es_conn`      - 문서를 색인할 Elasticsearch 저장소
+    /// * `bulk_config`  - flush 기준 (문서 수, 바이트, 대기시간)
+    /// * `retry_config` - 일시적인 _bulk 요청 실패의 재시도 정책
+    /// * `spool_config` - 전송 실패 문서를 보관할 디스크 spool 설정 (None 이면 사용하지 않음)
+    /// * `dead_letter_config` - 거부된 문서를 보관할 dead-letter 설정 (None 이면 로그만 남김)
+    ///
+    /// # Returns
+    /// * BulkShipperServiceImpl
+    pub fn new(
+        es_conn: Arc<EsRepositoryPub>,
+        bulk_config: BulkConfig,
+        retry_config: RetryConfig,
+        spool_config: Option<SpoolConfig>,
+        dead_letter_config: Option<DeadLetterConfig>,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel::<EsDocument>(*bulk_config.queue_capacity());
+
+        let replay_interval: Duration = spool_config
+            .as_ref()
+            .map(|spool_config| Duration::from_secs(*spool_config.replay_interval_secs()))
+            .unwrap_or(FLUSH_CHECK_INTERVAL);
+
+        let spool: Option<DiskSpoolRepository> =
+            spool_config.and_then(|spool_config| match DiskSpoolRepository::new(spool_config) {
+                Ok(spool) => Some(spool),
+                Err(e) => {
+                    error!("[ERROR][BulkShipperServiceImpl->new] Failed to open spool: {:?}", e);
+                    None
+                }
+            });
+
+        let bulk_worker: BulkWorker = BulkWorker {
+            es_conn,
+            queue: BulkQueue::new(*bulk_config.queue_capacity()),
+            bulk_config,
+            retry_config,
+            spool,
+            dead_letter_config,
+            replay_interval,
+            next_replay_at: Instant::now(),
+            receiver,
+        };
+        tokio::spawn(bulk_worker.run());
+
+        BulkShipperServiceImpl { sender }
+    }
+}
+
+impl BulkShipperService for BulkShipperServiceImpl {
+    #[doc = "문서를 _bulk 큐에 넣어주는 함수 - 색인이 끝날때까지 기다리지 않는다."]
+    fn enqueue(&self, document: EsDocument) -> Result<(), anyhow::Error> {
+        self.sender.try_send(document).map_err(|e| {
+            anyhow!(
+                "[ERROR][BulkShipperServiceImpl->enqueue] Failed to enqueue document: {}",
+                e
+            )
+        })
+    }
+}
+
+#[doc = "_bulk 큐와 spool 을 소유하고 flush/재전송을 담당하는 백그라운드 작업"]
+struct BulkWorker {
+    es_conn: Arc<EsRepositoryPub>,
+    bulk_config: BulkConfig,
+    retry_config: RetryConfig,
+    queue: BulkQueue,
+    spool: Option<DiskSpoolRepository>,
+    dead_letter_config: Option<DeadLetterConfig>,
+    replay_interval: Duration,
+    next_replay_at: Instant,
+    receiver: mpsc::Receiver<EsDocument>,
+}
+
+impl BulkWorker {
+    async fn run(mut self) {
+        let mut flush_ticker: tokio::time::Interval = interval(FLUSH_CHECK_INTERVAL);
+
+        loop {
+            select! {
+                document = self.receiver.recv() => {
+                    match document {
+                        Some(document) => {
+                            if let Err(e) = self.queue.push_back(document) {
+                                error!("[ERROR][BulkWorker->run] {:?}", e);
+                            }
+                        }
+                        None => break,
+                    }
+                }
+                _ = flush_ticker.tick() => {
+                    self.replay_spool().await;
+                }
+            }
+
+            while self.queue.should_flush(&self.bulk_config) {
+                if !self.flush().await {
+                    break;
+                }
+            }
+        }
+
+        /* 채널이 닫힌 경우 남은 문서를 모두 보낸다. */
+        while !self.queue.is_empty() {
+            if !self.flush().await {
+                break;
+            }
+        }
+    }
+
+    #[doc = "spool 에 보관된 문서가 남아있는지 여부"]
+    fn has_spooled(&self) -> bool {
+        self.spool.as_ref().is_some_and(|spool| !spool.is_empty())
+    }
+
+    #[doc = "큐 앞쪽의 문서들을 _bulk 로 보내고 결과를 처리하는 함수"]
+    /// # Returns
+    /// * bool - 이어서 flush 해도 되는지 여부 (재시도 가능한 실패로 Elasticsearch 에 보내지 못했다면 false)
+    async fn flush(&mut self) -> bool {
+        let batch: Vec<EsDocument> = self.queue.drain_batch(&self.bulk_config);
+
+        if batch.is_empty() {
+            return true;
+        }
+
+        /* spool 에 먼저 쌓인 문서가 있으면 순서를 지키기 위해 뒤에 이어 붙인다. */
+        if self.has_spooled() {
+            self.spool_docs(batch);
+            return true;
+        }
+
+        match self.send_batch_with_retry(&batch).await {
+            Ok(item_results) => {
+                self.handle_item_results(batch, item_results).await;
+                true
+            }
+            Err(e) => {
+                error!("[ERROR][BulkWorker->flush] [{}] {:?}", self.es_conn.output_name(), e);
+
+                /* 다시 보내도 실패할 요청(ex. 400, 413)은 spool/재시도하지 않는다 - spool 이 막히는 것을 방지 */
+                match RetryClass::from_error(&e) {
+                    RetryClass::Retryable { .. } => {
+                        if self.spool.is_some() {
+                            self.spool_docs(batch);
+                        } else {
+                            self.retry_or_drop(batch);
+                        }
+                        false
+                    }
+                    RetryClass::Permanent => {
+                        self.reject_batch(batch, &e).await;
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    #[doc = "문서 목록을 NDJSON 으로 직렬화해서 _bulk 로 보내고 문서별 결과를 반환해주는 함수"]
+    async fn send_batch(
+        &self,
+        batch: &[EsDocument],
+    ) -> Result<Vec<BulkItemResult>, anyhow::Error> {
+        let bulk_body: String = batch
+            .iter()
+            .map(|doc| doc.to_bulk_lines())
+            .collect::<Result<String, anyhow::Error>>()?;
+
+        let response_body: Value = self.es_conn.post_bulk(bulk_body).await?;
+
+        BulkItemResult::from_bulk_response(&response_body)
+    }
+
+    #[doc = "일시적인 실패(429, 502/503/504, 연결 끊김)는 backoff 후 다시 보내주는 함수"]
+    /// 재시도 횟수나 retry budget 을 넘기거나 재시도해도 실패할 응답이면 마지막 에러를 반환한다.
+    async fn send_batch_with_retry(
+        &mut self,
+        batch: &[EsDocument],
+    ) -> Result<Vec<BulkItemResult>, anyhow::Error> {
+        let started_at: Instant = Instant::now();
+        let retry_budget: Duration = Duration::from_secs(*self.retry_config.retry_budget_secs());
+        let mut attempt: u32 = 0;
+
+        loop {
+            let err: anyhow::Error = match self.send_batch(batch).await {
+                Ok(item_results) => return Ok(item_results),
+                Err(err) => err,
+            };
+
+            let RetryClass::Retryable { retry_after } = RetryClass::from_error(&err) else {
+                return Err(err);
+            };
+
+            attempt += 1;
+            if attempt > *self.retry_config.max_retries() {
+                return Err(err.context(format!("Giving up after {} retries", attempt - 1)));
+            }
+
+            let delay: Duration = self.retry_config.backoff_delay(attempt, retry_after);
+            if started_at.elapsed() + delay > retry_budget {
+                return Err(err.context(format!(
+                    "Retry budget of {}s exhausted after {} retries",
+                    retry_budget.as_secs(),
+                    attempt - 1
+                )));
+            }
+
+            warn!(
+                "[WARN][BulkWorker->send_batch_with_retry] [{}] Bulk request failed, retry {}/{} in {}ms: {:?}",
+                self.es_conn.output_name(),
+                attempt,
+                self.retry_config.max_retries(),
+                delay.as_millis(),
+                err
+            );
+            self.wait_draining(delay).await;
+        }
+    }
+
+    #[doc = "재시도 대기 중에도 채널을 비워서 enqueue 가 문서를 버리지 않게 해주는 함수"]
+    /// 큐에 자리가 있으면 큐에 넣고, 가득 찼다면 spool 에 보관한다 (spool 이 없으면 버린다).
+    ///
+    /// # Arguments
+    /// * `delay` - 다음 재시도까지 기다릴 시간
+    async fn wait_draining(&mut self, delay: Duration) {
+        let deadline: tokio::time::Instant = tokio::time::Instant::now() + delay;
+        let mut overflow: Vec<EsDocument> = Vec::new();
+
+        loop {
+            select! {
+                document = self.receiver.recv() => {
+                    match document {
+                        Some(document) if self.queue.is_full() => overflow.push(document),
+                        Some(document) => {
+                            if let Err(e) = self.queue.push_back(document) {
+                                error!("[ERROR][BulkWorker->wait_draining] {:?}", e);
+                            }
+                        }
+                        /* 채널이 닫혔다면 남은 시간만큼 기다린다. */
+                        None => {
+                            tokio::time::sleep_until(deadline).await;
+                            break;
+                        }
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline) => break,
+            }
+
+            if overflow.len() >= *self.bulk_config.max_docs() {
+                self.spool_overflow(std::mem::take(&mut overflow));
+            }
+        }
+
+        self.spool_overflow(overflow);
+    }
+
+    #[doc = "큐에 넣지 못한 문서를 spool 에 보관하고, spool 이 없다면 버리는 함수"]
+    fn spool_overflow(&mut self, docs: Vec<EsDocument>) {
+        if docs.is_empty() {
+            return;
+        }
+
+        if self.spool.is_some() {
+            self.spool_docs(docs);
+        } else {
+            error!(
+                "[ERROR][BulkWorker->spool_overflow] [{}] Queue is full while retrying, dropping {} documents",
+                self.es_conn.output_name(),
+                docs.len()
+            );
+        }
+    }
+
+    #[doc = "Elasticsearch 가 다시 응답하면 spool 에 보관된 문서를 오래된 순서대로 재전송하는 함수"]
+    async fn replay_spool(&mut self) {
+        if !self.has_spooled() || Instant::now() < self.next_replay_at {
+            return;
+        }
+
+        let max_docs: usize = *self.bulk_config.max_docs();
+
+        while let Some(spool) = self.spool.as_mut() {
+            let batch: SpoolBatch = match spool.peek_oldest(max_docs) {
+                Ok(batch) => batch,
+                Err(e) => {
+                    error!("[ERROR][BulkWorker->replay_spool] {:?}", e);
+                    break;
+                }
+            };
+
+            if batch.is_empty() {
+                if spool.is_empty() {
+                    info!("[{}] Spool replay completed", self.es_conn.output_name());
+                    break;
+                }
+                continue;
+            }
+
+            let SpoolBatch {
+                mut docs,
+                doc_offsets,
+                mut undecodable,
+            } = batch;
+
+            if docs.is_empty() {
+                self.commit_replayed();
+                self.dead_letter_undecodable(undecodable).await;
+                continue;
+            }
+
+            match self.send_batch(&docs).await {
+                Ok(mut item_results) => {
+                    /* 재시도할 문서(결과가 없는 문서 포함)가 있으면 그 앞까지만 완료로 기록하고 나머지는 spool 에 그대로 둔다.
+                     * 큐로 돌려보내면 spool 맨 뒤에 다시 쌓여서 순서가 바뀐다. */
+                    let retry_idx: Option<usize> = BulkItemResult::first_retry_index(&item_results, docs.len());
+
+                    let Some(retry_idx) = retry_idx else {
+                        self.commit_replayed();
+                        self.handle_item_results(docs, item_results).await;
+                        self.dead_letter_undecodable(undecodable).await;
+                        continue;
+                    };
+
+                    let retry_offset: u64 = doc_offsets[retry_idx];
+                    if let Some(Err(e)) = self.spool.as_mut().map(|spool| spool.commit_replayed_until(retry_offset)) {
+                        error!("[ERROR][BulkWorker->replay_spool] Failed to record replay position: {:?}", e);
+                    }
+
+                    warn!(
+                        "[WARN][BulkWorker->replay_spool] [{}] {} spooled documents will be replayed again",
+                        self.es_conn.output_name(),
+                        docs.len() - retry_idx
+                    );
+
+                    docs.truncate(retry_idx);
+                    item_results.truncate(retry_idx);
+                    undecodable.retain(|record| record.offset < retry_offset);
+
+                    self.handle_item_results(docs, item_results).await;
+                    self.dead_letter_undecodable(undecodable).await;
+                    self.next_replay_at = Instant::now() + self.replay_interval;
+                    break;
+                }
+                Err(e) if RetryClass::from_error(&e) == RetryClass::Permanent => {
+                    /* 영구 거부된 batch 는 건너뛰어야 뒤의 문서들을 계속 재전송할 수 있다. */
+                    self.commit_replayed();
+                    self.reject_batch(docs, &e).await;
+                    self.dead_letter_undecodable(undecodable).await;
+                }
+                Err(e) => {
+                    warn!(
+                        "[WARN][BulkWorker->replay_spool] [{}] Elasticsearch is still unreachable: {:?}",
+                        self.es_conn.output_name(),
+                        e
+                    );
+                    self.next_replay_at = Instant::now() + self.replay_interval;
+                    break;
+                }
+            }
+        }
+    }
+
+    #[doc = "peek_oldest 로 꺼낸 record 들을 재전송 완료로 기록해주는 함수"]
+    fn commit_replayed(&mut self) {
+        if let Some(Err(e)) = self.spool.as_mut().map(|spool| spool.commit_replayed()) {
+            error!("[ERROR][BulkWorker->commit_replayed] Failed to record replay position: {:?}", e);
+        }
+    }
+
+    #[doc = "spool 에서 역직렬화하지 못한 record 를 dead-letter 로 보내는 함수"]
+    async fn dead_letter_undecodable(&self, undecodable: Vec<UndecodableRecord>) {
+        if undecodable.is_empty() {
+            return;
+        }
+
+        if self.dead_letter_config.is_none() {
+            error!(
+                "[ERROR][BulkWorker->dead_letter_undecodable] [{}] Dropping {} undecodable spool records (dead_letter is not configured)",
+                self.es_conn.output_name(),
+                undecodable.len()
+            );
+            return;
+        }
+
+        let dead_letters: Vec<DeadLetterRecord> = undecodable
+            .into_iter()
+            .map(|record| DeadLetterRecord::from_undecodable(record.payload, record.reason))
+            .collect();
+
+        self.dead_letter(dead_letters).await;
+    }
+
+    #[doc = "_bulk 요청 자체가 영구적으로 거부된 batch 를 spool/재시도 없이 dead-letter 로 보내는 함수"]
+    async fn reject_batch(&self, batch: Vec<EsDocument>, err: &anyhow::Error) {
+        error!(
+            "[ERROR][BulkWorker->reject_batch] [{}] Bulk request permanently rejected, {} documents: {:?}",
+            self.es_conn.output_name(),
+            batch.len(),
+            err
+        );
+
+        let dead_letters: Vec<DeadLetterRecord> = batch
+            .iter()
+            .map(|doc| DeadLetterRecord::from_request_error(doc, err))
+            .collect();
+
+        self.dead_letter(dead_letters).await;
+    }
+
+    #[doc = "전송하지 못한 문서를 spool 에 추가하는 함수"]
+    fn spool_docs(&mut self, docs: Vec<EsDocument>) {
+        if docs.is_empty() {
+            return;
+        }
+
+        match self.spool.as_mut() {
+            Some(spool) => match spool.append(&docs) {
+                Ok(()) => warn!(
+                    "[WARN][BulkWorker->spool_docs] [{}] {} documents appended to spool",
+                    self.es_conn.output_name(),
+                    docs.len()
+                ),
+                Err(e) => error!(
+                    "[ERROR][BulkWorker->spool_docs] Failed to append {} documents to spool: {:?}",
+                    docs.len(),
+                    e
+                ),
+            },
+            None => self.retry_or_drop(docs),
+        }
+    }
+
+    #[doc = "문서별 처리 결과를 확인해서 재시도 가능한 실패는 다시 큐에 넣고, 그 외 실패는 개별적으로 로깅하는 함수"]
+    async fn handle_item_results(&mut self, batch: Vec<EsDocument>, item_results: Vec<BulkItemResult>) {
+        let mut retry_docs: Vec<EsDocument> = Vec::new();
+        let mut dead_letters: Vec<DeadLetterRecord> = Vec::new();
+        let mut success_cnt: usize = 0;
+        let mut duplicate_cnt: usize = 0;
+
+        /* 응답의 문서별 결과가 요청보다 적으면 결과가 없는 문서는 색인 여부를 알 수 없으므로 재시도한다. */
+        let mut batch: Vec<EsDocument> = batch;
+        let unmatched_docs: Vec<EsDocument> = batch.split_off(item_results.len().min(batch.len()));
+        if !unmatched_docs.is_empty() {
+            warn!(
+                "[WARN][BulkWorker->handle_item_results] Bulk response has no result for {} documents",
+                unmatched_docs.len()
+            );
+            retry_docs.extend(unmatched_docs);
+        }
+
+        for (doc, item_result) in batch.into_iter().zip(item_results) {
+            if item_result.is_success() {
+                success_cnt += 1;
+            } else if item_result.is_duplicate() {
+                /* 타임아웃 후 재시도/spool 재전송으로 같은 문서가 다시 온 경우 - 중복 색인 없이 건너뜀 */
+                duplicate_cnt += 1;
+            } else if item_result.is_retryable() {
+                retry_docs.push(doc);
+            } else {
+                error!(
+                    "[ERROR][BulkWorker->handle_item_results] Document rejected: index={}, status={}, type={:?}, reason={:?}",
+                    doc.index_name, item_result.status, item_result.error_type, item_result.error_reason
+                );
+                dead_letters.push(DeadLetterRecord::from_rejected(&doc, &item_result));
+            }
+        }
+
+        if !retry_docs.is_empty() {
+            warn!(
+                "[WARN][BulkWorker->handle_item_results] {} documents will be retried",
+                retry_docs.len()
+            );
+        }
+
+        self.retry_or_drop(retry_docs);
+        self.dead_letter(dead_letters).await;
+
+        info!(
+            "[{}] Bulk request completed: {} documents indexed, {} duplicates skipped",
+            self.es_conn.output_name(),
+            success_cnt,
+            duplicate_cnt
+        );
+    }
+
+    #[doc = "거부된 문서를 dead-letter 인덱스에 색인하고, 그것도 실패하면 로컬 NDJSON 파일에 남기는 함수"]
+    async fn dead_letter(&self, records: Vec<DeadLetterRecord>) {
+        let Some(dead_letter_config) = self.dead_letter_config.as_ref() else {
+            return;
+        };
+
+        if records.is_empty() {
+            return;
+        }
+
+        let docs: Vec<EsDocument> = match records
+            .iter()
+            .map(|record| {
+                Ok(EsDocument::new(
+                    dead_letter_config.index().clone(),
+                    BulkOpType::Index,
+                    serde_json::to_value(record)?,
+                ))
+            })
+            .collect::<Result<Vec<EsDocument>, anyhow::Error>>()
+        {
+            Ok(docs) => docs,
+            Err(e) => {
+                error!("[ERROR][BulkWorker->dead_letter] {:?}", e);
+                self.write_dead_letter_file(dead_letter_config, &records);
+                return;
+            }
+        };
+
+        let failed_records: Vec<DeadLetterRecord> = match self.send_batch(&docs).await {
+            /* 결과가 없는 기록(응답 item 이 요청보다 적은 경우)도 색인되었다고 볼 수 없으므로 파일에 남긴다. */
+            Ok(item_results) => records
+                .into_iter()
+                .enumerate()
+                .filter(|(idx, _)| !item_results.get(*idx).is_some_and(|item_result| item_result.is_success()))
+                .map(|(_, record)| record)
+                .collect(),
+            Err(e) => {
+                error!("[ERROR][BulkWorker->dead_letter] Failed to index into '{}': {:?}", dead_letter_config.index(), e);
+                records
+            }
+        };
+
+        self.write_dead_letter_file(dead_letter_config, &failed_records);
+    }
+
+    #[doc = "dead-letter 인덱스에 색인하지 못한 기록을 NDJSON 파일에 추가하는 함수"]
+    fn write_dead_letter_file(&self, dead_letter_config: &DeadLetterConfig, records: &[DeadLetterRecord]) {
+        if records.is_empty() {
+            return;
+        }
+
+        let result: Result<(), anyhow::Error> = (|| {
+            let mut dead_letter_file: File = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(dead_letter_config.fallback_path())?;
+            for record in records {
+                writeln!(dead_letter_file, "{}", serde_json::to_string(record)?)?;
+            }
+            Ok(())
+        })();
+
+        match result {
+            Ok(()) => warn!(
+                "[WARN][BulkWorker->write_dead_letter_file] {} rejected documents written to '{}'",
+                records.len(),
+                dead_letter_config.fallback_path()
+            ),
+            Err(e) => error!("[ERROR][BulkWorker->write_dead_letter_file] {:?}", e),
+        }
+    }
+
+    #[doc = "재시도 횟수가 남은 문서는 큐 앞쪽에 다시 넣고, 초과한 문서는 spool 에 보관하거나 버리는 함수"]
+    fn retry_or_drop(&mut self, docs: Vec<EsDocument>) {
+        let max_item_retries: u32 = *self.bulk_config.max_item_retries();
+
+        let (retry_docs, exhausted_docs): (Vec<EsDocument>, Vec<EsDocument>) = docs
+            .into_iter()
+            .map(|mut doc| {
+                doc.attempts += 1;
+                doc
+            })
+            .partition(|doc| doc.attempts <= max_item_retries);
+
+        if let Err(e) = self.queue.push_front_all(retry_docs) {
+            error!("[ERROR][BulkWorker->retry_or_drop] {:?}", e);
+        }
+
+        if exhausted_docs.is_empty() {
+            return;
+        }
+
+        if self.spool.is_some() {
+            self.spool_docs(exhausted_docs);
+        } else {
+            for doc in exhausted_docs {
+                error!(
+                    "[ERROR][BulkWorker->retry_or_drop] Dropping document after {} attempts: index={}",
+                    doc.attempts, doc.index_name
+                );
+            }
+        }
+    }
+}
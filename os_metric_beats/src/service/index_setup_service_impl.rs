@@ -0,0 +1,363 @@
+use crate::common::*;
+
+use crate::common_enums::es_flavour::*;
+
+use crate::repository::es_repository::*;
+
+use crate::model::elastic::{ilm_config::*, index_template_spec::*, template_config::*};
+use crate::model::metric_info::*;
+use crate::model::slowlog::slowlog_metric_info::*;
+
+use crate::traits::index_setup_service::*;
+
+#[derive(Clone, Debug)]
+pub struct IndexSetupServiceImpl {
+    es_conn: Arc<EsRepositoryPub>,
+}
+
+impl IndexSetupServiceImpl {
+    pub fn new(es_conn: Arc<EsRepositoryPub>) -> Self {
+        IndexSetupServiceImpl { es_conn }
+    }
+
+    #[doc = "문서 종류별로 설치할 템플릿 목록을 만들어주는 함수"]
+    fn template_specs(&self) -> Vec<IndexTemplateSpec> {
+        let template_config: &TemplateConfig = self.es_conn.template_config();
+
+        /* data stream 모드 - 템플릿이 data stream 을 만들고 rollover 는 클러스터가 담당 */
+        if let Some(data_stream_config) = self.es_conn.data_stream_config() {
+            return vec![
+                IndexTemplateSpec::new(
+                    format!("{}-metrics", template_config.name_prefix()),
+                    vec![data_stream_config.metric_data_stream()],
+                    with_timestamp_field(MetricInfo::index_mappings()),
+                    *template_config.priority(),
+                    true,
+                ),
+                IndexTemplateSpec::new(
+                    format!("{}-slowlog", template_config.name_prefix()),
+                    vec![data_stream_config.slowlog_data_stream()],
+                    with_timestamp_field(SlowLogMetricInfo::index_mappings()),
+                    *template_config.priority(),
+                    true,
+                ),
+            ];
+        }
+
+        let index_pattern: String = format!("{}*", self.es_conn.index_pattern());
+        let slowlog_index_pattern: String = format!("{}*", self.es_conn.slowlog_index_pattern());
+
+        /* 두 문서가 같은 인덱스에 색인된다면 mapping 을 합쳐서 하나의 템플릿으로 설치 */
+        if index_pattern == slowlog_index_pattern {
+            return vec![IndexTemplateSpec::new(
+                format!("{}-metrics", template_config.name_prefix()),
+                vec![index_pattern],
+                merge_mappings(&MetricInfo::index_mappings(), &SlowLogMetricInfo::index_mappings()),
+                *template_config.priority(),
+                false,
+            )];
+        }
+
+        /* 패턴이 겹치는 경우(ex. os_metric_* / os_metric_slowlog_*) 더 구체적인 slow log 템플릿이 우선하도록 priority 를 높게 준다. */
+        vec![
+            IndexTemplateSpec::new(
+                format!("{}-metrics", template_config.name_prefix()),
+                vec![index_pattern],
+                MetricInfo::index_mappings(),
+                *template_config.priority(),
+                false,
+            ),
+            IndexTemplateSpec::new(
+                format!("{}-slowlog", template_config.name_prefix()),
+                vec![slowlog_index_pattern],
+                SlowLogMetricInfo::index_mappings(),
+                template_config.priority() + 1,
+                false,
+            ),
+        ]
+    }
+
+    #[doc = "템플릿을 설치해주는 함수 - 이미 같은 내용으로 설치되어 있으면 건너뛴다."]
+    /// # Arguments
+    /// * `spec` - 설치할 템플릿 정의
+    /// * `ilm_policy` - 템플릿에 연결할 lifecycle policy 이름
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn install_template(&self, spec: &IndexTemplateSpec, ilm_policy: Option<&str>) -> Result<(), anyhow::Error> {
+        let template_body: Value = spec.to_template_body(self.es_conn.template_config(), ilm_policy);
+
+        let installed_checksum: Option<Value> = self
+            .es_conn
+            .get_index_template(spec.name())
+            .await?
+            .map(|template| template["_meta"]["checksum"].clone());
+
+        if installed_checksum.as_ref() == Some(&template_body["_meta"]["checksum"]) {
+            info!(
+                "[IndexSetupServiceImpl->install_template] [{}] Index template '{}' is up to date",
+                self.es_conn.output_name(),
+                spec.name()
+            );
+            return Ok(());
+        }
+
+        self.es_conn
+            .put_index_template(spec.name(), &template_body)
+            .await?;
+
+        info!(
+            "[IndexSetupServiceImpl->install_template] [{}] Index template '{}' {} for {:?}",
+            self.es_conn.output_name(),
+            spec.name(),
+            if installed_checksum.is_some() { "updated" } else { "installed" },
+            spec.index_patterns()
+        );
+
+        Ok(())
+    }
+
+    #[doc = "설정에 선언된 ILM policy 를 설치/갱신해주는 함수 - 이미 같은 내용이면 건너뛴다."]
+    /// # Arguments
+    /// * `ilm_config` - lifecycle policy 설정
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn install_ilm_policy(&self, ilm_config: &IlmConfig) -> Result<(), anyhow::Error> {
+        let policy_name: &str = ilm_config.policy_name();
+        let policy_body: Value = ilm_config.to_policy_body(self.es_conn.data_stream_config().is_some());
+
+        let installed_checksum: Option<Value> = self
+            .es_conn
+            .get_ilm_policy(policy_name)
+            .await?
+            .map(|policy| policy["_meta"]["checksum"].clone());
+
+        if installed_checksum.as_ref() == Some(&policy_body["policy"]["_meta"]["checksum"]) {
+            info!(
+                "[IndexSetupServiceImpl->install_ilm_policy] [{}] ILM policy '{}' is up to date",
+                self.es_conn.output_name(),
+                policy_name
+            );
+            return Ok(());
+        }
+
+        self.es_conn.put_ilm_policy(policy_name, &policy_body).await?;
+
+        info!(
+            "[IndexSetupServiceImpl->install_ilm_policy] [{}] ILM policy '{}' {}",
+            self.es_conn.output_name(),
+            policy_name,
+            if installed_checksum.is_some() { "updated" } else { "installed" }
+        );
+
+        Ok(())
+    }
+
+    #[doc = "OpenSearch 용 ISM policy 를 설치/갱신해주는 함수 - 이미 같은 내용이면 건너뛴다."]
+    /// OpenSearch 는 템플릿의 index.lifecycle.name 을 지원하지 않으므로, policy 의 ism_template 으로 대상 인덱스에 연결한다.
+    ///
+    /// # Arguments
+    /// * `ilm_config` - lifecycle policy 설정
+    /// * `index_patterns` - policy 를 적용할 인덱스(또는 data stream) 패턴
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn install_ism_policy(&self, ilm_config: &IlmConfig, index_patterns: &[String]) -> Result<(), anyhow::Error> {
+        let policy_name: &str = ilm_config.policy_name();
+        let policy_body: Value = ilm_config.to_ism_policy_body(
+            self.es_conn.data_stream_config().is_some(),
+            index_patterns,
+            *self.es_conn.template_config().priority(),
+        );
+
+        let installed: Option<Value> = self.es_conn.get_ism_policy(policy_name).await?;
+
+        if let Some(installed) = &installed {
+            if installed["policy"]["description"] == policy_body["policy"]["description"] {
+                info!(
+                    "[IndexSetupServiceImpl->install_ism_policy] [{}] ISM policy '{}' is up to date",
+                    self.es_conn.output_name(),
+                    policy_name
+                );
+                return Ok(());
+            }
+        }
+
+        let if_match: Option<(i64, i64)> = installed.as_ref().and_then(|installed| {
+            Some((installed["_seq_no"].as_i64()?, installed["_primary_term"].as_i64()?))
+        });
+
+        self.es_conn
+            .put_ism_policy(policy_name, &policy_body, if_match)
+            .await?;
+
+        info!(
+            "[IndexSetupServiceImpl->install_ism_policy] [{}] ISM policy '{}' {} for {:?}",
+            self.es_conn.output_name(),
+            policy_name,
+            if installed.is_some() { "updated" } else { "installed" },
+            index_patterns
+        );
+
+        Ok(())
+    }
+
+    #[doc = "기존 인덱스의 mapping 이 템플릿과 다른 필드를 찾아서 알려주는 함수"]
+    /// # Arguments
+    /// * `spec` - 비교 기준 템플릿 정의
+    ///
+    /// # Returns
+    /// * Result<(), anyhow::Error>
+    async fn report_mapping_conflicts(&self, spec: &IndexTemplateSpec) -> Result<(), anyhow::Error> {
+        let mut expected_types: HashMap<String, String> = HashMap::new();
+        collect_field_types(&spec.mappings()["properties"], "", &mut expected_types);
+
+        for index_pattern in spec.index_patterns() {
+            let index_mappings: Value = self.es_conn.get_mappings(index_pattern).await?;
+            let Some(index_mappings) = index_mappings.as_object() else {
+                continue;
+            };
+
+            for (index_name, index_mapping) in index_mappings {
+                let mut actual_types: HashMap<String, String> = HashMap::new();
+                collect_field_types(&index_mapping["mappings"]["properties"], "", &mut actual_types);
+
+                let mut conflicts: Vec<String> = actual_types
+                    .iter()
+                    .filter_map(|(field, actual_type)| {
+                        let expected_type: &String = expected_types.get(field)?;
+                        (type_family(expected_type) != type_family(actual_type))
+                            .then(|| format!("{} ({} -> expected {})", field, actual_type, expected_type))
+                    })
+                    .collect();
+
+                if !conflicts.is_empty() {
+                    conflicts.sort();
+                    warn!(
+                        "[WARN][IndexSetupServiceImpl->report_mapping_conflicts] [{}] Index '{}' has mapping conflicts with template '{}': {}",
+                        self.es_conn.output_name(),
+                        index_name,
+                        spec.name(),
+                        conflicts.join(", ")
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl IndexSetupService for IndexSetupServiceImpl {
+    #[doc = "ILM policy 와 문서 종류별 index template 을 설치하고, 기존 인덱스와의 mapping 충돌을 점검해주는 함수"]
+    async fn setup_index_templates(&self) -> Result<(), anyhow::Error> {
+        let flavour: EsFlavour = self.es_conn.resolve_flavour().await?;
+        let specs: Vec<IndexTemplateSpec> = self.template_specs();
+
+        /* 템플릿이 참조하므로 lifecycle policy 를 먼저 설치 - OpenSearch 는 템플릿 대신 ISM policy 가 인덱스를 지정한다. */
+        let ilm_policy: Option<&str> = match (self.es_conn.ilm_config(), flavour) {
+            (Some(ilm_config), EsFlavour::OpenSearch) => {
+                let index_patterns: Vec<String> = specs
+                    .iter()
+                    .flat_map(|spec| spec.index_patterns().clone())
+                    .collect();
+                self.install_ism_policy(ilm_config, &index_patterns).await?;
+                None
+            }
+            (Some(ilm_config), _) => {
+                self.install_ilm_policy(ilm_config).await?;
+                Some(ilm_config.policy_name())
+            }
+            (None, _) => None,
+        };
+
+        if !self.es_conn.template_config().enabled() {
+            info!(
+                "[IndexSetupServiceImpl->setup_index_templates] [{}] Index template setup is disabled",
+                self.es_conn.output_name()
+            );
+            return Ok(());
+        }
+
+        for spec in specs {
+            self.install_template(&spec, ilm_policy).await?;
+
+            /* 충돌 점검은 보고용이므로 실패해도 템플릿 설치 결과에는 영향을 주지 않는다. */
+            if let Err(e) = self.report_mapping_conflicts(&spec).await {
+                warn!("[WARN][IndexSetupServiceImpl->setup_index_templates] {:?}", e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "data stream 필수 필드인 @timestamp 를 mapping 에 추가해주는 함수"]
+fn with_timestamp_field(mut mappings: Value) -> Value {
+    mappings["properties"]["@timestamp"] = json!({ "type": "date" });
+    mappings
+}
+
+#[doc = "두 mapping 의 properties 와 dynamic_templates 를 합쳐주는 함수"]
+fn merge_mappings(base: &Value, other: &Value) -> Value {
+    let mut merged: Value = base.clone();
+
+    if let (Some(merged_props), Some(other_props)) = (
+        merged["properties"].as_object_mut(),
+        other["properties"].as_object(),
+    ) {
+        for (field, mapping) in other_props {
+            merged_props.entry(field.clone()).or_insert_with(|| mapping.clone());
+        }
+    }
+
+    if let Some(other_templates) = other["dynamic_templates"].as_array() {
+        if !merged["dynamic_templates"].is_array() {
+            merged["dynamic_templates"] = json!([]);
+        }
+
+        if let Some(merged_templates) = merged["dynamic_templates"].as_array_mut() {
+            for template in other_templates {
+                if !merged_templates.contains(template) {
+                    merged_templates.push(template.clone());
+                }
+            }
+        }
+    }
+
+    merged
+}
+
+#[doc = "mapping 의 properties 를 '필드경로 -> 타입' 형태로 펼쳐주는 함수"]
+fn collect_field_types(properties: &Value, prefix: &str, field_types: &mut HashMap<String, String>) {
+    let Some(properties) = properties.as_object() else {
+        return;
+    };
+
+    for (field, mapping) in properties {
+        let path: String = if prefix.is_empty() {
+            field.clone()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match mapping["type"].as_str() {
+            Some(field_type) if field_type != "object" && field_type != "nested" => {
+                field_types.insert(path, field_type.to_string());
+            }
+            _ => collect_field_types(&mapping["properties"], &path, field_types),
+        }
+    }
+}
+
+#[doc = "정수/실수 타입끼리의 차이(ex. float/double)는 충돌로 보지 않도록 타입을 묶어주는 함수"]
+fn type_family(field_type: &str) -> &str {
+    match field_type {
+        "long" | "integer" | "short" | "byte" | "unsigned_long" => "integer",
+        "float" | "double" | "half_float" | "scaled_float" => "floating",
+        "date" | "date_nanos" => "date",
+        other => other,
+    }
+}
@@ -0,0 +1,221 @@
+use crate::common::*;
+
+use crate::common_enums::influx_protocol::*;
+
+use crate::model::exporter::{
+    counter_totals::*, influx_config::*, influx_line::*, metric_snapshot::*,
+};
+
+use crate::traits::metric_exporter_service::*;
+
+use crate::utils_module::compress_utils::*;
+use crate::utils_module::http_utils::*;
+
+use tokio::net::UdpSocket;
+
+#[doc = "수집 결과를 InfluxDB line protocol 로 보내주는 출력 (HTTP /api/v2/write 또는 UDP)"]
+#[derive(Clone, Debug)]
+pub struct InfluxExporterServiceImpl {
+    sender: mpsc::Sender<MetricSnapshot>,
+}
+
+impl InfluxExporterServiceImpl {
+    #[doc = "수집 결과를 InfluxDB 로 보내주는 백그라운드 작업을 띄우고 핸들을 반환하는 함수"]
+    /// # Arguments
+    /// * `influx_config` - 전송 방식, 주소, org/bucket, token, tag, 재시도 설정
+    ///
+    /// # Returns
+    /// * Result<InfluxExporterServiceImpl, anyhow::Error>
+    pub fn new(influx_config: InfluxConfig) -> Result<Self, anyhow::Error> {
+        let transport: InfluxTransport = match influx_config.protocol() {
+            InfluxProtocol::Http => {
+                let mut headers: HeaderMap = HeaderMap::new();
+                headers.insert(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("text/plain; charset=utf-8"),
+                );
+                if *influx_config.compression() {
+                    headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+                }
+                if let Some(token) = influx_config.token() {
+                    headers.insert(
+                        AUTHORIZATION,
+                        HeaderValue::from_str(&format!("Token {}", token.resolve()?))?,
+                    );
+                }
+
+                let client: reqwest::Client = reqwest::Client::builder()
+                    .timeout(Duration::from_secs(*influx_config.timeout_secs()))
+                    .user_agent(concat!("os_metric_beats/", env!("CARGO_PKG_VERSION")))
+                    .default_headers(headers)
+                    .build()?;
+
+                /* reverse proxy 뒤의 base path(ex. http://gw/influx) 를 유지하도록 path 뒤에 덧붙인다. */
+                let mut write_url: Url = Url::parse(influx_config.url())?;
+                write_url
+                    .path_segments_mut()
+                    .map_err(|_| anyhow!("[ERROR][InfluxExporterServiceImpl->new] Invalid url: {}", influx_config.url()))?
+                    .pop_if_empty()
+                    .extend(["api", "v2", "write"]);
+                write_url
+                    .query_pairs_mut()
+                    .append_pair("org", influx_config.org())
+                    .append_pair("bucket", influx_config.bucket())
+                    .append_pair("precision", "ms");
+
+                info!(
+                    "[InfluxExporterServiceImpl->new] Writing metrics to {}",
+                    write_url
+                );
+                InfluxTransport::Http { client, write_url }
+            }
+            InfluxProtocol::Udp => {
+                /* 연결 없는 소켓이므로 여기서 바인드해두고, 대상 주소는 전송할때마다 지정한다. */
+                let socket: std::net::UdpSocket = std::net::UdpSocket::bind("0.0.0.0:0")?;
+                socket.set_nonblocking(true)?;
+
+                info!(
+                    "[InfluxExporterServiceImpl->new] Writing metrics to udp://{}",
+                    influx_config.udp_addr()
+                );
+                InfluxTransport::Udp {
+                    socket: UdpSocket::from_std(socket)?,
+                }
+            }
+        };
+
+        let (sender, receiver) = mpsc::channel::<MetricSnapshot>(*influx_config.queue_capacity());
+
+        let influx_worker: InfluxWorker = InfluxWorker {
+            transport,
+            influx_config,
+            counter_totals: CounterTotals::default(),
+        };
+        tokio::spawn(influx_worker.run(receiver));
+
+        Ok(InfluxExporterServiceImpl { sender })
+    }
+}
+
+impl MetricExporterService for InfluxExporterServiceImpl {
+    fn name(&self) -> &'static str {
+        "influx"
+    }
+
+    #[doc = "수집 결과를 전송 큐에 넣어주는 함수 - 전송이 끝날때까지 기다리지 않는다."]
+    fn export(&self, snapshot: &MetricSnapshot) -> Result<(), anyhow::Error> {
+        self.sender.try_send(snapshot.clone()).map_err(|e| {
+            anyhow!(
+                "[ERROR][InfluxExporterServiceImpl->export] Failed to enqueue metrics: {}",
+                e
+            )
+        })
+    }
+}
+
+#[doc = "line protocol 을 실제로 보내는 통로"]
+enum InfluxTransport {
+    Http {
+        client: reqwest::Client,
+        write_url: Url,
+    },
+    Udp {
+        socket: UdpSocket,
+    },
+}
+
+#[doc = "전송 큐를 소비하면서 수집 주기마다 line protocol 을 보내는 백그라운드 작업"]
+struct InfluxWorker {
+    transport: InfluxTransport,
+    influx_config: InfluxConfig,
+    counter_totals: CounterTotals,
+}
+
+impl InfluxWorker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<MetricSnapshot>) {
+        while let Some(snapshot) = receiver.recv().await {
+            /* counter 는 Telegraf 와 같이 에이전트 시작 이후의 누적값으로 기록한다. */
+            let lines: Vec<String> = render_line_protocol(
+                &self.counter_totals.accumulate(&snapshot.samples),
+                &snapshot.host,
+                self.influx_config.tags(),
+                snapshot.timestamp_ms,
+            );
+
+            let result: Result<(), anyhow::Error> = match &self.transport {
+                InfluxTransport::Http { client, write_url } => {
+                    self.write_http(client, write_url, &lines).await
+                }
+                InfluxTransport::Udp { socket } => self.write_udp(socket, &lines).await,
+            };
+
+            match result {
+                Ok(()) => info!(
+                    "[InfluxWorker->run] Influx write completed: {} lines",
+                    lines.len()
+                ),
+                Err(e) => error!(
+                    "[ERROR][InfluxWorker->run] Dropped {} lines: {:?}",
+                    lines.len(),
+                    e
+                ),
+            }
+        }
+    }
+
+    #[doc = "line protocol 을 datagram 크기 이하로 줄 단위로 나눠서 UDP 로 보내주는 함수 - 재시도하지 않는다."]
+    async fn write_udp(&self, socket: &UdpSocket, lines: &[String]) -> Result<(), anyhow::Error> {
+        let max_bytes: usize = *self.influx_config.udp_max_datagram_bytes();
+        let mut datagram: String = String::new();
+
+        for line in lines {
+            if line.len() + 1 > max_bytes {
+                warn!(
+                    "[WARN][InfluxWorker->write_udp] Line exceeds udp_max_datagram_bytes ({} bytes), skipped",
+                    line.len()
+                );
+                continue;
+            }
+
+            if datagram.len() + line.len() + 1 > max_bytes {
+                socket
+                    .send_to(datagram.as_bytes(), self.influx_config.udp_addr())
+                    .await?;
+                datagram.clear();
+            }
+
+            datagram.push_str(line);
+            datagram.push('\n');
+        }
+
+        if !datagram.is_empty() {
+            socket
+                .send_to(datagram.as_bytes(), self.influx_config.udp_addr())
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    #[doc = "line protocol 을 /api/v2/write 로 보내고, 429/502/503/504 와 연결 오류는 backoff 후 재시도해주는 함수"]
+    async fn write_http(
+        &self,
+        client: &reqwest::Client,
+        write_url: &Url,
+        lines: &[String],
+    ) -> Result<(), anyhow::Error> {
+        let mut body: Vec<u8> = lines.join("\n").into_bytes();
+        if *self.influx_config.compression() {
+            body = gzip_compress(&body, 6)?;
+        }
+
+        post_with_retry(
+            client,
+            write_url.as_str(),
+            body,
+            self.influx_config.retry(),
+            "InfluxWorker->write_http",
+        )
+        .await
+    }
+}
@@ -10,21 +10,44 @@ use crate::model::{
         iface_counters::*, net_state::*, network_socket_info::*,
         network_usage::*,
     },
-    network_packet::{packet_state::*, network_packet_info::*},
-    memory::os_mem_res::*
+    network_packet::{iface_counters::IfaceCounters as PacketIfaceCounters, packet_state::*, network_packet_info::*},
+    jvm::gc_log_stats::*,
+    memory::{mmap_info::*, os_mem_res::*, process_mem_res::*, process_mmap_info::*},
+    process::{thread_pool_info::*, thread_pool_usage::*, thread_state_count::*},
+    slowlog::{slowlog_entry::*, slowlog_stats::*},
 };
 
 use crate::traits::metirc_service::*;
 
+use crate::utils_module::file_tail_utils::*;
 use crate::utils_module::io_utils::*;
 use crate::utils_module::math_utils::*;
+use crate::utils_module::thread_dump_utils::*;
 use crate::utils_module::time_utils::*;
 
+#[doc = "Elasticsearch 관련 프로세스를 찾기 위한 키워드"]
+const ES_PROCESS_KEYWORDS: [&str; 3] = ["java", "jdk", "elasticsearch"];
+
+#[doc = "/proc 에서 사용하는 clock tick 단위 (USER_HZ) - 커널 ABI 상 100 으로 고정되어 있음"]
+const USER_HZ: u64 = 100;
+
 #[derive(Debug, Getters)]
 #[getset(get = "pub")]
 pub struct LinuxMetricServiceImpl {
     system: System,
     linux_config: LinuxConfig,
+    prev_map_counts: HashMap<u32, u64>,
+    /* vm.max_map_count 를 읽지 못한 경우 매 수집마다 같은 로그를 남기지 않도록 한 번만 경고한다. */
+    max_map_count_warned: bool,
+    prev_thread_ticks: HashMap<(u32, u32), u64>,
+    prev_thread_sampled_at: Option<Instant>,
+    /* (pid, tid) -> 스레드 덤프에서 찾은 전체 이름 (덤프에 없던 native 스레드는 None) */
+    jvm_thread_names: HashMap<(u32, u32), Option<String>>,
+    thread_dumped_at: HashMap<u32, Instant>,
+    /* pid -> 실행 중인 스레드 덤프 - jcmd 는 수초가 걸릴 수 있어 수집 경로(tokio worker)를 막지 않도록 별도 스레드에서 뜬다. */
+    thread_dumps: HashMap<u32, ThreadDumpHandle>,
+    gc_log_tailer: Option<FileTailer>,
+    slowlog_tailers: Vec<FileTailer>,
 }
 
 impl Default for LinuxMetricServiceImpl {
@@ -45,12 +68,64 @@ impl LinuxMetricServiceImpl {
         let mut system: System = System::new_all();
         system.refresh_all();
 
+        /* gc.log 경로가 설정된 경우에만 GC 로그를 수집한다. */
+        let gc_log_tailer: Option<FileTailer> = linux_config
+            .gc_log_path()
+            .as_ref()
+            .map(|path| FileTailer::new(path));
+
+        /* search/indexing slow log 파일 */
+        let slowlog_tailers: Vec<FileTailer> = linux_config
+            .slowlog_paths()
+            .iter()
+            .map(|path| FileTailer::new(path))
+            .collect();
+
+        /* comm 은 15 byte 로 잘려서(`elasticsearch[n`) pool 이름을 알 수 없으므로 pool 별 집계에는 jcmd 가 필요하다. */
+        if linux_config.jcmd_path().is_none() {
+            info!("[LinuxMetricServiceImpl->new] jcmd_path is not set, Elasticsearch threads are not split by thread pool");
+        }
+
         LinuxMetricServiceImpl {
             system,
             linux_config,
+            prev_map_counts: HashMap::new(),
+            max_map_count_warned: false,
+            prev_thread_ticks: HashMap::new(),
+            prev_thread_sampled_at: None,
+            jvm_thread_names: HashMap::new(),
+            thread_dumped_at: HashMap::new(),
+            thread_dumps: HashMap::new(),
+            gc_log_tailer,
+            slowlog_tailers,
         }
     }
 
+    #[doc = "Elasticsearch 관련 프로세스들의 (pid, 이름) 목록을 pid 순서로 반환해주는 함수 - 프로세스 목록은 refresh_processes 에서 갱신된다."]
+    fn get_target_processes(&self) -> Vec<(u32, String)> {
+        let mut target_processes: Vec<(u32, String)> = self
+            .system
+            .processes()
+            .iter()
+            .filter(|(_pid, proc_)| {
+                let name_lower: String = proc_.name().to_lowercase();
+                ES_PROCESS_KEYWORDS.iter().any(|kw| name_lower.contains(kw))
+            })
+            .map(|(pid, proc_)| (pid.as_u32(), proc_.name().to_string()))
+            .collect();
+
+        target_processes.sort_by_key(|(pid, _name)| *pid);
+        target_processes
+    }
+
+    #[doc = "Elasticsearch 관련 프로세스들의 pid 목록을 반환해주는 함수"]
+    fn get_target_pids(&self) -> Vec<u32> {
+        self.get_target_processes()
+            .into_iter()
+            .map(|(pid, _name)| pid)
+            .collect()
+    }
+
     /*========================================================================================*/
     /*=================================== NETWORK NET STATE===================================*/
     /*========================================================================================*/
@@ -143,14 +218,23 @@ impl LinuxMetricServiceImpl {
         let eth_tx_delta: u64 = cur_eth.tx - prev_eth.tx;
         
         /* 결과 반환 */
-        NetworkUsage::new(
+        let mut network_usage: NetworkUsage = NetworkUsage::new(
             total_rx_delta,
             total_tx_delta,
             loop_rx_delta,
             loop_tx_delta,
             eth_rx_delta,
             eth_tx_delta,
-        )
+        );
+
+        /* 인터페이스 별 값은 exporter 가 counter 로 내보낼 수 있도록 커널 누적값 그대로 둔다. */
+        network_usage.interfaces = cur
+            .ifaces
+            .iter()
+            .map(|(iface, cur_c)| (iface.clone(), cur_c.clone()))
+            .collect();
+
+        network_usage
     }
 
     /*======================================================================================*/
@@ -201,6 +285,7 @@ impl LinuxMetricServiceImpl {
         let mut send_dropped_packets: u64 = 0; 
         let mut recv_errors_packet: u64 = 0; 
         let mut send_errors_packet: u64 = 0; 
+        let mut interfaces: BTreeMap<String, PacketIfaceCounters> = BTreeMap::new();
         
         for network in network_tx_rx_list {
             
@@ -208,10 +293,18 @@ impl LinuxMetricServiceImpl {
             let cur_iface_counter = cur.get_iface(network);
 
             if let(Some(prev_c), Some(cur_c)) = (prev_iface_counter, cur_iface_counter) {
-                recv_dropped_packets += cur_c.rx_dropped.saturating_sub(prev_c.rx_dropped);
-                send_dropped_packets += cur_c.tx_dropped.saturating_sub(prev_c.tx_dropped);
-                recv_errors_packet += cur_c.rx_errors.saturating_sub(prev_c.rx_errors);
-                send_errors_packet += cur_c.tx_errors.saturating_sub(prev_c.tx_errors);
+                let iface_delta: PacketIfaceCounters = PacketIfaceCounters::new(
+                    cur_c.rx_dropped.saturating_sub(prev_c.rx_dropped),
+                    cur_c.tx_dropped.saturating_sub(prev_c.tx_dropped),
+                    cur_c.rx_errors.saturating_sub(prev_c.rx_errors),
+                    cur_c.tx_errors.saturating_sub(prev_c.tx_errors),
+                );
+                recv_dropped_packets += iface_delta.rx_dropped;
+                send_dropped_packets += iface_delta.tx_dropped;
+                recv_errors_packet += iface_delta.rx_errors;
+                send_errors_packet += iface_delta.tx_errors;
+                /* 인터페이스 별 값은 커널 누적값 그대로 exporter 로 넘긴다. */
+                interfaces.insert(network.clone(), cur_c.clone());
             } else {
                 error!(
                     "[ERROR][LinuxMetricServiceImpl->calculate_network_packet_delta] missing iface counter: prev={:?}, cur={:?}, iface={}",
@@ -220,7 +313,10 @@ impl LinuxMetricServiceImpl {
             }
         }   
 
-        NetworkPacketInfo::new(recv_dropped_packets, send_dropped_packets, recv_errors_packet, send_errors_packet)
+        let mut network_packet_info: NetworkPacketInfo =
+            NetworkPacketInfo::new(recv_dropped_packets, send_dropped_packets, recv_errors_packet, send_errors_packet);
+        network_packet_info.interfaces = interfaces;
+        network_packet_info
     }
     
 
@@ -267,6 +363,146 @@ impl LinuxMetricServiceImpl {
     fn count_udp_sockets(&self, content: &str) -> i32 {
         content.lines().skip(1).count() as i32
     }
+
+    /*======================================================================================*/
+    /*===================================== MMAP COUNT =====================================*/
+    /*======================================================================================*/
+    #[doc = "/proc/<pid>/maps 의 라인수(= 메모리 매핑 개수)를 세어주는 함수"]
+    fn read_proc_map_count(&self, pid: u32) -> Result<u64, anyhow::Error> {
+        let maps: Vec<u8> = fs::read(format!("/proc/{}/maps", pid))?;
+        Ok(maps.iter().filter(|b| **b == b'\n').count() as u64)
+    }
+
+    /*======================================================================================*/
+    /*================================== THREAD POOL CPU ===================================*/
+    /*======================================================================================*/
+    #[doc = "스레드 이름을 thread pool 그룹 이름으로 변환해주는 함수"]
+    /// pool 이름은 스레드 덤프에서 구한 전체 이름(`elasticsearch[node][search][T#3]`)에서만 꺼낼 수 있다.
+    /// comm 은 커널에서 15 byte 로 잘리므로(TASK_COMM_LEN, ex. `elasticsearch[n`) ES 스레드는 모두
+    /// `elasticsearch` 로 묶이고, JVM/GC/JIT 스레드만 구분된다.
+    ///
+    /// # Arguments
+    /// * `comm` - 스레드 덤프의 스레드 이름, 없으면 /proc/<pid>/task/<tid>/comm 의 내용
+    ///
+    /// # Returns
+    /// * String - thread pool 그룹 이름
+    fn classify_thread_pool(&self, comm: &str) -> String {
+        let comm: &str = comm.trim();
+
+        if comm.starts_with("elasticsearch[") {
+            let segments: Vec<&str> = comm
+                .split(['[', ']'])
+                .filter(|seg| !seg.is_empty())
+                .collect();
+
+            /* segments = ["elasticsearch", "node", "search", "T#3"] */
+            return match segments.get(2) {
+                Some(pool) if !pool.starts_with("T#") => pool.replace('.', "_"),
+                _ => String::from("elasticsearch"),
+            };
+        }
+
+        if comm.starts_with("GC Thread") || comm.starts_with("G1 ") || comm.starts_with("GC ") {
+            return String::from("gc");
+        }
+
+        if comm.starts_with("C1 CompilerThre") || comm.starts_with("C2 CompilerThre") {
+            return String::from("jit_compiler");
+        }
+
+        if comm.starts_with("VM ") {
+            return String::from("jvm");
+        }
+
+        /* 그 외의 스레드는 번호(#n, 숫자)를 제거한 이름으로 묶어준다. */
+        let base_name: String = comm
+            .split('#')
+            .next()
+            .unwrap_or(comm)
+            .trim_end_matches(|c: char| c.is_ascii_digit() || c == '-' || c == '_' || c == ' ')
+            .to_lowercase()
+            .replace([' ', '.'], "_");
+
+        if base_name.is_empty() {
+            String::from("other")
+        } else {
+            base_name
+        }
+    }
+
+    #[doc = "새로 생긴 스레드가 있으면 jcmd 스레드 덤프로 해당 프로세스의 전체 스레드 이름을 갱신해주는 함수"]
+    /// 덤프는 JVM safepoint 를 유발하므로 프로세스마다 `thread_dump_interval_secs` 에 한번만 뜬다.
+    /// 덤프는 별도 스레드에서 뜨고 결과는 끝난 뒤의 수집 주기에 반영되며, 그 전까지는 comm 이름으로 묶는다.
+    ///
+    /// # Arguments
+    /// * `pid` - 대상 JVM 의 pid
+    /// * `tids` - 현재 살아있는 스레드 id 목록
+    fn refresh_jvm_thread_names(&mut self, pid: u32, tids: &[u32]) {
+        let jcmd_path: &str = match self.linux_config.jcmd_path() {
+            Some(jcmd_path) => jcmd_path,
+            None => return,
+        };
+
+        /* 이전 주기에 띄운 덤프가 끝났으면 결과를 반영하고, 아직 실행 중이면 기다리지 않는다. */
+        if let Some(thread_dump) = self.thread_dumps.remove(&pid) {
+            if !thread_dump.is_finished() {
+                self.thread_dumps.insert(pid, thread_dump);
+                return;
+            }
+
+            match thread_dump.join() {
+                Ok(Ok(mut thread_names)) => {
+                    for tid in tids {
+                        self.jvm_thread_names.insert((pid, *tid), thread_names.remove(tid));
+                    }
+                }
+                Ok(Err(e)) => {
+                    warn!("[WARN][LinuxMetricServiceImpl->refresh_jvm_thread_names] {:?}", e);
+                }
+                Err(_) => {
+                    error!("[ERROR][LinuxMetricServiceImpl->refresh_jvm_thread_names] Thread dump worker panicked for pid={}", pid);
+                }
+            }
+        }
+
+        if tids.iter().all(|tid| self.jvm_thread_names.contains_key(&(pid, *tid))) {
+            return;
+        }
+
+        let dump_interval: Duration = Duration::from_secs(*self.linux_config.thread_dump_interval_secs());
+        if let Some(dumped_at) = self.thread_dumped_at.get(&pid) {
+            if dumped_at.elapsed() < dump_interval {
+                return;
+            }
+        }
+        self.thread_dumped_at.insert(pid, Instant::now());
+
+        let jcmd_path: String = jcmd_path.to_string();
+        match std::thread::Builder::new()
+            .name(String::from("jcmd-thread-dump"))
+            .spawn(move || read_jvm_thread_names(&jcmd_path, pid))
+        {
+            Ok(thread_dump) => {
+                self.thread_dumps.insert(pid, thread_dump);
+            }
+            Err(e) => {
+                warn!("[WARN][LinuxMetricServiceImpl->refresh_jvm_thread_names] Failed to start thread dump: {:?}", e);
+            }
+        }
+    }
+
+    #[doc = "/proc/<pid>/task/<tid>/stat 에서 스레드 상태와 누적 CPU tick(utime + stime)을 파싱해주는 함수"]
+    fn parse_task_stat(&self, stat: &str) -> Option<(char, u64)> {
+        /* comm 에 공백이나 괄호가 들어갈 수 있으므로 마지막 ')' 이후부터 파싱한다. */
+        let (_, rest) = stat.rsplit_once(')')?;
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+
+        let state: char = fields.first()?.chars().next()?;
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+
+        Some((state, utime + stime))
+    }
 }
 
 
@@ -333,6 +569,25 @@ impl MetricService for LinuxMetricServiceImpl {
         0.0
     }
 
+    #[doc = "마운트 지점 별 디스크 사용률을 수집해주는 함수 - exporter 에서 mount label 로 내보낸다."]
+    fn get_disk_usage_by_mount(&mut self) -> BTreeMap<String, f64> {
+        self.system.refresh_disks_list();
+
+        self.system
+            .disks()
+            .iter()
+            .filter(|disk| disk.total_space() > 0)
+            .filter_map(|disk| {
+                let mount: &str = disk.mount_point().to_str()?;
+                let total_space: f64 = disk.total_space() as f64;
+                let used_space: f64 = total_space - disk.available_space() as f64;
+                let usage_percentage: f64 = (used_space / total_space) * 100.0;
+
+                Some((mount.to_string(), round2_f64(usage_percentage.clamp(0.0, 100.0))))
+            })
+            .collect()
+    }
+
     #[doc = "시스템 메모리 사용률을 수집해주는 함수"]
     fn get_memory_usage(&mut self) -> f32 {
         self.system.refresh_memory();
@@ -368,9 +623,13 @@ impl MetricService for LinuxMetricServiceImpl {
         Ok(cur_network_usage)
     }
 
+    #[doc = "프로세스 목록을 갱신해주는 함수 - 수집 주기마다 한 번 호출된다."]
+    fn refresh_processes(&mut self) {
+        self.system.refresh_processes();
+    }
+
     #[doc = "현재 시스템의 프로세스의 개수를 반환해주는 함수"]
     fn get_process_count(&mut self) -> usize {
-        self.system.refresh_processes();
         self.system.processes().len()
     }
 
@@ -455,24 +714,203 @@ impl MetricService for LinuxMetricServiceImpl {
     
     #[doc = "Elasticsearch 관련 프로세스가 메모리를 얼마나 사용하는지 체크해주는 함수"]
     fn get_process_mem_usage(&mut self) -> Result<OsMemRes, anyhow::Error> {
-
-        let target_keywords: [&str; 3] = ["java", "jdk", "elasticsearch"];
-
-        self.system.refresh_all();
-
         let mut total_rss_byte: u64 = 0;
         let mut total_vms_byte: u64 = 0;
+        let mut processes: Vec<ProcessMemRes> = Vec::new();
 
-        for proc_ in self.system.processes().values() {
+        for (pid, proc_) in self.system.processes() {
             let name_lower: String = proc_.name().to_lowercase();
 
-            if target_keywords.iter().any(|kw| name_lower.contains(&kw.to_lowercase())) {
+            if ES_PROCESS_KEYWORDS.iter().any(|kw| name_lower.contains(kw)) {
                 /* sysinfo: memory()와 virtual_memory()는 KiB 단위 */ 
                 total_rss_byte += proc_.memory();
                 total_vms_byte += proc_.virtual_memory();
+                processes.push(ProcessMemRes::new(
+                    pid.as_u32(),
+                    proc_.name().to_string(),
+                    proc_.memory(),
+                    proc_.virtual_memory(),
+                ));
+            }
+        }
+        processes.sort_by_key(|process| process.pid);
+
+        let mut os_mem_res: OsMemRes = OsMemRes::new(total_rss_byte, total_vms_byte);
+        os_mem_res.processes = processes;
+
+        Ok(os_mem_res)
+    }
+
+    #[doc = "Elasticsearch 관련 프로세스 별 mmap 개수를 vm.max_map_count 대비 계산해주는 함수"]
+    fn get_process_mmap_info(&mut self) -> Result<MmapInfo, anyhow::Error> {
+        /* 일부 컨테이너에서는 읽을 수 없으므로, 실패해도 수집을 멈추지 않고 한도를 0(사용률 0)으로 둔다. */
+        let max_map_count: u64 = match read_u64("/proc/sys/vm/max_map_count") {
+            Ok(max_map_count) => max_map_count,
+            Err(e) => {
+                if !self.max_map_count_warned {
+                    warn!("[WARN][LinuxMetricServiceImpl->get_process_mmap_info] failed to read vm.max_map_count, map count usage is reported as 0: {:?}", e);
+                    self.max_map_count_warned = true;
+                }
+                0
+            }
+        };
+
+        let mut cur_map_counts: HashMap<u32, u64> = HashMap::new();
+        let mut processes: Vec<ProcessMmapInfo> = Vec::new();
+
+        for (pid, name) in self.get_target_processes() {
+            let map_count: u64 = match self.read_proc_map_count(pid) {
+                Ok(map_count) => map_count,
+                Err(e) => {
+                    warn!("[WARN][LinuxMetricServiceImpl->get_process_mmap_info] failed to read maps of pid={}: {:?}", pid, e);
+                    continue;
+                }
+            };
+
+            /* 처음 본 프로세스는 비교할 이전 값이 없으므로 증감량을 0 으로 둔다. */
+            let prev_map_count: u64 = self.prev_map_counts.get(&pid).copied().unwrap_or(map_count);
+            let map_count_usage: f32 = if max_map_count == 0 {
+                0.0
+            } else {
+                round2_f32((map_count as f64 / max_map_count as f64) * 100.0)
+            };
+
+            cur_map_counts.insert(pid, map_count);
+            processes.push(ProcessMmapInfo::new(
+                pid,
+                name,
+                map_count,
+                map_count_usage,
+                map_count as i64 - prev_map_count as i64,
+            ));
+        }
+
+        self.prev_map_counts = cur_map_counts;
+
+        Ok(MmapInfo::new(max_map_count, processes))
+    }
+
+    #[doc = "Elasticsearch 프로세스의 스레드를 thread pool 별로 묶어 구간 CPU 사용시간과 상태별 개수를 계산해주는 함수"]
+    fn get_thread_pool_info(&mut self) -> Result<ThreadPoolInfo, anyhow::Error> {
+        let target_pids: Vec<u32> = self.get_target_pids();
+        let sampled_at: Instant = Instant::now();
+
+        let mut cur_thread_ticks: HashMap<(u32, u32), u64> = HashMap::new();
+        let mut thread_pools: HashMap<String, ThreadPoolUsage> = HashMap::new();
+        let mut thread_states: ThreadStateCount = ThreadStateCount::default();
+
+        /* 첫 수집에서는 비교할 이전 값이 없으므로 CPU 시간은 0 으로 둔다. */
+        let has_prev: bool = self.prev_thread_sampled_at.is_some();
+
+        for pid in target_pids {
+            let task_dir: fs::ReadDir = match fs::read_dir(format!("/proc/{}/task", pid)) {
+                Ok(task_dir) => task_dir,
+                Err(e) => {
+                    warn!("[WARN][LinuxMetricServiceImpl->get_thread_pool_info] failed to read tasks of pid={}: {:?}", pid, e);
+                    continue;
+                }
+            };
+
+            let tids: Vec<u32> = task_dir
+                .flatten()
+                .filter_map(|task| task.file_name().to_str().and_then(|t| t.parse().ok()))
+                .collect();
+
+            self.refresh_jvm_thread_names(pid, &tids);
+
+            for tid in tids {
+                let task_path: String = format!("/proc/{}/task/{}", pid, tid);
+
+                /* 스레드가 수집 도중 종료될 수 있으므로 읽기 실패는 건너뛴다. */
+                let (stat, comm) = match (
+                    fs::read_to_string(format!("{}/stat", task_path)),
+                    fs::read_to_string(format!("{}/comm", task_path)),
+                ) {
+                    (Ok(stat), Ok(comm)) => (stat, comm),
+                    _ => continue,
+                };
+
+                let (state, ticks) = match self.parse_task_stat(&stat) {
+                    Some(parsed) => parsed,
+                    None => continue,
+                };
+
+                let delta_ticks: u64 = if has_prev {
+                    match self.prev_thread_ticks.get(&(pid, tid)) {
+                        Some(prev_ticks) => ticks.saturating_sub(*prev_ticks),
+                        None => ticks, /* 이번 구간에 새로 생긴 스레드 */
+                    }
+                } else {
+                    0
+                };
+
+                let thread_name: &str = match self.jvm_thread_names.get(&(pid, tid)) {
+                    Some(Some(jvm_thread_name)) => jvm_thread_name,
+                    _ => &comm,
+                };
+
+                let pool_usage: &mut ThreadPoolUsage = thread_pools
+                    .entry(self.classify_thread_pool(thread_name))
+                    .or_default();
+                pool_usage.thread_count += 1;
+                pool_usage.cpu_time_ms += delta_ticks * 1000 / USER_HZ;
+
+                thread_states.add_state(state);
+                cur_thread_ticks.insert((pid, tid), ticks);
+            }
+        }
+
+        /* 구간 길이 대비 CPU 사용률 (코어 1개 = 100%) */
+        if let Some(prev_sampled_at) = self.prev_thread_sampled_at {
+            let interval_ms: u128 = sampled_at.duration_since(prev_sampled_at).as_millis();
+
+            if interval_ms > 0 {
+                for pool_usage in thread_pools.values_mut() {
+                    pool_usage.cpu_usage =
+                        round2_f32(pool_usage.cpu_time_ms as f64 / interval_ms as f64 * 100.0);
+                }
             }
         }
 
-        Ok(OsMemRes::new(total_rss_byte, total_vms_byte))
+        /* 종료된 스레드/프로세스의 이름은 버린다. */
+        self.jvm_thread_names.retain(|key, _| cur_thread_ticks.contains_key(key));
+        self.thread_dumped_at.retain(|pid, _| cur_thread_ticks.keys().any(|(cur_pid, _)| cur_pid == pid));
+        self.thread_dumps.retain(|pid, _| cur_thread_ticks.keys().any(|(cur_pid, _)| cur_pid == pid));
+
+        self.prev_thread_ticks = cur_thread_ticks;
+        self.prev_thread_sampled_at = Some(sampled_at);
+
+        Ok(ThreadPoolInfo::new(thread_pools, thread_states))
+    }
+
+    #[doc = "gc.log 에서 이번 구간에 발생한 GC pause 정보를 집계해주는 함수"]
+    fn get_gc_log_stats(&mut self) -> Result<GcLogStats, anyhow::Error> {
+        let gc_log_tailer: &mut FileTailer = match self.gc_log_tailer.as_mut() {
+            Some(gc_log_tailer) => gc_log_tailer,
+            None => return Ok(GcLogStats::default()),
+        };
+
+        let lines: Vec<String> = gc_log_tailer.read_new_lines().unwrap_or_else(|e| {
+            warn!("[WARN][LinuxMetricServiceImpl->get_gc_log_stats] failed to read gc log: {:?}", e);
+            Vec::new()
+        });
+
+        Ok(GcLogStats::from_lines(&lines))
+    }
+
+    #[doc = "slow log 파일에서 이번 구간에 발생한 slow query/indexing 을 인덱스 별로 집계해주는 함수"]
+    fn get_slowlog_stats(&mut self) -> Result<Vec<SlowLogStats>, anyhow::Error> {
+        let mut entries: Vec<SlowLogEntry> = Vec::new();
+
+        for slowlog_tailer in self.slowlog_tailers.iter_mut() {
+            let lines: Vec<String> = slowlog_tailer.read_new_lines().unwrap_or_else(|e| {
+                warn!("[WARN][LinuxMetricServiceImpl->get_slowlog_stats] failed to read slow log: {:?}", e);
+                Vec::new()
+            });
+
+            entries.extend(lines.iter().filter_map(|line| SlowLogEntry::parse_line(line)));
+        }
+
+        Ok(SlowLogStats::aggregate(entries))
     }
 }
\ No newline at end of file
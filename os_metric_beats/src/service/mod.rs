@@ -1,5 +1,13 @@
+pub mod bulk_shipper_service_impl;
+pub mod index_setup_service_impl;
+pub mod influx_exporter_service_impl;
 pub mod linux_metric_service_impl;
 //pub mod linux_process_service_impl;
+pub mod node_info_service_impl;
+pub mod otlp_exporter_service_impl;
+pub mod prometheus_exporter_service_impl;
+pub mod remote_write_exporter_service_impl;
 pub mod request_service_impl;
+pub mod retention_service_impl;
 pub mod windows_metirc_service_impl;
 // pub mod wmi_conn_service_impl;
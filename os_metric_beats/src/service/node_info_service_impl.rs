@@ -0,0 +1,184 @@
+use crate::common::*;
+
+use crate::env_configuration::env_config::*;
+
+use crate::model::{
+    elastic::{auth_config::*, es_node_identity::*},
+    system_config::*,
+};
+
+use crate::repository::{es_node_pool::*, es_repository::*};
+
+use crate::traits::node_info_service::*;
+
+use crate::utils_module::io_utils::*;
+
+#[doc = "노드 정보를 다시 조회하는 주기 (노드 이름/역할은 재시작 전에는 바뀌지 않음)"]
+const NODE_INFO_REFRESH_INTERVAL: Duration = Duration::from_secs(600);
+
+#[doc = "조회에 실패한 뒤 다시 시도하기까지의 간격 - 로컬 노드가 죽어있는 동안 매 수집마다 timeout 을 기다리지 않게 한다."]
+const NODE_INFO_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+#[doc = "노드 정보 조회 timeout - 수집 주기를 오래 막지 않도록 짧게 둔다."]
+const NODE_INFO_REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub struct NodeInfoServiceImpl {
+    /* 인증/TLS/transport 설정을 빌려 쓰는 출력과 그 설정으로 만든 로컬 노드 클라이언트 */
+    local_node: Option<(Arc<EsRepositoryPub>, EsClient)>,
+    /* local_es_id / local_es_pw 가 설정된 경우 출력의 인증 대신 사용 */
+    local_auth: Option<AuthConfig>,
+    node_identity: Option<EsNodeIdentity>,
+    next_refresh_at: Option<Instant>,
+}
+
+impl Default for NodeInfoServiceImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeInfoServiceImpl {
+    pub fn new() -> Self {
+        let system_config: SystemConfig = read_toml_from_file::<SystemConfig>(&SYSTEM_INFO)
+            .unwrap_or_else(|e| {
+                error!("[ERROR][NodeInfoServiceImpl->new] {:?}", e);
+                panic!("[ERROR][NodeInfoServiceImpl->new] {:?}", e);
+            });
+
+        let local_auth: Option<AuthConfig> = match (system_config.local_es_id(), system_config.local_es_pw()) {
+            (Some(es_id), Some(es_pw)) if !es_id.is_empty() => Some(AuthConfig::Basic {
+                username: es_id.clone(),
+                password: es_pw.clone(),
+            }),
+            _ => None,
+        };
+
+        /* local_es_url 이 설정되어 있지 않으면 노드 정보를 붙이지 않는다. */
+        let local_node: Option<(Arc<EsRepositoryPub>, EsClient)> = match system_config.local_es_url() {
+            Some(local_es_url) => match Self::create_local_node(
+                local_es_url,
+                system_config.local_es_output().as_deref(),
+                local_auth.as_ref(),
+            ) {
+                Ok(local_node) => Some(local_node),
+                Err(e) => {
+                    error!("[ERROR][NodeInfoServiceImpl->new] {:?}", e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        NodeInfoServiceImpl {
+            local_node,
+            local_auth,
+            node_identity: None,
+            next_refresh_at: None,
+        }
+    }
+
+    #[doc = "출력의 인증/TLS 설정으로 로컬 Elasticsearch 노드에 접속하기 위한 클라이언트를 생성해주는 함수"]
+    /// # Arguments
+    /// * `local_es_url` - 로컬 노드 주소
+    /// * `local_es_output` - 설정을 빌려 쓸 출력 이름 (없으면 첫 번째 출력)
+    /// * `local_auth` - 출력의 인증 대신 사용할 인증
+    ///
+    /// # Returns
+    /// * Result<(Arc<EsRepositoryPub>, EsClient), anyhow::Error>
+    fn create_local_node(
+        local_es_url: &str,
+        local_es_output: Option<&str>,
+        local_auth: Option<&AuthConfig>,
+    ) -> Result<(Arc<EsRepositoryPub>, EsClient), anyhow::Error> {
+        let es_conns: Vec<Arc<EsRepositoryPub>> = get_elastic_conns();
+
+        let es_conn: Arc<EsRepositoryPub> = match local_es_output {
+            Some(output_name) => es_conns
+                .into_iter()
+                .find(|es_conn| es_conn.output_name() == output_name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "[ERROR][NodeInfoServiceImpl->create_local_node] local_es_output '{}' is not a configured output",
+                        output_name
+                    )
+                })?,
+            None => es_conns.into_iter().next().ok_or_else(|| {
+                anyhow!("[ERROR][NodeInfoServiceImpl->create_local_node] No Elasticsearch output is configured")
+            })?,
+        };
+
+        let local_client: EsClient = es_conn.create_local_client(local_es_url, local_auth)?;
+
+        Ok((es_conn, local_client))
+    }
+
+    #[doc = "`_nodes/_local` API 로 로컬 노드 정보를 조회해주는 함수"]
+    async fn fetch_node_identity(
+        &self,
+        es_conn: &EsRepositoryPub,
+        local_client: &EsClient,
+    ) -> Result<EsNodeIdentity, anyhow::Error> {
+        let local_es_conn: Elasticsearch = es_conn.local_conn(local_client, self.local_auth.as_ref()).await?;
+
+        let response: Response = local_es_conn
+            .nodes()
+            .info(NodesInfoParts::NodeId(&["_local"]))
+            .filter_path(&[
+                "cluster_name",
+                "nodes.*.name",
+                "nodes.*.roles",
+                "nodes.*.attributes",
+            ])
+            .request_timeout(NODE_INFO_REQUEST_TIMEOUT)
+            .send()
+            .await?;
+
+        if !response.status_code().is_success() {
+            return Err(anyhow!(
+                "[ERROR][NodeInfoServiceImpl->fetch_node_identity] Status Code: {}",
+                response.status_code()
+            ));
+        }
+
+        let nodes_info: Value = response.json::<Value>().await?;
+
+        EsNodeIdentity::from_nodes_info(&nodes_info).ok_or_else(|| {
+            anyhow!(
+                "[ERROR][NodeInfoServiceImpl->fetch_node_identity] Unexpected response: {}",
+                nodes_info
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl NodeInfoService for NodeInfoServiceImpl {
+    #[doc = "캐시된 로컬 노드 정보를 반환해주는 함수 - 캐시가 없거나 오래된 경우에만 API 를 호출하고, 실패하면 잠시 뒤에 다시 시도한다."]
+    async fn get_node_identity(&mut self) -> Option<EsNodeIdentity> {
+        let (es_conn, local_client) = self.local_node.as_ref()?;
+
+        let is_due: bool = self
+            .next_refresh_at
+            .is_none_or(|next_refresh_at| Instant::now() >= next_refresh_at);
+
+        if is_due {
+            match self.fetch_node_identity(es_conn, local_client).await {
+                Ok(node_identity) => {
+                    self.node_identity = Some(node_identity);
+                    self.next_refresh_at = Some(Instant::now() + NODE_INFO_REFRESH_INTERVAL);
+                }
+                Err(e) => {
+                    /* 로컬 노드가 응답하지 않더라도 이전에 조회한 정보는 계속 사용한다. */
+                    warn!(
+                        "[WARN][NodeInfoServiceImpl->get_node_identity] retrying in {}s: {:?}",
+                        NODE_INFO_RETRY_INTERVAL.as_secs(),
+                        e
+                    );
+                    self.next_refresh_at = Some(Instant::now() + NODE_INFO_RETRY_INTERVAL);
+                }
+            }
+        }
+
+        self.node_identity.clone()
+    }
+}
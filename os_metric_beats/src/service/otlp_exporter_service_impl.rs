@@ -0,0 +1,187 @@
+use crate::common::*;
+
+use crate::common_enums::{otlp_protocol::*, otlp_temporality::*};
+
+use crate::model::exporter::{counter_totals::*, metric_sample::*, metric_snapshot::*, otlp_config::*, otlp_metrics_request::*};
+
+use crate::traits::metric_exporter_service::*;
+
+use crate::utils_module::compress_utils::*;
+use crate::utils_module::http_utils::*;
+
+use prost::Message;
+
+#[doc = "수집 결과를 OpenTelemetry OTLP/HTTP (protobuf 또는 JSON) 로 보내주는 출력"]
+#[derive(Clone, Debug)]
+pub struct OtlpExporterServiceImpl {
+    sender: mpsc::Sender<MetricSnapshot>,
+}
+
+impl OtlpExporterServiceImpl {
+    #[doc = "수집 결과를 OTLP 로 보내주는 백그라운드 작업을 띄우고 핸들을 반환하는 함수"]
+    /// # Arguments
+    /// * `otlp_config` - endpoint, 본문 형식, temporality, 헤더, 재시도 설정
+    /// * `os_type` - resource 의 os.type (linux, windows)
+    ///
+    /// # Returns
+    /// * Result<OtlpExporterServiceImpl, anyhow::Error>
+    pub fn new(otlp_config: OtlpConfig, os_type: &str) -> Result<Self, anyhow::Error> {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static(otlp_config.protocol().content_type()));
+        if *otlp_config.compression() {
+            headers.insert(CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        }
+        for (key, value) in otlp_config.headers() {
+            headers.insert(
+                reqwest::header::HeaderName::from_bytes(key.as_bytes())?,
+                HeaderValue::from_str(value)?,
+            );
+        }
+
+        let client: reqwest::Client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(*otlp_config.timeout_secs()))
+            .user_agent(concat!("os_metric_beats/", env!("CARGO_PKG_VERSION")))
+            .default_headers(headers)
+            .build()?;
+
+        let host_name: String = System::new().host_name().unwrap_or_default();
+        let mut resource_attributes: BTreeMap<String, String> = BTreeMap::from([
+            (String::from("host.name"), host_name),
+            (String::from("os.type"), os_type.to_string()),
+            (String::from("service.name"), otlp_config.service_name().clone()),
+        ]);
+        for (key, value) in otlp_config.resource_attributes() {
+            resource_attributes.insert(key.clone(), value.clone());
+        }
+
+        let (sender, receiver) = mpsc::channel::<MetricSnapshot>(*otlp_config.queue_capacity());
+
+        info!(
+            "[OtlpExporterServiceImpl->new] Sending metrics to {} ({:?}, {:?} temporality)",
+            otlp_config.endpoint(),
+            otlp_config.protocol(),
+            otlp_config.temporality()
+        );
+
+        let otlp_worker: OtlpWorker = OtlpWorker {
+            client,
+            otlp_config,
+            resource_attributes,
+            counter_totals: CounterTotals::default(),
+            started_at_ms: Utc::now().timestamp_millis(),
+        };
+        tokio::spawn(otlp_worker.run(receiver));
+
+        Ok(OtlpExporterServiceImpl { sender })
+    }
+}
+
+impl MetricExporterService for OtlpExporterServiceImpl {
+    fn name(&self) -> &'static str {
+        "otlp"
+    }
+
+    #[doc = "수집 결과를 전송 큐에 넣어주는 함수 - 전송이 끝날때까지 기다리지 않는다."]
+    fn export(&self, snapshot: &MetricSnapshot) -> Result<(), anyhow::Error> {
+        self.sender.try_send(snapshot.clone()).map_err(|e| {
+            anyhow!(
+                "[ERROR][OtlpExporterServiceImpl->export] Failed to enqueue metrics: {}",
+                e
+            )
+        })
+    }
+}
+
+#[doc = "전송 큐를 소비하면서 수집 주기마다 OTLP 요청을 보내는 백그라운드 작업"]
+struct OtlpWorker {
+    client: reqwest::Client,
+    otlp_config: OtlpConfig,
+    resource_attributes: BTreeMap<String, String>,
+    counter_totals: CounterTotals,
+    started_at_ms: i64,
+}
+
+impl OtlpWorker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<MetricSnapshot>) {
+        while let Some(snapshot) = receiver.recv().await {
+            let request: ExportMetricsServiceRequest = self.build_request(&snapshot);
+
+            let body: Vec<u8> = match self.encode(&request) {
+                Ok(body) => body,
+                Err(e) => {
+                    error!("[ERROR][OtlpWorker->run] Failed to encode metrics: {:?}", e);
+                    continue;
+                }
+            };
+
+            match post_with_retry(
+                &self.client,
+                self.otlp_config.endpoint(),
+                body,
+                self.otlp_config.retry(),
+                "OtlpWorker->run",
+            )
+            .await
+            {
+                Ok(()) => info!(
+                    "[OtlpWorker->run] OTLP export completed: {} data points",
+                    snapshot.samples.len()
+                ),
+                Err(e) => error!(
+                    "[ERROR][OtlpWorker->run] Dropped {} data points: {:?}",
+                    snapshot.samples.len(),
+                    e
+                ),
+            }
+        }
+    }
+
+    #[doc = "수집 결과를 temporality 에 맞는 OTLP 요청으로 만들어주는 함수"]
+    /// delta 는 수집기의 직전 수집 시각부터 이번 수집 시각까지의 증가분이고 (첫 수집은 에이전트 시작 시각부터),
+    /// 구간 시작은 워커가 마지막으로 받은 결과가 아니라 수집기 기준이므로, 큐가 가득 차서 결과가 버려져도 수집기가 준 증가분의 구간과 맞는다.
+    /// (커널 누적값 counter 는 직전에 받은 값과의 차이라서 버려진 구간의 증가분이 다음 값에 합쳐진다.)
+    /// cumulative 는 에이전트 시작 시각부터의 누적값이다 (인터페이스 별 네트워크 counter 는 커널 누적값).
+    fn build_request(&mut self, snapshot: &MetricSnapshot) -> ExportMetricsServiceRequest {
+        let (temporality, samples, start_time_ms): (AggregationTemporality, Vec<MetricSample>, i64) =
+            match self.otlp_config.temporality() {
+                OtlpTemporality::Delta => (
+                    AggregationTemporality::Delta,
+                    self.counter_totals.deltas(&snapshot.samples),
+                    snapshot.prev_timestamp_ms.unwrap_or(self.started_at_ms),
+                ),
+                OtlpTemporality::Cumulative => (
+                    AggregationTemporality::Cumulative,
+                    self.counter_totals.accumulate(&snapshot.samples),
+                    self.started_at_ms,
+                ),
+            };
+
+        let mut resource_attributes: Vec<KeyValue> = vec![KeyValue::string("host.ip", &snapshot.host)];
+        resource_attributes.extend(
+            self.resource_attributes
+                .iter()
+                .map(|(key, value)| KeyValue::string(key, value)),
+        );
+
+        ExportMetricsServiceRequest::from_samples(
+            resource_attributes,
+            &samples,
+            temporality,
+            start_time_ms,
+            snapshot.timestamp_ms,
+        )
+    }
+
+    fn encode(&self, request: &ExportMetricsServiceRequest) -> Result<Vec<u8>, anyhow::Error> {
+        let body: Vec<u8> = match self.otlp_config.protocol() {
+            OtlpProtocol::HttpProtobuf => request.encode_to_vec(),
+            OtlpProtocol::HttpJson => serde_json::to_vec(&request.to_json())?,
+        };
+
+        if *self.otlp_config.compression() {
+            gzip_compress(&body, 6)
+        } else {
+            Ok(body)
+        }
+    }
+}
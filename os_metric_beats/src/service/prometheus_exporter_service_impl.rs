@@ -0,0 +1,255 @@
+use crate::common::*;
+
+use crate::common_enums::metric_kind::*;
+
+use crate::model::exporter::{counter_totals::*, metric_sample::*, metric_snapshot::*, prometheus_config::*};
+
+use crate::traits::metric_exporter_service::*;
+
+use crate::utils_module::tls_utils::*;
+
+use hyper::{server::conn::Http, service::service_fn, Body, Request, StatusCode};
+use std::{convert::Infallible, sync::Mutex};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+const TEXT_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+const OPENMETRICS_CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Debug, Default)]
+struct PrometheusState {
+    counter_totals: CounterTotals,
+    samples: Vec<MetricSample>,
+}
+
+#[doc = "가장 최근 수집 결과를 scrape 요청에 응답해주는 Prometheus exporter - counter 는 에이전트 시작 이후의 누적값으로 노출한다."]
+#[derive(Clone, Debug)]
+pub struct PrometheusExporterServiceImpl {
+    state: Arc<Mutex<PrometheusState>>,
+    path: String,
+    auth_header: Option<HeaderValue>,
+}
+
+impl PrometheusExporterServiceImpl {
+    #[doc = "설정된 주소로 내장 HTTP 서버를 띄우고 핸들을 반환하는 함수"]
+    /// # Arguments
+    /// * `prometheus_config` - listen 주소, 경로, basic 인증, TLS 설정
+    ///
+    /// # Returns
+    /// * Result<PrometheusExporterServiceImpl, anyhow::Error>
+    pub fn new(prometheus_config: &PrometheusConfig) -> Result<Self, anyhow::Error> {
+        let auth_header: Option<HeaderValue> = match prometheus_config.basic_auth() {
+            Some(basic_auth) => Some(basic_auth.to_header_value()?),
+            None => None,
+        };
+
+        let tls_acceptor: Option<TlsAcceptor> = match prometheus_config.tls() {
+            Some(tls_config) => Some(build_tls_acceptor(tls_config)?),
+            None => None,
+        };
+
+        /* 포트 충돌 등은 기동 시점에 바로 알 수 있도록 bind 는 동기로 수행 */
+        let std_listener: std::net::TcpListener = std::net::TcpListener::bind(prometheus_config.listen_addr())
+            .map_err(|e| {
+                anyhow!(
+                    "[ERROR][PrometheusExporterServiceImpl->new] Failed to bind '{}': {}",
+                    prometheus_config.listen_addr(),
+                    e
+                )
+            })?;
+        std_listener.set_nonblocking(true)?;
+        let listener: TcpListener = TcpListener::from_std(std_listener)?;
+
+        let exporter: PrometheusExporterServiceImpl = PrometheusExporterServiceImpl {
+            state: Arc::new(Mutex::new(PrometheusState::default())),
+            path: prometheus_config.path().clone(),
+            auth_header,
+        };
+
+        info!(
+            "[PrometheusExporterServiceImpl->new] Serving metrics on {}://{}{}",
+            if tls_acceptor.is_some() { "https" } else { "http" },
+            prometheus_config.listen_addr(),
+            prometheus_config.path()
+        );
+
+        tokio::spawn(exporter.clone().serve(listener, tls_acceptor));
+
+        Ok(exporter)
+    }
+
+    #[doc = "연결을 받아서 요청마다 최신 수집 결과를 응답해주는 함수"]
+    async fn serve(self, listener: TcpListener, tls_acceptor: Option<TlsAcceptor>) {
+        loop {
+            let stream: tokio::net::TcpStream = match listener.accept().await {
+                Ok((stream, _peer)) => stream,
+                Err(e) => {
+                    error!("[ERROR][PrometheusExporterServiceImpl->serve] Failed to accept connection: {:?}", e);
+                    sleep(Duration::from_millis(100)).await;
+                    continue;
+                }
+            };
+
+            let exporter: PrometheusExporterServiceImpl = self.clone();
+            let tls_acceptor: Option<TlsAcceptor> = tls_acceptor.clone();
+
+            tokio::spawn(async move {
+                let service = service_fn(move |request: Request<Body>| {
+                    let response: hyper::Response<Body> = exporter.handle(&request);
+                    async move { Ok::<_, Infallible>(response) }
+                });
+
+                let result: Result<(), hyper::Error> = match tls_acceptor {
+                    Some(tls_acceptor) => match tls_acceptor.accept(stream).await {
+                        Ok(tls_stream) => Http::new().http1_only(true).serve_connection(tls_stream, service).await,
+                        Err(e) => {
+                            warn!("[WARN][PrometheusExporterServiceImpl->serve] TLS handshake failed: {:?}", e);
+                            return;
+                        }
+                    },
+                    None => Http::new().http1_only(true).serve_connection(stream, service).await,
+                };
+
+                if let Err(e) = result {
+                    warn!("[WARN][PrometheusExporterServiceImpl->serve] {:?}", e);
+                }
+            });
+        }
+    }
+
+    #[doc = "scrape 요청 하나를 처리해주는 함수 - Accept 헤더에 OpenMetrics 가 있으면 OpenMetrics 형식으로 응답한다."]
+    fn handle(&self, request: &Request<Body>) -> hyper::Response<Body> {
+        if request.uri().path() != self.path {
+            return plain_response(StatusCode::NOT_FOUND, "Not Found");
+        }
+
+        if request.method() != hyper::Method::GET && request.method() != hyper::Method::HEAD {
+            return plain_response(StatusCode::METHOD_NOT_ALLOWED, "Method Not Allowed");
+        }
+
+        if let Some(auth_header) = &self.auth_header {
+            if request.headers().get(AUTHORIZATION) != Some(auth_header) {
+                let mut response: hyper::Response<Body> = plain_response(StatusCode::UNAUTHORIZED, "Unauthorized");
+                response.headers_mut().insert(
+                    hyper::header::WWW_AUTHENTICATE,
+                    HeaderValue::from_static("Basic realm=\"os_metric_beats\""),
+                );
+                return response;
+            }
+        }
+
+        let open_metrics: bool = request
+            .headers()
+            .get(hyper::header::ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .is_some_and(|accept| accept.contains("application/openmetrics-text"));
+
+        let body: String = match self.state.lock() {
+            Ok(state) => render_exposition(&state.samples, open_metrics),
+            Err(e) => {
+                error!("[ERROR][PrometheusExporterServiceImpl->handle] {:?}", e);
+                return plain_response(StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error");
+            }
+        };
+
+        let mut response: hyper::Response<Body> = hyper::Response::new(Body::from(body));
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static(if open_metrics { OPENMETRICS_CONTENT_TYPE } else { TEXT_CONTENT_TYPE }),
+        );
+        response
+    }
+}
+
+impl MetricExporterService for PrometheusExporterServiceImpl {
+    fn name(&self) -> &'static str {
+        "prometheus"
+    }
+
+    #[doc = "scrape 에 응답할 최신 수집 결과를 교체해주는 함수"]
+    fn export(&self, snapshot: &MetricSnapshot) -> Result<(), anyhow::Error> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|e| anyhow!("[ERROR][PrometheusExporterServiceImpl->export] {}", e))?;
+
+        state.samples = state.counter_totals.accumulate(&snapshot.samples);
+        Ok(())
+    }
+}
+
+fn plain_response(status: StatusCode, body: &'static str) -> hyper::Response<Body> {
+    let mut response: hyper::Response<Body> = hyper::Response::new(Body::from(body));
+    *response.status_mut() = status;
+    response
+}
+
+#[doc = "시계열 목록을 Prometheus text(0.0.4) 또는 OpenMetrics 형식으로 만들어주는 함수"]
+/// 같은 이름의 시계열은 처음 나온 위치에 모아서 HELP/TYPE 을 한 번만 출력한다.
+/// OpenMetrics 에서는 counter family 이름에 _total 을 붙이지 않고, 마지막에 # EOF 를 붙인다.
+///
+/// # Arguments
+/// * `samples` - 노출할 시계열 (counter 는 누적값)
+/// * `open_metrics` - OpenMetrics 형식 여부
+///
+/// # Returns
+/// * String
+fn render_exposition(samples: &[MetricSample], open_metrics: bool) -> String {
+    let mut families: Vec<(&str, Vec<&MetricSample>)> = Vec::new();
+    for sample in samples {
+        match families.iter_mut().find(|(name, _)| *name == sample.name) {
+            Some((_, family)) => family.push(sample),
+            None => families.push((sample.name, vec![sample])),
+        }
+    }
+
+    let mut body: String = String::new();
+
+    for (name, family) in families {
+        let first: &MetricSample = family[0];
+        let family_name: &str = match (open_metrics, first.kind) {
+            (true, MetricKind::Counter) => name.strip_suffix("_total").unwrap_or(name),
+            _ => name,
+        };
+
+        body.push_str(&format!("# HELP {} {}\n", family_name, escape_help(first.help)));
+        body.push_str(&format!("# TYPE {} {}\n", family_name, first.kind.as_str()));
+
+        for sample in family {
+            body.push_str(name);
+            if !sample.labels.is_empty() {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(key, value)| format!("{}=\"{}\"", key, escape_label_value(value)))
+                    .collect();
+                body.push_str(&format!("{{{}}}", labels.join(",")));
+            }
+            body.push_str(&format!(" {}\n", format_value(sample.value)));
+        }
+    }
+
+    if open_metrics {
+        body.push_str("# EOF\n");
+    }
+
+    body
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn escape_label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        String::from("NaN")
+    } else if value.is_infinite() {
+        String::from(if value > 0.0 { "+Inf" } else { "-Inf" })
+    } else {
+        value.to_string()
+    }
+}
@@ -0,0 +1,323 @@
+use crate::common::*;
+
+use crate::model::exporter::{counter_totals::*, metric_snapshot::*, remote_write_config::*, remote_write_request::*};
+
+use crate::traits::metric_exporter_service::*;
+
+use crate::utils_module::http_utils::*;
+
+use prost::Message;
+
+#[doc = "remote_write 의 batch 전송 기준(batch_max_samples) 확인과 별개로, 대기시간을 확인하는 주기"]
+const FLUSH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[doc = "수집 결과를 모아서 Prometheus remote_write 엔드포인트로 보내주는 출력"]
+#[derive(Clone, Debug)]
+pub struct RemoteWriteExporterServiceImpl {
+    sender: mpsc::Sender<MetricSnapshot>,
+}
+
+impl RemoteWriteExporterServiceImpl {
+    #[doc = "수집 결과를 모아서 remote_write 로 보내주는 백그라운드 작업을 띄우고 핸들을 반환하는 함수"]
+    /// # Arguments
+    /// * `remote_write_config` - 전송 URL, batch 기준, 인증, 재시도 설정
+    ///
+    /// # Returns
+    /// * Result<RemoteWriteExporterServiceImpl, anyhow::Error>
+    pub fn new(remote_write_config: RemoteWriteConfig) -> Result<Self, anyhow::Error> {
+        let mut headers: HeaderMap = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/x-protobuf"));
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static("snappy"));
+        headers.insert("X-Prometheus-Remote-Write-Version", HeaderValue::from_static("0.1.0"));
+
+        if let Some(basic_auth) = remote_write_config.basic_auth() {
+            headers.insert(AUTHORIZATION, basic_auth.to_header_value()?);
+        } else if let Some(bearer_token) = remote_write_config.bearer_token() {
+            headers.insert(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", bearer_token.resolve()?))?);
+        }
+
+        let client: reqwest::Client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(*remote_write_config.timeout_secs()))
+            .user_agent(concat!("os_metric_beats/", env!("CARGO_PKG_VERSION")))
+            .default_headers(headers)
+            .build()?;
+
+        let (sender, receiver) = mpsc::channel::<MetricSnapshot>(*remote_write_config.queue_capacity());
+
+        info!(
+            "[RemoteWriteExporterServiceImpl->new] Sending metrics to {}",
+            remote_write_config.url()
+        );
+
+        let remote_write_worker: RemoteWriteWorker = RemoteWriteWorker {
+            client,
+            remote_write_config,
+            counter_totals: CounterTotals::default(),
+            pending: Vec::new(),
+            pending_sample_cnt: 0,
+            first_pending_at: None,
+        };
+        tokio::spawn(remote_write_worker.run(receiver));
+
+        Ok(RemoteWriteExporterServiceImpl { sender })
+    }
+}
+
+impl MetricExporterService for RemoteWriteExporterServiceImpl {
+    fn name(&self) -> &'static str {
+        "remote_write"
+    }
+
+    #[doc = "수집 결과를 전송 큐에 넣어주는 함수 - 전송이 끝날때까지 기다리지 않는다."]
+    fn export(&self, snapshot: &MetricSnapshot) -> Result<(), anyhow::Error> {
+        self.sender.try_send(snapshot.clone()).map_err(|e| {
+            anyhow!(
+                "[ERROR][RemoteWriteExporterServiceImpl->export] Failed to enqueue metrics: {}",
+                e
+            )
+        })
+    }
+}
+
+#[doc = "전송 큐를 소비하면서 batch 단위로 remote_write 요청을 보내는 백그라운드 작업"]
+struct RemoteWriteWorker {
+    client: reqwest::Client,
+    remote_write_config: RemoteWriteConfig,
+    counter_totals: CounterTotals,
+    pending: Vec<PendingSamples>,
+    pending_sample_cnt: usize,
+    first_pending_at: Option<Instant>,
+}
+
+impl RemoteWriteWorker {
+    async fn run(mut self, mut receiver: mpsc::Receiver<MetricSnapshot>) {
+        let mut flush_check = interval(FLUSH_CHECK_INTERVAL);
+        let flush_interval: Duration = Duration::from_secs(*self.remote_write_config.flush_interval_secs());
+
+        loop {
+            select! {
+                snapshot = receiver.recv() => {
+                    let Some(snapshot) = snapshot else {
+                        /* 핸들이 모두 drop 되면 남은 값을 보내고 종료 */
+                        self.flush().await;
+                        break;
+                    };
+
+                    /* remote_write 의 counter 는 누적값이어야 하므로 큐에 넣기 전에 누적 */
+                    let samples = self.counter_totals.accumulate(&snapshot.samples);
+                    self.pending_sample_cnt += samples.len();
+                    self.pending.push(PendingSamples::new(snapshot.timestamp_ms, snapshot.host, samples));
+                    self.first_pending_at.get_or_insert_with(Instant::now);
+
+                    if self.pending_sample_cnt >= *self.remote_write_config.batch_max_samples() {
+                        self.flush().await;
+                    }
+                }
+                _ = flush_check.tick() => {
+                    if self.first_pending_at.is_some_and(|first_pending_at| first_pending_at.elapsed() >= flush_interval) {
+                        self.flush().await;
+                    }
+                }
+            }
+        }
+    }
+
+    #[doc = "쌓인 수집 결과를 remote_write 요청 하나로 보내는 함수 - 재시도해도 실패하면 버린다."]
+    async fn flush(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let pending: Vec<PendingSamples> = std::mem::take(&mut self.pending);
+        let sample_cnt: usize = std::mem::take(&mut self.pending_sample_cnt);
+        self.first_pending_at = None;
+
+        let write_request: WriteRequest =
+            WriteRequest::from_pending(&pending, self.remote_write_config.external_labels());
+
+        let body: Vec<u8> = match snap::raw::Encoder::new().compress_vec(&write_request.encode_to_vec()) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("[ERROR][RemoteWriteWorker->flush] Failed to compress write request: {:?}", e);
+                return;
+            }
+        };
+
+        match post_with_retry(
+            &self.client,
+            self.remote_write_config.url(),
+            body,
+            self.remote_write_config.retry(),
+            "RemoteWriteWorker->flush",
+        )
+        .await
+        {
+            Ok(()) => info!(
+                "[RemoteWriteWorker->flush] Remote write completed: {} series, {} samples",
+                write_request.timeseries.len(),
+                sample_cnt
+            ),
+            Err(e) => error!(
+                "[ERROR][RemoteWriteWorker->flush] Dropped {} samples: {:?}",
+                sample_cnt, e
+            ),
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::model::exporter::metric_sample::*;
+
+    use hyper::{server::conn::Http, service::service_fn, Body, Request};
+    use std::{convert::Infallible, sync::Mutex};
+    use tokio::net::TcpListener;
+
+    #[doc = "받은 요청 body 를 넘겨주고, 정해둔 상태코드를 순서대로 응답하는 loopback remote_write 수신기"]
+    /// 정해둔 상태코드를 다 쓰면 204 로 응답한다.
+    async fn start_receiver(statuses: Vec<u16>) -> (String, mpsc::UnboundedReceiver<Vec<u8>>) {
+        let listener: TcpListener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: String = format!("http://{}/api/v1/write", listener.local_addr().unwrap());
+        let (body_sender, body_receiver) = mpsc::unbounded_channel::<Vec<u8>>();
+        let statuses: Arc<Mutex<VecDeque<u16>>> = Arc::new(Mutex::new(VecDeque::from(statuses)));
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, _peer) = listener.accept().await.unwrap();
+                let body_sender: mpsc::UnboundedSender<Vec<u8>> = body_sender.clone();
+                let statuses: Arc<Mutex<VecDeque<u16>>> = Arc::clone(&statuses);
+
+                tokio::spawn(async move {
+                    let service = service_fn(move |request: Request<Body>| {
+                        let body_sender: mpsc::UnboundedSender<Vec<u8>> = body_sender.clone();
+                        let status: u16 = statuses.lock().unwrap().pop_front().unwrap_or(204);
+                        async move {
+                            let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                            body_sender.send(body.to_vec()).unwrap();
+                            let mut response: hyper::Response<Body> = hyper::Response::new(Body::empty());
+                            *response.status_mut() = hyper::StatusCode::from_u16(status).unwrap();
+                            Ok::<_, Infallible>(response)
+                        }
+                    });
+                    let _ = Http::new().http1_only(true).serve_connection(stream, service).await;
+                });
+            }
+        });
+
+        (url, body_receiver)
+    }
+
+    fn test_config(url: &str) -> RemoteWriteConfig {
+        toml::from_str(&format!(
+            r#"
+            url = "{}"
+            batch_max_samples = 2
+            flush_interval_secs = 60
+
+            [external_labels]
+            cluster = "test-cluster"
+            host = "external-host"
+
+            [retry]
+            max_retries = 3
+            initial_backoff_ms = 10
+            max_backoff_ms = 10
+            "#,
+            url
+        ))
+        .unwrap()
+    }
+
+    fn test_snapshot(timestamp_ms: i64, received_bytes: f64) -> MetricSnapshot {
+        MetricSnapshot::new(
+            timestamp_ms,
+            String::from("10.0.0.1"),
+            vec![
+                MetricSample::gauge("os_cpu_usage_percent", "System CPU usage", 12.5),
+                MetricSample::counter("os_network_received_bytes_total", "Bytes received", received_bytes)
+                    .with_label("interface", "eth0"),
+            ],
+        )
+    }
+
+    async fn next_write_request(body_receiver: &mut mpsc::UnboundedReceiver<Vec<u8>>) -> WriteRequest {
+        let body: Vec<u8> = tokio::time::timeout(Duration::from_secs(5), body_receiver.recv())
+            .await
+            .expect("remote_write request was not sent")
+            .unwrap();
+        let decoded: Vec<u8> = snap::raw::Decoder::new().decompress_vec(&body).unwrap();
+        WriteRequest::decode(decoded.as_slice()).unwrap()
+    }
+
+    fn series<'a>(write_request: &'a WriteRequest, name: &str) -> &'a TimeSeries {
+        write_request
+            .timeseries
+            .iter()
+            .find(|time_series| time_series.labels.iter().any(|label| label.name == "__name__" && label.value == name))
+            .unwrap_or_else(|| panic!("series {} is missing", name))
+    }
+
+    fn label_pairs(time_series: &TimeSeries) -> Vec<(&str, &str)> {
+        time_series
+            .labels
+            .iter()
+            .map(|label| (label.name.as_str(), label.value.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn sends_sorted_labels_and_cumulative_counters() {
+        let (url, mut body_receiver) = start_receiver(Vec::new()).await;
+        let exporter: RemoteWriteExporterServiceImpl = RemoteWriteExporterServiceImpl::new(test_config(&url)).unwrap();
+
+        exporter.export(&test_snapshot(1_000, 100.0)).unwrap();
+        let first: WriteRequest = next_write_request(&mut body_receiver).await;
+
+        exporter.export(&test_snapshot(2_000, 50.0)).unwrap();
+        let second: WriteRequest = next_write_request(&mut body_receiver).await;
+
+        let counter: &TimeSeries = series(&second, "os_network_received_bytes_total");
+        /* 이름순 정렬 + 시계열의 host 가 같은 이름의 external label 보다 우선 */
+        assert_eq!(
+            label_pairs(counter),
+            vec![
+                ("__name__", "os_network_received_bytes_total"),
+                ("cluster", "test-cluster"),
+                ("host", "10.0.0.1"),
+                ("interface", "eth0"),
+            ]
+        );
+        assert_eq!(counter.samples, vec![Sample { value: 150.0, timestamp: 2_000 }]);
+        assert_eq!(
+            series(&first, "os_network_received_bytes_total").samples,
+            vec![Sample { value: 100.0, timestamp: 1_000 }]
+        );
+
+        let gauge: &TimeSeries = series(&second, "os_cpu_usage_percent");
+        assert_eq!(
+            label_pairs(gauge),
+            vec![("__name__", "os_cpu_usage_percent"), ("cluster", "test-cluster"), ("host", "10.0.0.1")]
+        );
+        assert_eq!(gauge.samples, vec![Sample { value: 12.5, timestamp: 2_000 }]);
+    }
+
+    #[tokio::test]
+    async fn retries_only_retryable_statuses() {
+        let (url, mut body_receiver) = start_receiver(vec![503, 500]).await;
+        let exporter: RemoteWriteExporterServiceImpl = RemoteWriteExporterServiceImpl::new(test_config(&url)).unwrap();
+
+        /* 503 은 재시도하고, 이어지는 500 은 재시도 없이 버린다. */
+        exporter.export(&test_snapshot(1_000, 100.0)).unwrap();
+        let rejected: WriteRequest = next_write_request(&mut body_receiver).await;
+        let retried: WriteRequest = next_write_request(&mut body_receiver).await;
+        assert_eq!(rejected, retried);
+
+        exporter.export(&test_snapshot(2_000, 50.0)).unwrap();
+        let next: WriteRequest = next_write_request(&mut body_receiver).await;
+        assert_eq!(series(&next, "os_network_received_bytes_total").samples[0].timestamp, 2_000);
+        assert!(body_receiver.try_recv().is_err());
+    }
+}
@@ -1,26 +1,304 @@
 use crate::common::*;
 
+use crate::common_enums::{bulk_op_type::*, doc_type::*};
+
 use crate::repository::es_repository::*;
 
+use crate::utils_module::{hash_utils::*, io_utils::*};
+
+use crate::env_configuration::env_config::*;
+
+use crate::model::elastic::{
+    bulk_config::*, dead_letter_config::*, document_route_config::*, es_document::*, retry_config::*,
+    spool_config::*,
+};
+use crate::model::exporter::metric_snapshot::*;
 use crate::model::metric_info::*;
+use crate::model::slowlog::slowlog_metric_info::*;
+use crate::model::system_config::*;
+
+use crate::service::{
+    bulk_shipper_service_impl::*, influx_exporter_service_impl::*, otlp_exporter_service_impl::*,
+    prometheus_exporter_service_impl::*, remote_write_exporter_service_impl::*,
+};
+
+use crate::traits::{bulk_shipper_service::*, metric_exporter_service::*, request_service::*};
+
+#[doc = "출력(클러스터) 하나로 문서를 보내는 전송 단위 - 출력마다 큐/spool/재시도 상태를 따로 가져서 느린 클러스터가 다른 출력을 지연시키지 않는다."]
+#[derive(Clone, Debug)]
+struct OutputShipper {
+    es_conn: Arc<EsRepositoryPub>,
+    bulk_shipper: BulkShipperServiceImpl,
+    /* 이 출력으로 보내는 문서 종류별 라우팅 결과 - 라우팅 규칙이 다른 출력을 선택한 문서 종류는 없다. */
+    routes: HashMap<DocType, DocumentRouteConfig>,
+}
+
+impl OutputShipper {
+    fn new(es_conn: Arc<EsRepositoryPub>, host_tags: &HashMap<String, String>) -> Self {
+        let bulk_config: BulkConfig = es_conn.bulk_config().clone();
+        let retry_config: RetryConfig = es_conn.retry_config().clone();
+        let spool_config: Option<SpoolConfig> = es_conn.spool_config().clone();
+        let dead_letter_config: Option<DeadLetterConfig> = es_conn.dead_letter_config().clone();
+
+        let routes: HashMap<DocType, DocumentRouteConfig> = [DocType::Metric, DocType::Slowlog]
+            .into_iter()
+            .filter_map(|doc_type| Some((doc_type, resolve_route(&es_conn, doc_type, host_tags)?)))
+            .collect();
+
+        OutputShipper {
+            bulk_shipper: BulkShipperServiceImpl::new(
+                es_conn.clone(),
+                bulk_config,
+                retry_config,
+                spool_config,
+                dead_letter_config,
+            ),
+            es_conn,
+            routes,
+        }
+    }
+
+    #[doc = "색인 모드와 문서 종류별 라우팅에 맞는 전송 문서로 감싸주는 함수 - data stream 은 @timestamp 가 필수"]
+    /// 호스트/문서 종류/수집 시각/대상(entity key)으로 _id 를 고정하고 create 로 보내서,
+    /// 실제로는 색인된 요청이 타임아웃으로 재시도되거나 spool 에서 재전송되어도 중복 문서가 생기지 않게 한다.
+    ///
+    /// # Arguments
+    /// * `doc_type` - 문서 종류
+    /// * `index_date` - 날짜별 인덱스 접미사 (라우팅에 index 가 지정되면 사용하지 않음)
+    /// * `document` - 문서 본문
+    ///
+    /// # Returns
+    /// * EsDocument
+    fn to_es_document(&self, doc_type: DocType, index_date: &str, mut document: Value) -> EsDocument {
+        let route: Option<&DocumentRouteConfig> = self.routes.get(&doc_type);
+        let index_name: String = route
+            .and_then(|route| route.index().clone())
+            .unwrap_or_else(|| self.es_conn.default_index_name(doc_type, index_date));
+
+        if self.es_conn.data_stream_config().is_some() {
+            document["@timestamp"] = document["timestamp"].clone();
+        }
+
+        let doc_id: String = deterministic_doc_id(&[
+            document["host"].as_str().unwrap_or_default(),
+            doc_type.as_str(),
+            document["timestamp"].as_str().unwrap_or_default(),
+            &doc_type.entity_key(&document),
+        ]);
 
-use crate::traits::request_service::*;
+        let mut es_document: EsDocument = EsDocument::new(index_name, BulkOpType::Create, document);
+        es_document.id = Some(doc_id);
+        es_document.pipeline = route.and_then(|route| route.pipeline().clone());
+
+        es_document
+    }
+
+    #[doc = "라우팅 규칙상 이 출력으로 해당 문서 종류를 보내는지 여부"]
+    fn accepts(&self, doc_type: DocType) -> bool {
+        self.routes.contains_key(&doc_type)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RequestServiceImpl {
+    outputs: Vec<OutputShipper>,
+    exporters: Vec<Arc<dyn MetricExporterService>>,
+}
+
+impl Default for RequestServiceImpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RequestServiceImpl {
+    pub fn new() -> Self {
+        let system_config: Option<SystemConfig> = match read_toml_from_file::<SystemConfig>(&SYSTEM_INFO) {
+            Ok(system_config) => Some(system_config),
+            Err(e) => {
+                error!("[ERROR][RequestServiceImpl->new] Failed to read system config: {:?}", e);
+                None
+            }
+        };
+
+        /* 호스트 태그는 실행 중에 바뀌지 않으므로 문서 종류별 라우팅 결과를 한 번만 계산 */
+        let host_tags: HashMap<String, String> = system_config
+            .as_ref()
+            .map(|system_config| system_config.host_tags.clone())
+            .unwrap_or_default();
+
+        RequestServiceImpl {
+            outputs: get_elastic_conns()
+                .into_iter()
+                .map(|es_conn| OutputShipper::new(es_conn, &host_tags))
+                .collect(),
+            exporters: system_config.as_ref().map(create_exporters).unwrap_or_default(),
+        }
+    }
 
-#[derive(Clone, Debug, new)]
-pub struct RequestServiceImpl;
+    #[doc = "문서를 라우팅 규칙이 선택한 모든 출력의 큐에 넣어주는 함수 - 한 출력의 큐가 가득 차도 나머지 출력에는 계속 넣는다."]
+    fn enqueue_to_all(&self, doc_type: DocType, index_date: &str, document: Value) -> Result<(), anyhow::Error> {
+        let failed_outputs: Vec<String> = self
+            .outputs
+            .iter()
+            .filter(|output| output.accepts(doc_type))
+            .filter_map(|output| {
+                output
+                    .bulk_shipper
+                    .enqueue(output.to_es_document(doc_type, index_date, document.clone()))
+                    .err()
+                    .map(|e| format!("{} ({})", output.es_conn.output_name(), e))
+            })
+            .collect();
+
+        if failed_outputs.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[ERROR][RequestServiceImpl->enqueue_to_all] Failed to enqueue {:?} document to outputs: {}",
+                doc_type,
+                failed_outputs.join(", ")
+            ))
+        }
+    }
+}
 
 #[async_trait]
 impl RequestService for RequestServiceImpl {
     async fn request_metric_to_elastic(
         &self,
-        index_name: String,
+        index_date: String,
         metric_info: MetricInfo,
     ) -> Result<(), anyhow::Error> {
-        let es_conn: Arc<EsRepositoryPub> = get_elastic_conn();
         let document: Value = serde_json::to_value(&metric_info)?;
+        self.enqueue_to_all(DocType::Metric, &index_date, document)
+    }
+
+    async fn request_slowlog_to_elastic(
+        &self,
+        index_date: String,
+        slowlog_infos: Vec<SlowLogMetricInfo>,
+    ) -> Result<(), anyhow::Error> {
+        let mut last_error: Option<anyhow::Error> = None;
+
+        for slowlog_info in slowlog_infos {
+            let document: Value = serde_json::to_value(&slowlog_info)?;
+            if let Err(e) = self.enqueue_to_all(DocType::Slowlog, &index_date, document) {
+                last_error = Some(e);
+            }
+        }
+
+        match last_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+
+    #[doc = "수집 결과를 설정된 모든 exporter 로 내보내는 함수 - 한 exporter 가 실패해도 나머지에는 계속 내보낸다."]
+    async fn request_metric_to_exporters(&self, snapshot: MetricSnapshot) -> Result<(), anyhow::Error> {
+        let failed_exporters: Vec<String> = self
+            .exporters
+            .iter()
+            .filter_map(|exporter| {
+                exporter
+                    .export(&snapshot)
+                    .err()
+                    .map(|e| format!("{} ({})", exporter.name(), e))
+            })
+            .collect();
+
+        if failed_exporters.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "[ERROR][RequestServiceImpl->request_metric_to_exporters] Failed to export metrics: {}",
+                failed_exporters.join(", ")
+            ))
+        }
+    }
+}
 
-        es_conn.post_doc(&index_name, document).await?;
+#[doc = "system config 에 설정된 exporter 들을 띄워주는 함수 - 시작에 실패한 exporter 는 로그를 남기고 제외한다."]
+fn create_exporters(system_config: &SystemConfig) -> Vec<Arc<dyn MetricExporterService>> {
+    let mut exporters: Vec<Arc<dyn MetricExporterService>> = Vec::new();
 
-        Ok(())
+    if let Some(prometheus_config) = &system_config.prometheus {
+        match PrometheusExporterServiceImpl::new(prometheus_config) {
+            Ok(exporter) => exporters.push(Arc::new(exporter)),
+            Err(e) => error!("[ERROR][create_exporters] Failed to start prometheus exporter: {:?}", e),
+        }
     }
+
+    if let Some(remote_write_config) = &system_config.remote_write {
+        match RemoteWriteExporterServiceImpl::new(remote_write_config.clone()) {
+            Ok(exporter) => exporters.push(Arc::new(exporter)),
+            Err(e) => error!("[ERROR][create_exporters] Failed to start remote_write exporter: {:?}", e),
+        }
+    }
+
+    if let Some(otlp_config) = &system_config.otlp {
+        match OtlpExporterServiceImpl::new(otlp_config.clone(), &system_config.os_ver.to_lowercase()) {
+            Ok(exporter) => exporters.push(Arc::new(exporter)),
+            Err(e) => error!("[ERROR][create_exporters] Failed to start otlp exporter: {:?}", e),
+        }
+    }
+
+    if let Some(influx_config) = &system_config.influx {
+        match InfluxExporterServiceImpl::new(influx_config.clone()) {
+            Ok(exporter) => exporters.push(Arc::new(exporter)),
+            Err(e) => error!("[ERROR][create_exporters] Failed to start influx exporter: {:?}", e),
+        }
+    }
+
+    exporters
+}
+
+#[doc = "문서 종류별 설정에 호스트 태그와 일치하는 첫 번째 라우팅 규칙을 덮어써서 최종 색인 대상/pipeline 을 정해주는 함수"]
+/// # Arguments
+/// * `es_conn` - 문서 종류별 설정과 라우팅 규칙을 가진 Elasticsearch 저장소
+/// * `doc_type` - 문서 종류
+/// * `host_tags` - 이 호스트의 태그 (system config 의 host_tags)
+///
+/// # Returns
+/// * Option<DocumentRouteConfig> - 일치한 규칙의 outputs 에 이 출력이 없으면 None (이 출력으로 보내지 않음)
+fn resolve_route(
+    es_conn: &EsRepositoryPub,
+    doc_type: DocType,
+    host_tags: &HashMap<String, String>,
+) -> Option<DocumentRouteConfig> {
+    let base_route: DocumentRouteConfig = es_conn
+        .document_routes()
+        .get(&doc_type)
+        .cloned()
+        .unwrap_or_default();
+
+    let route: DocumentRouteConfig = match es_conn
+        .routing_rules()
+        .iter()
+        .find(|rule| rule.matches(doc_type, host_tags))
+    {
+        Some(rule) if !rule.selects_output(es_conn.output_name()) => {
+            info!(
+                "[RequestServiceImpl->resolve_route] [{}] {:?} documents are routed to outputs {:?} only, skipping this output",
+                es_conn.output_name(),
+                doc_type,
+                rule.outputs()
+            );
+            return None;
+        }
+        Some(rule) => base_route.overridden_by(rule.route()),
+        None => base_route,
+    };
+
+    if route != DocumentRouteConfig::default() {
+        info!(
+            "[RequestServiceImpl->resolve_route] [{}] {:?} documents are routed to index={:?}, pipeline={:?}",
+            es_conn.output_name(),
+            doc_type,
+            route.index(),
+            route.pipeline()
+        );
+    }
+
+    Some(route)
 }
@@ -0,0 +1,210 @@
+use crate::common::*;
+
+use crate::repository::es_repository::*;
+
+use crate::model::elastic::{retention_audit_record::*, retention_config::*, retention_lock::*};
+
+use crate::traits::retention_service::*;
+
+use crate::utils_module::time_utils::*;
+
+#[doc = "retention lock 문서의 id - 모든 에이전트가 같은 문서를 두고 경쟁한다."]
+const RETENTION_LOCK_ID: &str = "retention";
+
+#[derive(Clone, Debug)]
+pub struct RetentionServiceImpl {
+    es_conn: Arc<EsRepositoryPub>,
+    retention_config: RetentionConfig,
+    owner: String,
+}
+
+impl RetentionServiceImpl {
+    pub fn new(es_conn: Arc<EsRepositoryPub>, retention_config: RetentionConfig) -> Self {
+        let host: String = match local_ip() {
+            Ok(ip) => ip.to_string(),
+            Err(_) => String::from("unknown"),
+        };
+
+        RetentionServiceImpl {
+            es_conn,
+            retention_config,
+            owner: format!("{}:{}", host, std::process::id()),
+        }
+    }
+
+    #[doc = "정리 작업 lock 을 획득해주는 함수 - 만료된 lock 은 seq_no 비교로 한 에이전트만 가져간다."]
+    /// # Returns
+    /// * Result<Option<(i64, i64)>, anyhow::Error> - 획득했다면 해제에 필요한 (_seq_no, _primary_term)
+    async fn acquire_lock(&self) -> Result<Option<(i64, i64)>, anyhow::Error> {
+        let now_epoch: i64 = get_curretn_utc_epoch();
+        let lock: RetentionLock = RetentionLock::new(
+            self.owner.clone(),
+            get_currnet_utc_str(),
+            now_epoch + *self.retention_config.lock_ttl_secs() as i64,
+        );
+        let lock_doc: Value = serde_json::to_value(&lock)?;
+        let lock_index: &str = self.retention_config.lock_index();
+
+        if let Some(response) = self
+            .es_conn
+            .create_doc(lock_index, RETENTION_LOCK_ID, &lock_doc)
+            .await?
+        {
+            return Ok(seq_no_and_primary_term(&response));
+        }
+
+        /* 이미 lock 이 있다면, 만료된 경우에만 가져온다. */
+        let Some(current) = self.es_conn.get_doc(lock_index, RETENTION_LOCK_ID).await? else {
+            return Ok(None);
+        };
+
+        let current_lock: RetentionLock = serde_json::from_value(current["_source"].clone())?;
+        if !current_lock.is_expired(now_epoch) {
+            info!(
+                "[RetentionServiceImpl->acquire_lock] Retention lock is held by '{}'. Skip this round",
+                current_lock.owner
+            );
+            return Ok(None);
+        }
+
+        let Some((seq_no, primary_term)) = seq_no_and_primary_term(&current) else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .es_conn
+            .put_doc_if_match(lock_index, RETENTION_LOCK_ID, &lock_doc, seq_no, primary_term)
+            .await?
+            .and_then(|response| seq_no_and_primary_term(&response)))
+    }
+
+    #[doc = "획득했던 lock 을 해제해주는 함수 - 그 사이 다른 에이전트가 가져갔다면 건드리지 않는다."]
+    async fn release_lock(&self, seq_no: i64, primary_term: i64) {
+        match self
+            .es_conn
+            .delete_doc_if_match(self.retention_config.lock_index(), RETENTION_LOCK_ID, seq_no, primary_term)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => warn!("[WARN][RetentionServiceImpl->release_lock] Retention lock was taken over before release"),
+            Err(e) => error!("[ERROR][RetentionServiceImpl->release_lock] {:?}", e),
+        }
+    }
+
+    #[doc = "index_pattern 뒤의 YYYYMMDD 가 보관 기간을 지난 인덱스만 골라주는 함수"]
+    /// # Arguments
+    /// * `index_pattern` - 인덱스 접두어 (ex. os_metric_)
+    /// * `index_names` - index_pattern* 로 조회한 인덱스 목록
+    /// * `cutoff_date` - 이 날짜보다 이전 인덱스를 삭제 대상으로 본다.
+    ///
+    /// # Returns
+    /// * Vec<(String, NaiveDate)>
+    fn expired_indices(
+        &self,
+        index_pattern: &str,
+        index_names: Vec<String>,
+        cutoff_date: NaiveDate,
+    ) -> Vec<(String, NaiveDate)> {
+        let mut expired: Vec<(String, NaiveDate)> = index_names
+            .into_iter()
+            .filter_map(|index_name| {
+                let suffix: &str = index_name.strip_prefix(index_pattern)?;
+                /* 다른 접두어를 가진 인덱스(ex. os_metric_slowlog_20250101)는 날짜 파싱 단계에서 제외된다. */
+                if suffix.len() != 8 {
+                    return None;
+                }
+                let index_date: NaiveDate = get_naive_date_from_str(suffix, "%Y%m%d").ok()?;
+                (index_date < cutoff_date).then_some((index_name, index_date))
+            })
+            .collect();
+
+        expired.sort_by_key(|(_, index_date)| *index_date);
+        expired
+    }
+
+    #[doc = "감사 기록을 NDJSON 파일에 추가해주는 함수"]
+    fn write_audit(&self, record: &RetentionAuditRecord) {
+        let result: Result<(), anyhow::Error> = (|| {
+            let mut audit_file: File = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.retention_config.audit_log_path())?;
+            writeln!(audit_file, "{}", serde_json::to_string(record)?)?;
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            error!("[ERROR][RetentionServiceImpl->write_audit] {:?}", e);
+        }
+    }
+
+    #[doc = "lock 을 잡은 상태에서 보관 기간이 지난 인덱스를 삭제(또는 dry-run)해주는 함수"]
+    async fn delete_expired_indices(&self) -> Result<(), anyhow::Error> {
+        let cutoff_date: NaiveDate = get_current_utc_naivedate()
+            - chrono::Duration::days(*self.retention_config.retention_days() as i64);
+
+        let mut index_patterns: Vec<&String> = vec![self.es_conn.index_pattern(), self.es_conn.slowlog_index_pattern()];
+        index_patterns.dedup();
+
+        for index_pattern in index_patterns {
+            let index_names: Vec<String> = self
+                .es_conn
+                .get_index_names(&format!("{}*", index_pattern))
+                .await?;
+
+            for (index_name, index_date) in self.expired_indices(index_pattern, index_names, cutoff_date) {
+                let (action, error) = if *self.retention_config.dry_run() {
+                    info!("[RetentionServiceImpl->delete_expired_indices] [DRY-RUN] Would delete index '{}'", index_name);
+                    (String::from("dry_run"), None)
+                } else {
+                    match self.es_conn.delete_index(&index_name).await {
+                        Ok(_) => {
+                            info!("[RetentionServiceImpl->delete_expired_indices] Deleted index '{}'", index_name);
+                            (String::from("deleted"), None)
+                        }
+                        Err(e) => {
+                            error!("[ERROR][RetentionServiceImpl->delete_expired_indices] {:?}", e);
+                            (String::from("failed"), Some(e.to_string()))
+                        }
+                    }
+                };
+
+                self.write_audit(&RetentionAuditRecord::new(
+                    get_currnet_utc_str(),
+                    self.owner.clone(),
+                    index_name,
+                    index_date.format("%Y-%m-%d").to_string(),
+                    action,
+                    error,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl RetentionService for RetentionServiceImpl {
+    #[doc = "보관 기간이 지난 날짜별 인덱스를 정리해주는 함수 - 여러 에이전트 중 lock 을 잡은 하나만 수행한다."]
+    async fn cleanup_expired_indices(&self) -> Result<(), anyhow::Error> {
+        if self.es_conn.data_stream_config().is_some() {
+            info!("[RetentionServiceImpl->cleanup_expired_indices] Data stream mode uses cluster-side retention. Skip");
+            return Ok(());
+        }
+
+        let Some((seq_no, primary_term)) = self.acquire_lock().await? else {
+            return Ok(());
+        };
+
+        let result: Result<(), anyhow::Error> = self.delete_expired_indices().await;
+        self.release_lock(seq_no, primary_term).await;
+
+        result
+    }
+}
+
+#[doc = "문서 응답에서 _seq_no, _primary_term 을 꺼내주는 함수"]
+fn seq_no_and_primary_term(response: &Value) -> Option<(i64, i64)> {
+    Some((response["_seq_no"].as_i64()?, response["_primary_term"].as_i64()?))
+}
@@ -1,9 +1,13 @@
 use crate::common::*;
 
 use crate::model::network_packet::network_packet_info::*;
+use crate::model::network::iface_counters::*;
 use crate::model::network::network_socket_info::*;
 use crate::model::network::network_usage::*;
-use crate::model::memory::os_mem_res::*;
+use crate::model::memory::{mmap_info::*, os_mem_res::*, process_mem_res::*};
+use crate::model::process::thread_pool_info::*;
+use crate::model::jvm::gc_log_stats::*;
+use crate::model::slowlog::slowlog_stats::*;
 
 use crate::utils_module::math_utils::*;
 
@@ -82,6 +86,25 @@ impl MetricService for WindowsMetricServiceImpl {
         0.0
     }
 
+    #[doc = "드라이브 별 disk 사용률을 체크"]
+    fn get_disk_usage_by_mount(&mut self) -> BTreeMap<String, f64> {
+        self.system.refresh_disks_list();
+
+        self.system
+            .disks()
+            .iter()
+            .filter(|disk| disk.total_space() > 0)
+            .filter_map(|disk| {
+                let mount: &str = disk.mount_point().to_str()?;
+                let total_space: f64 = disk.total_space() as f64;
+                let used_space: f64 = total_space - disk.available_space() as f64;
+                let usage_percentage: f64 = (used_space / total_space) * 100.0;
+
+                Some((mount.to_string(), round2_f64(usage_percentage)))
+            })
+            .collect()
+    }
+
     #[doc = "memory 사용률을 체크"]
     fn get_memory_usage(&mut self) -> f32 {
         self.system.refresh_memory();
@@ -103,26 +126,37 @@ impl MetricService for WindowsMetricServiceImpl {
         let networks: &sysinfo::Networks = self.system.networks();
         let mut network_received: u64 = 0;
         let mut network_transmitted: u64 = 0;
+        let mut interfaces: BTreeMap<String, IfaceCounters> = BTreeMap::new();
 
-        for (_interface_name, network) in networks.iter() {
+        for (interface_name, network) in networks.iter() {
             network_received += network.received();
             network_transmitted += network.transmitted();
+            interfaces.insert(
+                interface_name.clone(),
+                IfaceCounters::new(network.total_received(), network.total_transmitted()),
+            );
         }
 
-        Ok(NetworkUsage::new(
+        let mut network_usage: NetworkUsage = NetworkUsage::new(
             network_received,
             network_transmitted,
             0,
             0,
             0,
             0,
-        ))
+        );
+        network_usage.interfaces = interfaces;
+
+        Ok(network_usage)
     }
 
-    #[doc = "현재 동작중인 프로세스의 개수"]
-    fn get_process_count(&mut self) -> usize {
+    #[doc = "프로세스 목록을 갱신해주는 함수 - 수집 주기마다 한 번 호출된다."]
+    fn refresh_processes(&mut self) {
         self.system.refresh_processes();
+    }
 
+    #[doc = "현재 동작중인 프로세스의 개수"]
+    fn get_process_count(&mut self) -> usize {
         let process_count: usize = self.system.processes().len();
 
         process_count
@@ -242,21 +276,50 @@ impl MetricService for WindowsMetricServiceImpl {
 
         let target_keywords: [&str; 3] = ["java", "jdk", "elasticsearch"];
 
-        self.system.refresh_all();
-
         let mut total_rss_byte: u64 = 0;
         let mut total_vms_byte: u64 = 0;
+        let mut processes: Vec<ProcessMemRes> = Vec::new();
 
-        for proc_ in self.system.processes().values() {
+        for (pid, proc_) in self.system.processes() {
             let name_lower: String = proc_.name().to_lowercase();
 
             if target_keywords.iter().any(|kw| name_lower.contains(&kw.to_lowercase())) {
                 /* sysinfo: memory()와 virtual_memory()는 KiB 단위 */ 
                 total_rss_byte += proc_.memory();
                 total_vms_byte += proc_.virtual_memory();
+                processes.push(ProcessMemRes::new(
+                    pid.as_u32(),
+                    proc_.name().to_string(),
+                    proc_.memory(),
+                    proc_.virtual_memory(),
+                ));
             }
         }
-        
-        Ok(OsMemRes::new(total_rss_byte, total_vms_byte))
+        processes.sort_by_key(|process| process.pid);
+
+        let mut os_mem_res: OsMemRes = OsMemRes::new(total_rss_byte, total_vms_byte);
+        os_mem_res.processes = processes;
+
+        Ok(os_mem_res)
+    }
+
+    #[doc = "Windows 에는 vm.max_map_count 제한이 없으므로 기본값을 반환"]
+    fn get_process_mmap_info(&mut self) -> Result<MmapInfo, anyhow::Error> {
+        Ok(MmapInfo::default())
+    }
+
+    #[doc = "Windows 에는 /proc/<pid>/task 가 없으므로 기본값을 반환"]
+    fn get_thread_pool_info(&mut self) -> Result<ThreadPoolInfo, anyhow::Error> {
+        Ok(ThreadPoolInfo::default())
+    }
+
+    #[doc = "Windows 모드에서는 gc.log 를 수집하지 않으므로 기본값을 반환"]
+    fn get_gc_log_stats(&mut self) -> Result<GcLogStats, anyhow::Error> {
+        Ok(GcLogStats::default())
+    }
+
+    #[doc = "Windows 모드에서는 slow log 를 수집하지 않으므로 빈 값을 반환"]
+    fn get_slowlog_stats(&mut self) -> Result<Vec<SlowLogStats>, anyhow::Error> {
+        Ok(Vec::new())
     }
 }
@@ -0,0 +1,7 @@
+use crate::common::*;
+
+use crate::model::elastic::es_document::*;
+
+pub trait BulkShipperService {
+    fn enqueue(&self, document: EsDocument) -> Result<(), anyhow::Error>;
+}
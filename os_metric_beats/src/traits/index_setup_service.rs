@@ -0,0 +1,6 @@
+use crate::common::*;
+
+#[async_trait]
+pub trait IndexSetupService {
+    async fn setup_index_templates(&self) -> Result<(), anyhow::Error>;
+}
@@ -3,12 +3,17 @@ use crate::common::*;
 use crate::model::network_packet::network_packet_info::*;
 use crate::model::network::network_socket_info::*;
 use crate::model::network::network_usage::*;
-use crate::model::memory::os_mem_res::*;
+use crate::model::memory::{mmap_info::*, os_mem_res::*};
+use crate::model::process::thread_pool_info::*;
+use crate::model::jvm::gc_log_stats::*;
+use crate::model::slowlog::slowlog_stats::*;
 
 pub trait MetricService {
+    fn refresh_processes(&mut self);
     fn get_cpu_usage(&mut self) -> f32;
     fn get_cpu_usage_avg_thread(&mut self) -> f32;
     fn get_disk_usage(&mut self) -> f32;
+    fn get_disk_usage_by_mount(&mut self) -> BTreeMap<String, f64>;
     fn get_memory_usage(&mut self) -> f32;
     fn get_network_usage(&mut self) -> Result<NetworkUsage, anyhow::Error>;
     fn get_process_count(&mut self) -> usize;
@@ -16,4 +21,8 @@ pub trait MetricService {
     fn get_socket_info_parsing(&mut self, socket_vec: &[&str]) -> (u64, u64);
     fn get_socket_info(&mut self) -> Result<NetworkSocketInfo, anyhow::Error>;
     fn get_process_mem_usage(&mut self) -> Result<OsMemRes, anyhow::Error>;
+    fn get_process_mmap_info(&mut self) -> Result<MmapInfo, anyhow::Error>;
+    fn get_thread_pool_info(&mut self) -> Result<ThreadPoolInfo, anyhow::Error>;
+    fn get_gc_log_stats(&mut self) -> Result<GcLogStats, anyhow::Error>;
+    fn get_slowlog_stats(&mut self) -> Result<Vec<SlowLogStats>, anyhow::Error>;
 }
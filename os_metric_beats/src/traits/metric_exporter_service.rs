@@ -0,0 +1,9 @@
+use crate::common::*;
+
+use crate::model::exporter::metric_snapshot::*;
+
+#[doc = "Elasticsearch 외의 모니터링 시스템으로 수집 결과를 내보내는 출력"]
+pub trait MetricExporterService: Send + Sync + std::fmt::Debug {
+    fn name(&self) -> &'static str;
+    fn export(&self, snapshot: &MetricSnapshot) -> Result<(), anyhow::Error>;
+}
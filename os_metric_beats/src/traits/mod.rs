@@ -1,2 +1,7 @@
+pub mod bulk_shipper_service;
+pub mod index_setup_service;
+pub mod metric_exporter_service;
 pub mod metirc_service;
-pub mod request_service;
\ No newline at end of file
+pub mod node_info_service;
+pub mod request_service;
+pub mod retention_service;
@@ -0,0 +1,8 @@
+use crate::common::*;
+
+use crate::model::elastic::es_node_identity::*;
+
+#[async_trait]
+pub trait NodeInfoService {
+    async fn get_node_identity(&mut self) -> Option<EsNodeIdentity>;
+}
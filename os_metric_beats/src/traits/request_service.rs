@@ -1,12 +1,22 @@
 use crate::common::*;
 
+use crate::model::exporter::metric_snapshot::*;
 use crate::model::metric_info::*;
+use crate::model::slowlog::slowlog_metric_info::*;
 
 #[async_trait]
 pub trait RequestService {
     async fn request_metric_to_elastic(
         &self,
-        index_name: String,
+        index_date: String,
         metric_info: MetricInfo,
     ) -> Result<(), anyhow::Error>;
+
+    async fn request_slowlog_to_elastic(
+        &self,
+        index_date: String,
+        slowlog_infos: Vec<SlowLogMetricInfo>,
+    ) -> Result<(), anyhow::Error>;
+
+    async fn request_metric_to_exporters(&self, snapshot: MetricSnapshot) -> Result<(), anyhow::Error>;
 }
@@ -0,0 +1,6 @@
+use crate::common::*;
+
+#[async_trait]
+pub trait RetentionService {
+    async fn cleanup_expired_indices(&self) -> Result<(), anyhow::Error>;
+}
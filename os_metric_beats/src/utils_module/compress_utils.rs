@@ -0,0 +1,16 @@
+use crate::common::*;
+
+use flate2::{write::GzEncoder, Compression};
+
+#[doc = "byte 배열을 gzip 으로 압축해주는 함수"]
+/// # Arguments
+/// * `data` - 압축할 데이터
+/// * `level` - 압축 레벨 (0~9, 높을수록 느리지만 작아짐)
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error>
+pub fn gzip_compress(data: &[u8], level: u32) -> Result<Vec<u8>, anyhow::Error> {
+    let mut encoder: GzEncoder<Vec<u8>> = GzEncoder::new(Vec::new(), Compression::new(level.min(9)));
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
@@ -0,0 +1,150 @@
+use crate::common::*;
+
+use std::io::{Read, Seek, SeekFrom};
+
+#[doc = "로그 파일을 tail -F 처럼 따라가며 새로 추가된 라인만 읽어주는 구조체"]
+/// 파일 핸들을 유지하고 있기 때문에 로그가 rename 방식으로 rotate 되더라도
+/// 이전 파일에 남은 라인을 끝까지 읽은 뒤 새 파일로 넘어간다.
+/// copytruncate 방식으로 파일 크기가 줄어든 경우에는 처음부터 다시 읽는다.
+#[derive(Debug)]
+pub struct FileTailer {
+    path: String,
+    file: Option<File>,
+    file_id: u64,
+    offset: u64,
+    partial_line: String,
+    /* 파일이 없다는 경고는 한번만 남긴다 (ex. GC 로그를 끈 경우) */
+    was_missing: bool,
+}
+
+impl FileTailer {
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            file: None,
+            file_id: 0,
+            offset: 0,
+            partial_line: String::new(),
+            was_missing: false,
+        }
+    }
+
+    #[doc = "새로 추가된 완성된 라인들을 반환해주는 함수"]
+    /// # Returns
+    /// * Result<Vec<String>, anyhow::Error> - 마지막 호출 이후 추가된 라인 목록
+    pub fn read_new_lines(&mut self) -> Result<Vec<String>, anyhow::Error> {
+        let mut lines: Vec<String> = Vec::new();
+
+        /* 최초 호출 시에는 과거 로그를 다시 읽지 않도록 파일 끝에서부터 시작하고,
+        없던 파일이 나중에 생긴 경우에는 처음부터 읽는다. */
+        if self.file.is_none() {
+            let seek_to_end: bool = !self.was_missing;
+
+            if self.open_file(seek_to_end)? && !seek_to_end {
+                self.drain_file(&mut lines)?;
+            }
+
+            return Ok(lines);
+        }
+
+        self.drain_file(&mut lines)?;
+
+        let metadata: fs::Metadata = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(_) => return Ok(lines), /* rotate 직후 아직 새 파일이 없는 경우 */
+        };
+
+        if file_identity(&metadata) != self.file_id {
+            /* rename 방식의 rotate -> 새 파일을 처음부터 읽는다. */
+            self.open_file(false)?;
+            self.drain_file(&mut lines)?;
+        } else if metadata.len() < self.offset {
+            /* copytruncate 방식의 rotate */
+            self.offset = 0;
+            self.partial_line.clear();
+
+            if let Some(file) = self.file.as_mut() {
+                file.seek(SeekFrom::Start(0))?;
+            }
+
+            self.drain_file(&mut lines)?;
+        }
+
+        Ok(lines)
+    }
+
+    #[doc = "대상 파일을 열어주는 함수 - 파일이 없으면 빈 파일처럼 취급하고 false 를 반환한다."]
+    fn open_file(&mut self, seek_to_end: bool) -> Result<bool, anyhow::Error> {
+        let mut file: File = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                if !self.was_missing {
+                    warn!("[WARN][FileTailer->open_file] '{}' does not exist yet, treating it as empty", self.path);
+                    self.was_missing = true;
+                }
+                return Ok(false);
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let metadata: fs::Metadata = file.metadata()?;
+
+        self.offset = if seek_to_end {
+            file.seek(SeekFrom::End(0))?
+        } else {
+            0
+        };
+        self.file_id = file_identity(&metadata);
+        self.partial_line.clear();
+        self.file = Some(file);
+
+        Ok(true)
+    }
+
+    #[doc = "현재 열려있는 파일을 끝까지 읽어서 완성된 라인만 꺼내주는 함수"]
+    fn drain_file(&mut self, lines: &mut Vec<String>) -> Result<(), anyhow::Error> {
+        let file: &mut File = match self.file.as_mut() {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        let mut buf: Vec<u8> = Vec::new();
+        let read_len: usize = file.read_to_end(&mut buf)?;
+        self.offset += read_len as u64;
+
+        self.partial_line.push_str(&String::from_utf8_lossy(&buf));
+
+        /* 개행으로 끝나지 않은 마지막 라인은 다음 호출까지 보관한다. */
+        if let Some(last_newline) = self.partial_line.rfind('\n') {
+            let rest: String = self.partial_line.split_off(last_newline + 1);
+
+            lines.extend(
+                self.partial_line
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(String::from),
+            );
+
+            self.partial_line = rest;
+        }
+
+        Ok(())
+    }
+}
+
+#[doc = "rotate 여부를 판단하기 위한 파일 식별값 (unix: inode)"]
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[doc = "rotate 여부를 판단하기 위한 파일 식별값 (inode 가 없는 OS 에서는 생성시각 사용)"]
+#[cfg(not(unix))]
+fn file_identity(metadata: &fs::Metadata) -> u64 {
+    metadata
+        .created()
+        .ok()
+        .and_then(|created| created.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
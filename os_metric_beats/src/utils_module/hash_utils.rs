@@ -0,0 +1,53 @@
+use sha2::{Digest, Sha256};
+
+#[doc = "여러 값을 이어서 항상 같은 결과가 나오는 문서 _id (SHA-256 hex)를 만들어주는 함수"]
+/// 값 사이에 구분자(0x1F)를 넣어서 ("ab", "c") 와 ("a", "bc") 가 같은 _id 가 되지 않게 한다.
+///
+/// # Arguments
+/// * `parts` - _id 를 구성하는 값 목록
+///
+/// # Returns
+/// * String
+pub fn deterministic_doc_id(parts: &[&str]) -> String {
+    let mut hasher: Sha256 = Sha256::new();
+
+    for (idx, part) in parts.iter().enumerate() {
+        if idx > 0 {
+            hasher.update([0x1f]);
+        }
+        hasher.update(part.as_bytes());
+    }
+
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_id_is_stable_across_calls_and_releases() {
+        let parts: [&str; 2] = ["node-1", "2025-01-01T00:00:00Z"];
+
+        /* 재시작/버전업 후에도 같은 _id 가 나와야 재전송 시 중복 문서가 생기지 않는다. */
+        assert_eq!(
+            deterministic_doc_id(&parts),
+            "25ff84b65eb335f4956182896ee6d20d4d5c7cbfd7ff4a67cd0ed3337bbd71a1"
+        );
+        assert_eq!(deterministic_doc_id(&parts), deterministic_doc_id(&parts));
+        assert_eq!(
+            deterministic_doc_id(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn doc_id_separates_parts() {
+        assert_ne!(deterministic_doc_id(&["ab", "c"]), deterministic_doc_id(&["a", "bc"]));
+        assert_ne!(deterministic_doc_id(&["a", "b"]), deterministic_doc_id(&["b", "a"]));
+    }
+}
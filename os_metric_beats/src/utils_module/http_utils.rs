@@ -0,0 +1,91 @@
+use crate::common::*;
+
+use crate::common_enums::retry_class::*;
+
+use crate::model::elastic::retry_config::*;
+
+#[doc = "Retry-After 헤더 값을 대기시간으로 바꿔주는 함수 - 초 단위 숫자와 HTTP-date 형식을 모두 지원한다."]
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value: &str = value.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let retry_at: DateTime<Utc> = DateTime::parse_from_rfc2822(value).ok()?.with_timezone(&Utc);
+    (retry_at - Utc::now()).to_std().ok()
+}
+
+#[doc = "body 를 POST 로 보내고, 429/502/503/504 와 연결 오류는 backoff 후 재시도해주는 함수 - exporter 출력들이 공통으로 사용한다."]
+/// 재시도 여부는 `RetryClass::from_status` 를 따르고, 그 외 상태코드는 다시 보내도 실패하므로 바로 에러를 반환한다.
+///
+/// # Arguments
+/// * `client` - 헤더/timeout 이 설정된 HTTP client
+/// * `url` - 전송 URL
+/// * `body` - 요청 body (재시도할 때마다 같은 값을 보낸다)
+/// * `retry_config` - 재시도 횟수, backoff, 총 재시도 시간
+/// * `tag` - 로그에 붙일 호출 위치 (ex. "OtlpWorker->send")
+///
+/// # Returns
+/// * Result<(), anyhow::Error>
+pub async fn post_with_retry(
+    client: &reqwest::Client,
+    url: &str,
+    body: Vec<u8>,
+    retry_config: &RetryConfig,
+    tag: &str,
+) -> Result<(), anyhow::Error> {
+    let retry_budget: Duration = Duration::from_secs(*retry_config.retry_budget_secs());
+    let started_at: Instant = Instant::now();
+    let mut attempt: u32 = 0;
+
+    loop {
+        let retry_after: Option<Duration> = match client.post(url).body(body.clone()).send().await {
+            Ok(response) if response.status().is_success() => return Ok(()),
+            Ok(response) => {
+                let status: u16 = response.status().as_u16();
+                let retry_after: Option<Duration> = response
+                    .headers()
+                    .get(RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(parse_retry_after);
+                let error_body: String = response.text().await.unwrap_or_default();
+
+                match RetryClass::from_status(status, retry_after) {
+                    RetryClass::Retryable { retry_after } => {
+                        warn!("[WARN][{}] Status Code: {}, {}", tag, status, error_body.trim());
+                        retry_after
+                    }
+                    RetryClass::Permanent => {
+                        return Err(anyhow!(
+                            "[ERROR][{}] Rejected: Status Code: {}, {}",
+                            tag,
+                            status,
+                            error_body.trim()
+                        ))
+                    }
+                }
+            }
+            Err(e) => {
+                warn!("[WARN][{}] {:?}", tag, e);
+                None
+            }
+        };
+
+        attempt += 1;
+        let delay: Duration = retry_config.backoff_delay(attempt, retry_after);
+
+        if attempt > *retry_config.max_retries() || started_at.elapsed() + delay > retry_budget {
+            return Err(anyhow!("[ERROR][{}] Giving up after {} attempts", tag, attempt));
+        }
+
+        warn!(
+            "[WARN][{}] Retrying in {:?} (attempt {}/{})",
+            tag,
+            delay,
+            attempt,
+            retry_config.max_retries()
+        );
+        sleep(delay).await;
+    }
+}
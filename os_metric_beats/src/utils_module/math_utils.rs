@@ -9,3 +9,27 @@ pub fn round2_f32(x: f64) -> f32 {
     let x_f32: f32 = x as f32;
     (x_f32 * 100.0).round() / 100.0
 }
+
+#[doc = "소수점 둘째짜리까지만 표현해주는 함수 - f64 그대로 유지"]
+pub fn round2_f64(x: f64) -> f64 {
+    (x * 100.0).round() / 100.0
+}
+
+#[doc = "nearest-rank 방식으로 백분위 값을 계산해주는 함수"]
+/// # Arguments
+/// * `values`     - 정렬되지 않은 값 목록
+/// * `percentile` - 구할 백분위 (0.0 ~ 100.0)
+///
+/// # Returns
+/// * f64 - 값이 없는 경우에는 0.0
+pub fn percentile(values: &[f64], percentile: f64) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted: Vec<f64> = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+
+    let rank: usize = ((percentile / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
@@ -1,4 +1,10 @@
+pub mod compress_utils;
+pub mod file_tail_utils;
+pub mod hash_utils;
+pub mod http_utils;
 pub mod io_utils;
 pub mod logger_utils;
 pub mod math_utils;
+pub mod thread_dump_utils;
 pub mod time_utils;
+pub mod tls_utils;
@@ -0,0 +1,100 @@
+use crate::common::*;
+
+use std::process::{Child, Command, Stdio};
+
+#[doc = "jcmd 가 응답하지 않을때 기다려줄 최대 시간"]
+const JCMD_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[doc = "별도 스레드에서 실행 중인 스레드 덤프 - native thread id -> 스레드 이름"]
+pub type ThreadDumpHandle = std::thread::JoinHandle<Result<HashMap<u32, String>, anyhow::Error>>;
+
+#[doc = "jcmd <pid> Thread.print 로 JVM 스레드 덤프를 떠서 native thread id 별 전체 스레드 이름을 구해주는 함수"]
+/// /proc/<pid>/task/<tid>/comm 은 15 byte 로 잘리기 때문에 `elasticsearch[node][search][T#3]` 같은
+/// 전체 이름은 스레드 덤프의 `nid` 로만 찾을 수 있다. ES API 와 무관하게 JVM attach 로 동작하며,
+/// jcmd 는 JVM 과 같은 사용자(또는 root)로 실행되어야 한다.
+/// jcmd 가 끝날 때까지(최대 JCMD_TIMEOUT) 호출한 스레드를 막으므로 async 수집 경로가 아닌 별도 스레드에서 호출해야 한다.
+///
+/// # Arguments
+/// * `jcmd_path` - jcmd 실행 파일 경로
+/// * `pid` - 대상 JVM 의 pid
+///
+/// # Returns
+/// * Result<HashMap<u32, String>, anyhow::Error> - native thread id -> 스레드 이름
+pub fn read_jvm_thread_names(jcmd_path: &str, pid: u32) -> Result<HashMap<u32, String>, anyhow::Error> {
+    let mut child: Child = Command::new(jcmd_path)
+        .arg(pid.to_string())
+        .arg("Thread.print")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| anyhow!("[ERROR][thread_dump_utils->read_jvm_thread_names] Failed to run '{}': {}", jcmd_path, e))?;
+
+    /* 출력이 pipe 버퍼를 넘으면 jcmd 가 멈추므로 종료를 기다리기 전에 다른 스레드에서 읽어둔다. */
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| anyhow!("[ERROR][thread_dump_utils->read_jvm_thread_names] jcmd stdout is not piped"))?;
+    let reader: std::thread::JoinHandle<String> = std::thread::spawn(move || {
+        let mut output: String = String::new();
+        let _ = std::io::Read::read_to_string(&mut stdout, &mut output);
+        output
+    });
+
+    let started_at: Instant = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let output: String = reader
+                .join()
+                .map_err(|_| anyhow!("[ERROR][thread_dump_utils->read_jvm_thread_names] Failed to read jcmd output"))?;
+
+            if !status.success() {
+                return Err(anyhow!(
+                    "[ERROR][thread_dump_utils->read_jvm_thread_names] jcmd exited with {} for pid={}",
+                    status,
+                    pid
+                ));
+            }
+
+            return Ok(parse_thread_dump(&output));
+        }
+
+        if started_at.elapsed() > JCMD_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!(
+                "[ERROR][thread_dump_utils->read_jvm_thread_names] jcmd timed out for pid={}",
+                pid
+            ));
+        }
+
+        std_sleep(Duration::from_millis(50));
+    }
+}
+
+#[doc = "Thread.print 출력에서 (nid, 스레드 이름) 을 꺼내주는 함수"]
+/// ex) `"elasticsearch[node-1][search][T#3]" #45 daemon prio=5 os_prio=0 ... nid=0x1a2b waiting on condition`
+/// nid 는 JDK 19 이전에는 16진수(0x..), 이후에는 10진수로 출력된다.
+fn parse_thread_dump(output: &str) -> HashMap<u32, String> {
+    let mut thread_names: HashMap<u32, String> = HashMap::new();
+
+    for line in output.lines().filter(|line| line.starts_with('"')) {
+        let (name, rest) = match line[1..].rsplit_once('"') {
+            Some(parsed) => parsed,
+            None => continue,
+        };
+
+        let nid: Option<u32> = rest
+            .split_whitespace()
+            .find_map(|token| token.strip_prefix("nid="))
+            .and_then(|nid| match nid.strip_prefix("0x") {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => nid.parse().ok(),
+            });
+
+        if let Some(nid) = nid {
+            thread_names.insert(nid, name.to_string());
+        }
+    }
+
+    thread_names
+}
@@ -0,0 +1,331 @@
+use crate::common::*;
+
+use crate::common_enums::tls_verification_mode::*;
+use crate::model::elastic::tls_config::*;
+use crate::model::exporter::listen_tls_config::*;
+
+use elasticsearch::{
+    auth::ClientCertificate,
+    cert::{Certificate, CertificateValidation},
+};
+use anyhow::Context;
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use sha2::{Digest, Sha256};
+use std::{io::Read, sync::Mutex, time::SystemTime};
+
+#[doc = "인증서 지문 비교를 위해 ':' 구분자와 대소문자를 정규화해주는 함수"]
+fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+#[doc = "DER 인증서의 SHA-256 지문(hex)을 구해주는 함수"]
+fn sha256_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[doc = "서버 인증서 체인 중 지문이 일치하는 인증서를 신뢰 기준으로 삼아 실제 handshake 에서 검증하는 검증기"]
+/// 지문이 일치하는 인증서를 ca_path 의 CA 와 함께 trust anchor 로 두고 체인/호스트명까지 WebPKI 로 검증한다.
+/// 통과하면 일치한 인증서를 기억해두어 관리 API 용 transport 의 신뢰 목록으로 재사용한다.
+pub struct PinnedCertVerifier {
+    fingerprint: String,
+    ca_certs: Vec<rustls::Certificate>,
+    matched: Mutex<Option<Vec<u8>>>,
+}
+
+impl PinnedCertVerifier {
+    pub fn new(fingerprint: &str, ca_certs: &[Vec<u8>]) -> Self {
+        PinnedCertVerifier {
+            fingerprint: normalize_fingerprint(fingerprint),
+            ca_certs: ca_certs.iter().cloned().map(rustls::Certificate).collect(),
+            matched: Mutex::new(None),
+        }
+    }
+
+    #[doc = "마지막으로 검증에 성공한 handshake 에서 지문이 일치한 인증서(DER)"]
+    pub fn matched(&self) -> Option<Vec<u8>> {
+        self.matched.lock().ok().and_then(|matched| matched.clone())
+    }
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        intermediates: &[rustls::Certificate],
+        server_name: &rustls::ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let pinned: &rustls::Certificate = std::iter::once(end_entity)
+            .chain(intermediates.iter())
+            .find(|cert| sha256_fingerprint(&cert.0) == self.fingerprint)
+            .ok_or_else(|| {
+                rustls::Error::General(String::from("no certificate in the server chain matches ca_fingerprint"))
+            })?;
+
+        let mut roots: rustls::RootCertStore = rustls::RootCertStore::empty();
+        for cert in self.ca_certs.iter().chain(std::iter::once(pinned)) {
+            roots.add(cert)?;
+        }
+
+        let verified: ServerCertVerified = WebPkiVerifier::new(roots, None).verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )?;
+
+        if let Ok(mut matched) = self.matched.lock() {
+            *matched = Some(pinned.0.clone());
+        }
+        Ok(verified)
+    }
+}
+
+#[doc = "ca_fingerprint 로 고정한 인증서를 handshake 중에 검증하는 rustls client 설정을 만들어주는 함수"]
+/// # Arguments
+/// * `tls_config` - ES 출력 TLS 설정 (ca_fingerprint 와 mTLS 인증서)
+/// * `ca_certs` - ca_path 에서 읽은 CA 인증서(DER) 목록
+///
+/// # Returns
+/// * Result<(rustls::ClientConfig, Arc<PinnedCertVerifier>), anyhow::Error>
+pub fn build_pinned_tls_config(
+    tls_config: &TlsConfig,
+    ca_certs: &[Vec<u8>],
+) -> Result<(rustls::ClientConfig, Arc<PinnedCertVerifier>), anyhow::Error> {
+    let fingerprint: &str = tls_config
+        .ca_fingerprint()
+        .as_deref()
+        .ok_or_else(|| anyhow!("[ERROR][tls_utils->build_pinned_tls_config] ca_fingerprint is not set"))?;
+    let verifier: Arc<PinnedCertVerifier> = Arc::new(PinnedCertVerifier::new(fingerprint, ca_certs));
+
+    let builder = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier.clone());
+
+    let client_config: rustls::ClientConfig = match (tls_config.client_cert_path(), tls_config.client_key_path()) {
+        (Some(cert_path), Some(key_path)) => {
+            let certs: Vec<rustls::Certificate> = rustls_pemfile::certs(&mut BufReader::new(File::open(cert_path)?))?
+                .into_iter()
+                .map(rustls::Certificate)
+                .collect();
+            builder.with_client_auth_cert(certs, read_private_key(key_path)?)?
+        }
+        (None, None) => builder.with_no_client_auth(),
+        _ => {
+            return Err(anyhow!(
+                "[ERROR][tls_utils->build_pinned_tls_config] client_cert_path and client_key_path must be set together"
+            ))
+        }
+    };
+
+    Ok((client_config, verifier))
+}
+
+#[doc = "ES 노드와 TLS handshake 를 한번 수행해서 지문이 일치하는 CA 인증서(DER)를 가져오는 함수"]
+/// 관리 API 용 transport 는 검증기를 직접 넣을 수 없으므로, 필요할 때 비동기로 한 번 확인해서 신뢰 목록에 추가한다.
+///
+/// # Arguments
+/// * `es_url` - 대상 Elasticsearch 노드 주소
+/// * `tls_config` - ES 출력 TLS 설정
+/// * `ca_certs` - ca_path 에서 읽은 CA 인증서(DER) 목록
+/// * `timeout` - 연결과 handshake 에 쓸 수 있는 최대 시간
+///
+/// # Returns
+/// * Result<Vec<u8>, anyhow::Error>
+pub async fn fetch_pinned_certificate(
+    es_url: &Url,
+    tls_config: &TlsConfig,
+    ca_certs: &[Vec<u8>],
+    timeout: Duration,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let host: &str = es_url
+        .host_str()
+        .ok_or_else(|| anyhow!("[ERROR][tls_utils->fetch_pinned_certificate] URL has no host: {}", es_url))?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let port: u16 = es_url.port_or_known_default().unwrap_or(9200);
+
+    let (client_config, verifier) = build_pinned_tls_config(tls_config, ca_certs)?;
+    let server_name: rustls::ServerName = rustls::ServerName::try_from(host)
+        .map_err(|e| anyhow!("[ERROR][tls_utils->fetch_pinned_certificate] Invalid server name '{}': {}", host, e))?;
+    let connector: tokio_rustls::TlsConnector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+
+    tokio::time::timeout(timeout, async {
+        let stream: tokio::net::TcpStream = tokio::net::TcpStream::connect((host, port)).await?;
+        connector.connect(server_name, stream).await
+    })
+    .await
+    .unwrap_or_else(|_| Err(std::io::Error::from(std::io::ErrorKind::TimedOut)))
+    .with_context(|| format!("[ERROR][tls_utils->fetch_pinned_certificate] TLS handshake with {}:{} failed", host, port))?;
+
+    verifier.matched().ok_or_else(|| {
+        anyhow!(
+            "[ERROR][tls_utils->fetch_pinned_certificate] No certificate from {}:{} matches ca_fingerprint",
+            host,
+            port
+        )
+    })
+}
+
+#[doc = "PEM 파일에서 첫번째 개인키(PKCS#8, RSA, EC)를 읽어주는 함수"]
+fn read_private_key(path: &str) -> Result<rustls::PrivateKey, anyhow::Error> {
+    rustls_pemfile::read_all(&mut BufReader::new(File::open(path)?))?
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(rustls::PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| anyhow!("[ERROR][tls_utils->read_private_key] No private key found in '{}'", path))
+}
+
+#[doc = "파일 전체를 byte 로 읽어주는 함수"]
+fn read_file_bytes(path: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let mut file: File = File::open(path)
+        .map_err(|e| anyhow!("[ERROR][tls_utils->read_file_bytes] Failed to open '{}': {}", path, e))?;
+    let mut buf: Vec<u8> = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(buf)
+}
+
+#[doc = "ca_path 에서 신뢰할 CA 인증서(DER) 목록을 읽어주는 함수 - 검증을 끈 경우에는 비어있다."]
+/// ca_fingerprint 로 고정한 인증서는 여기에 포함되지 않으며, handshake 시점에 `PinnedCertVerifier` 가 확인한다.
+///
+/// # Arguments
+/// * `tls_config` - ES 출력 TLS 설정
+///
+/// # Returns
+/// * Result<Vec<Vec<u8>>, anyhow::Error> - 비어있으면 시스템 기본 신뢰 목록을 사용
+pub fn load_trusted_ca_certs(tls_config: &TlsConfig) -> Result<Vec<Vec<u8>>, anyhow::Error> {
+    if *tls_config.verification_mode() == TlsVerificationMode::None {
+        return Ok(Vec::new());
+    }
+
+    let Some(ca_path) = tls_config.ca_path() else {
+        return Ok(Vec::new());
+    };
+
+    let ca_certs: Vec<Vec<u8>> = rustls_pemfile::certs(&mut BufReader::new(File::open(ca_path).map_err(|e| {
+        anyhow!("[ERROR][tls_utils->load_trusted_ca_certs] Failed to open '{}': {}", ca_path, e)
+    })?))?;
+
+    if ca_certs.is_empty() {
+        return Err(anyhow!(
+            "[ERROR][tls_utils->load_trusted_ca_certs] No certificate found in '{}'",
+            ca_path
+        ));
+    }
+
+    Ok(ca_certs)
+}
+
+#[doc = "TLS 설정으로부터 해당 노드에 사용할 서버 인증서 검증 방식을 만들어주는 함수"]
+/// # Arguments
+/// * `tls_config` - ES 출력 TLS 설정
+/// * `trusted_certs` - load_trusted_ca_certs 로 구한 신뢰할 CA 인증서(DER) 목록
+/// * `es_url` - 대상 Elasticsearch 노드 주소
+///
+/// # Returns
+/// * Result<CertificateValidation, anyhow::Error>
+pub fn build_cert_validation(
+    tls_config: &TlsConfig,
+    trusted_certs: &[Vec<u8>],
+    es_url: &Url,
+) -> Result<CertificateValidation, anyhow::Error> {
+    if *tls_config.verification_mode() == TlsVerificationMode::None {
+        if tls_config.ca_fingerprint().is_some() {
+            warn!("[WARN][tls_utils->build_cert_validation] verification_mode = none, ca_fingerprint is ignored");
+        }
+        warn!("[WARN][tls_utils->build_cert_validation] TLS certificate verification is disabled for {}", es_url);
+        return Ok(CertificateValidation::None);
+    }
+
+    let mut trusted: Option<Certificate> = None;
+    for der in trusted_certs {
+        let cert: Certificate = Certificate::from_der(der)?;
+        match trusted.as_mut() {
+            Some(certs) => certs.append(cert),
+            None => trusted = Some(cert),
+        }
+    }
+
+    Ok(match trusted {
+        Some(certs) => CertificateValidation::Full(certs),
+        None => CertificateValidation::Default,
+    })
+}
+
+#[doc = "mTLS 용 클라이언트 개인키와 인증서 체인을 하나의 PEM 으로 읽어주는 함수"]
+/// # Arguments
+/// * `tls_config` - ES 출력 TLS 설정
+///
+/// # Returns
+/// * Result<Option<Vec<u8>>, anyhow::Error> - 인증서/개인키가 설정되지 않았다면 None
+pub fn read_client_identity_pem(tls_config: &TlsConfig) -> Result<Option<Vec<u8>>, anyhow::Error> {
+    match (tls_config.client_cert_path(), tls_config.client_key_path()) {
+        (Some(cert_path), Some(key_path)) => {
+            /* rustls 백엔드는 개인키와 인증서 체인을 하나의 PEM 으로 받는다 */
+            let mut pem: Vec<u8> = read_file_bytes(key_path)?;
+            pem.push(b'\n');
+            pem.extend(read_file_bytes(cert_path)?);
+            Ok(Some(pem))
+        }
+        (None, None) => Ok(None),
+        _ => Err(anyhow!(
+            "[ERROR][tls_utils->read_client_identity_pem] client_cert_path and client_key_path must be set together"
+        )),
+    }
+}
+
+#[doc = "mTLS 용 클라이언트 인증서 자격증명을 만들어주는 함수"]
+/// # Arguments
+/// * `tls_config` - ES 출력 TLS 설정
+///
+/// # Returns
+/// * Result<Option<Credentials>, anyhow::Error> - 인증서/개인키가 설정되지 않았다면 None
+pub fn build_client_certificate(tls_config: &TlsConfig) -> Result<Option<Credentials>, anyhow::Error> {
+    Ok(read_client_identity_pem(tls_config)?
+        .map(|pem| Credentials::Certificate(ClientCertificate::Pem(pem))))
+}
+
+#[doc = "PEM 인증서/개인키로 내장 HTTP 서버용 TLS acceptor 를 만들어주는 함수"]
+/// # Arguments
+/// * `tls_config` - 서버 인증서(chain 포함)와 개인키(PKCS#8, RSA, EC) 경로
+///
+/// # Returns
+/// * Result<tokio_rustls::TlsAcceptor, anyhow::Error>
+pub fn build_tls_acceptor(tls_config: &ListenTlsConfig) -> Result<tokio_rustls::TlsAcceptor, anyhow::Error> {
+    let certs: Vec<rustls::Certificate> =
+        rustls_pemfile::certs(&mut BufReader::new(File::open(tls_config.cert_path())?))?
+            .into_iter()
+            .map(rustls::Certificate)
+            .collect();
+
+    if certs.is_empty() {
+        return Err(anyhow!(
+            "[ERROR][build_tls_acceptor] No certificate found in '{}'",
+            tls_config.cert_path()
+        ));
+    }
+
+    let key: rustls::PrivateKey = read_private_key(tls_config.key_path())?;
+
+    let server_config: rustls::ServerConfig = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)?;
+
+    Ok(tokio_rustls::TlsAcceptor::from(Arc::new(server_config)))
+}